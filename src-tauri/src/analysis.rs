@@ -0,0 +1,1113 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/analysis.rs
+
+use crate::bulk::{BulkAction, BulkBuilder};
+use crate::confirmations::require_confirmation;
+use crate::elasticsearch::{
+    connection_index, connection_track_total_hits, parse_query_result, QueryResult,
+};
+use crate::transport::{encode_segment, Transport};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use tauri::{command, Window};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlattenedField {
+    // Dotted path, multi-fields included (e.g. "title.keyword")
+    pub path: String,
+    pub field_type: String,
+    pub analyzed: bool,
+    pub doc_values: bool,
+    pub searchable: bool,
+    // Set for multi-fields, the path of the field they hang off
+    pub parent: Option<String>,
+    pub runtime: bool,
+    // Every type the path has across the matched indices, more than one means a conflict
+    pub types: Vec<String>,
+}
+
+// Types that never have doc values, whatever the mapping says
+const NO_DOC_VALUES_TYPES: &[&str] = &[
+    "text",
+    "match_only_text",
+    "annotated_text",
+    "object",
+    "nested",
+];
+
+fn field_type_of(definition: &serde_json::Value) -> String {
+    match definition["type"].as_str() {
+        Some(field_type) => field_type.to_string(),
+        // Only objects get to leave the type out
+        None => "object".to_string(),
+    }
+}
+
+fn describe_field(
+    path: String,
+    definition: &serde_json::Value,
+    parent: Option<String>,
+    runtime: bool,
+) -> FlattenedField {
+    let field_type = field_type_of(definition);
+    let searchable = !runtime && definition["index"].as_bool().unwrap_or(true);
+    let doc_values = !runtime
+        && !NO_DOC_VALUES_TYPES.contains(&field_type.as_str())
+        && definition["doc_values"].as_bool().unwrap_or(true);
+
+    FlattenedField {
+        path,
+        analyzed: searchable
+            && matches!(
+                field_type.as_str(),
+                "text" | "match_only_text" | "annotated_text" | "search_as_you_type"
+            ),
+        doc_values,
+        searchable,
+        parent,
+        runtime,
+        types: vec![field_type.clone()],
+        field_type,
+    }
+}
+
+fn walk_properties(properties: &serde_json::Value, prefix: &str, fields: &mut Vec<FlattenedField>) {
+    let Some(properties) = properties.as_object() else {
+        return;
+    };
+
+    for (name, definition) in properties {
+        let path = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+
+        fields.push(describe_field(path.clone(), definition, None, false));
+
+        if let Some(multi_fields) = definition["fields"].as_object() {
+            for (sub_name, sub_definition) in multi_fields {
+                fields.push(describe_field(
+                    format!("{}.{}", path, sub_name),
+                    sub_definition,
+                    Some(path.clone()),
+                    false,
+                ));
+            }
+        }
+
+        walk_properties(&definition["properties"], &path, fields);
+    }
+}
+
+// Flattens a single index's mapping, runtime fields shadow mapped ones at query time
+fn flatten_mapping(mapping: &serde_json::Value) -> Vec<FlattenedField> {
+    let mut fields = Vec::new();
+    walk_properties(&mapping["properties"], "", &mut fields);
+
+    if let Some(runtime) = mapping["runtime"].as_object() {
+        for (path, definition) in runtime {
+            fields.retain(|field| &field.path != path);
+            fields.push(describe_field(path.clone(), definition, None, true));
+        }
+    }
+
+    fields
+}
+
+#[command]
+pub async fn get_flattened_fields(
+    window: Window,
+    index: String,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<FlattenedField>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let mappings = transport
+        .get_json_cached(&format!("/{}/_mapping", encode_segment(&index)))
+        .await?;
+
+    // Index patterns match several indices, merge them by path and keep track of type conflicts
+    let mut merged: BTreeMap<String, FlattenedField> = BTreeMap::new();
+    for index_mapping in mappings.as_object().into_iter().flat_map(|m| m.values()) {
+        for field in flatten_mapping(&index_mapping["mappings"]) {
+            match merged.get_mut(&field.path) {
+                Some(existing) => {
+                    if !existing.types.contains(&field.field_type) {
+                        existing.types.push(field.field_type);
+                    }
+                }
+                None => {
+                    merged.insert(field.path.clone(), field);
+                }
+            }
+        }
+    }
+
+    Ok(merged.into_values().collect())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermCount {
+    pub value: serde_json::Value,
+    // Date and ip buckets also come back formatted
+    pub value_as_string: Option<String>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldStats {
+    pub field: String,
+    // The field the aggregations actually ran on, e.g. "title.keyword" for a text field
+    pub aggregated_field: String,
+    pub field_type: String,
+    // "numeric", "date" or "terms"
+    pub kind: String,
+    pub total_docs: u64,
+    pub value_count: u64,
+    pub missing: u64,
+    pub cardinality: u64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub avg: Option<f64>,
+    pub min_as_string: Option<String>,
+    pub max_as_string: Option<String>,
+    pub percentiles: BTreeMap<String, f64>,
+    pub top_terms: Vec<TermCount>,
+}
+
+const NUMERIC_TYPES: &[&str] = &[
+    "long",
+    "integer",
+    "short",
+    "byte",
+    "double",
+    "float",
+    "half_float",
+    "scaled_float",
+    "unsigned_long",
+];
+
+// Looks the field up in the mapping and picks something aggregatable, falling back to a keyword multi-field
+async fn resolve_aggregatable_field(
+    transport: &Transport,
+    index: &str,
+    field: &str,
+) -> Result<FlattenedField, String> {
+    let mappings = transport
+        .get_json_cached(&format!("/{}/_mapping", encode_segment(index)))
+        .await?;
+
+    let fields: Vec<FlattenedField> = mappings
+        .as_object()
+        .into_iter()
+        .flat_map(|m| m.values())
+        .flat_map(|index_mapping| flatten_mapping(&index_mapping["mappings"]))
+        .collect();
+
+    let found = fields
+        .iter()
+        .find(|f| f.path == field)
+        .ok_or_else(|| format!("Field '{}' is not mapped in {}", field, index))?;
+
+    if found.doc_values || found.runtime {
+        return Ok(found.clone());
+    }
+
+    fields
+        .iter()
+        .find(|f| f.parent.as_deref() == Some(field) && f.doc_values)
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "Field '{}' ({}) has no doc values and no keyword sub-field to aggregate on",
+                field, found.field_type
+            )
+        })
+}
+
+fn parse_term_buckets(buckets: &serde_json::Value) -> Vec<TermCount> {
+    buckets
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|bucket| TermCount {
+            value: bucket["key"].clone(),
+            value_as_string: bucket["key_as_string"].as_str().map(|s| s.to_string()),
+            count: bucket["doc_count"].as_u64().unwrap_or(0),
+        })
+        .collect()
+}
+
+#[command]
+pub async fn get_field_stats(
+    window: Window,
+    index: String,
+    field: String,
+    timeout_ms: Option<u64>,
+) -> Result<FieldStats, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let index = connection_index(transport.connection(), &index)?;
+    let target = resolve_aggregatable_field(&transport, &index, &field).await?;
+
+    let kind = if NUMERIC_TYPES.contains(&target.field_type.as_str()) {
+        "numeric"
+    } else if matches!(target.field_type.as_str(), "date" | "date_nanos") {
+        "date"
+    } else {
+        "terms"
+    };
+
+    let mut aggs = serde_json::json!({
+        "value_count": { "value_count": { "field": target.path } },
+        "missing": { "missing": { "field": target.path } },
+        "cardinality": { "cardinality": { "field": target.path } }
+    });
+    if kind == "terms" {
+        aggs["top_terms"] = serde_json::json!({ "terms": { "field": target.path, "size": 10 } });
+    } else {
+        aggs["stats"] = serde_json::json!({ "stats": { "field": target.path } });
+        aggs["percentiles"] = serde_json::json!({
+            "percentiles": { "field": target.path, "percents": [1, 5, 25, 50, 75, 95, 99] }
+        });
+    }
+
+    let body = serde_json::json!({
+        "size": 0,
+        "track_total_hits": connection_track_total_hits(transport.connection()),
+        "aggs": aggs
+    });
+    let result = transport
+        .json(
+            Method::POST,
+            &format!("/{}/_search", encode_segment(&index)),
+            Some(&body),
+        )
+        .await?;
+    let aggregations = &result["aggregations"];
+
+    let percentiles = aggregations["percentiles"]["values"]
+        .as_object()
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|(percent, value)| value.as_f64().map(|v| (percent.clone(), v)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(FieldStats {
+        field,
+        aggregated_field: target.path.clone(),
+        field_type: target.field_type.clone(),
+        kind: kind.to_string(),
+        total_docs: result["hits"]["total"]["value"].as_u64().unwrap_or(0),
+        value_count: aggregations["value_count"]["value"].as_u64().unwrap_or(0),
+        missing: aggregations["missing"]["doc_count"].as_u64().unwrap_or(0),
+        cardinality: aggregations["cardinality"]["value"].as_u64().unwrap_or(0),
+        min: aggregations["stats"]["min"].as_f64(),
+        max: aggregations["stats"]["max"].as_f64(),
+        avg: aggregations["stats"]["avg"].as_f64(),
+        min_as_string: aggregations["stats"]["min_as_string"]
+            .as_str()
+            .map(|s| s.to_string()),
+        max_as_string: aggregations["stats"]["max_as_string"]
+            .as_str()
+            .map(|s| s.to_string()),
+        percentiles,
+        top_terms: parse_term_buckets(&aggregations["top_terms"]["buckets"]),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopFieldValues {
+    pub field: String,
+    pub aggregated_field: String,
+    // Documents matching the filter
+    pub total_docs: u64,
+    pub values: Vec<TermCount>,
+    // Matching documents whose value fell outside the top `size`
+    pub other_count: u64,
+    pub missing: u64,
+}
+
+#[command]
+pub async fn get_top_field_values(
+    window: Window,
+    index: String,
+    field: String,
+    size: Option<u32>,
+    query: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<TopFieldValues, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let index = connection_index(transport.connection(), &index)?;
+    let target = resolve_aggregatable_field(&transport, &index, &field).await?;
+
+    let mut body = serde_json::json!({
+        "size": 0,
+        "track_total_hits": connection_track_total_hits(transport.connection()),
+        "aggs": {
+            "top_values": { "terms": { "field": target.path, "size": size.unwrap_or(10).max(1) } },
+            "missing": { "missing": { "field": target.path } }
+        }
+    });
+    if let Some(query) = query {
+        body["query"] = query;
+    }
+
+    let result = transport
+        .json(
+            Method::POST,
+            &format!("/{}/_search", encode_segment(&index)),
+            Some(&body),
+        )
+        .await?;
+    let aggregations = &result["aggregations"];
+
+    Ok(TopFieldValues {
+        field,
+        aggregated_field: target.path,
+        total_docs: result["hits"]["total"]["value"].as_u64().unwrap_or(0),
+        values: parse_term_buckets(&aggregations["top_values"]["buckets"]),
+        other_count: aggregations["top_values"]["sum_other_doc_count"]
+            .as_u64()
+            .unwrap_or(0),
+        missing: aggregations["missing"]["doc_count"].as_u64().unwrap_or(0),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeSeriesMetric {
+    // "avg", "sum", "min", "max", "cardinality" or "value_count"
+    pub agg: String,
+    pub field: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeBucket {
+    // Bucket start in epoch millis
+    pub timestamp: i64,
+    pub key_as_string: Option<String>,
+    pub doc_count: u64,
+    // The metric for the bucket; None for empty buckets or when no metric was asked for
+    pub value: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeSeries {
+    // The interval the buckets ended up with, ES picks it when "auto" was requested
+    pub interval: String,
+    pub total_docs: u64,
+    pub buckets: Vec<TimeBucket>,
+}
+
+const TIME_SERIES_METRICS: &[&str] = &["avg", "sum", "min", "max", "cardinality", "value_count"];
+const AUTO_HISTOGRAM_BUCKETS: u32 = 60;
+
+// Single calendar units ("1d", "1M") follow the calendar, everything else ("90s", "6h") is fixed
+fn histogram_interval_kind(interval: &str) -> &'static str {
+    let calendar_units = ["m", "h", "d", "w", "M", "q", "y"];
+    match interval.strip_prefix('1') {
+        Some(unit) if calendar_units.contains(&unit) => "calendar_interval",
+        _ => "fixed_interval",
+    }
+}
+
+#[command]
+pub async fn get_time_series(
+    window: Window,
+    index: String,
+    time_field: String,
+    interval: Option<String>,
+    query: Option<serde_json::Value>,
+    metric: Option<TimeSeriesMetric>,
+    timeout_ms: Option<u64>,
+) -> Result<TimeSeries, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let index = connection_index(transport.connection(), &index)?;
+
+    if let Some(metric) = &metric {
+        if !TIME_SERIES_METRICS.contains(&metric.agg.as_str()) {
+            return Err(format!(
+                "Unsupported metric '{}', expected one of: {}",
+                metric.agg,
+                TIME_SERIES_METRICS.join(", ")
+            ));
+        }
+    }
+
+    let interval = interval
+        .map(|i| i.trim().to_string())
+        .filter(|i| !i.is_empty() && i != "auto");
+
+    let mut histogram = match &interval {
+        Some(interval) => {
+            let kind = histogram_interval_kind(interval);
+            // min_doc_count 0 fills the gaps so every bucket between the first and last is present
+            serde_json::json!({
+                "date_histogram": {
+                    "field": time_field,
+                    kind: interval,
+                    "min_doc_count": 0
+                }
+            })
+        }
+        None => serde_json::json!({
+            "auto_date_histogram": {
+                "field": time_field,
+                "buckets": AUTO_HISTOGRAM_BUCKETS
+            }
+        }),
+    };
+    if let Some(metric) = &metric {
+        histogram["aggs"] = serde_json::json!({
+            "metric": { metric.agg.as_str(): { "field": metric.field } }
+        });
+    }
+
+    let mut body = serde_json::json!({
+        "size": 0,
+        "track_total_hits": connection_track_total_hits(transport.connection()),
+        "aggs": { "series": histogram }
+    });
+    if let Some(query) = query {
+        body["query"] = query;
+    }
+
+    let result = transport
+        .json(
+            Method::POST,
+            &format!("/{}/_search", encode_segment(&index)),
+            Some(&body),
+        )
+        .await?;
+    let series = &result["aggregations"]["series"];
+
+    let buckets = series["buckets"]
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|bucket| TimeBucket {
+            timestamp: bucket["key"].as_i64().unwrap_or(0),
+            key_as_string: bucket["key_as_string"].as_str().map(|s| s.to_string()),
+            doc_count: bucket["doc_count"].as_u64().unwrap_or(0),
+            value: bucket["metric"]["value"].as_f64(),
+        })
+        .collect();
+
+    Ok(TimeSeries {
+        interval: interval
+            .or_else(|| series["interval"].as_str().map(|s| s.to_string()))
+            .unwrap_or_default(),
+        total_docs: result["hits"]["total"]["value"].as_u64().unwrap_or(0),
+        buckets,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldQuality {
+    pub path: String,
+    pub field_type: String,
+    // Counts over the sampled documents
+    pub present: u64,
+    pub null_count: u64,
+    pub missing: u64,
+    // (null + missing) / sampled documents
+    pub empty_rate: f64,
+    // JSON type -> number of values seen with it
+    pub observed_types: BTreeMap<String, u64>,
+    // Values whose JSON type doesn't fit the mapped type (ES may still coerce them)
+    pub mismatched: u64,
+    // Only computed for keyword fields, over the whole index
+    pub cardinality: Option<u64>,
+    pub high_cardinality: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexQualityReport {
+    pub index: String,
+    pub total_docs: u64,
+    pub sampled_docs: u64,
+    pub fields: Vec<FieldQuality>,
+    // Present in the sampled sources but not in the mapping (dynamic: false or runtime-only data)
+    pub unmapped_fields: Vec<String>,
+    pub type_mismatch_fields: Vec<String>,
+    pub high_cardinality_fields: Vec<String>,
+}
+
+// The quality check samples for coverage, so the connection's page size doesn't apply
+const DEFAULT_QUALITY_SAMPLE_SIZE: u32 = 500;
+pub(crate) const MAX_SAMPLE_SIZE: u32 = 10_000;
+// A keyword field is flagged when nearly every value is unique and there are lots of them
+const HIGH_CARDINALITY_RATIO: f64 = 0.95;
+const HIGH_CARDINALITY_MIN_VALUES: u64 = 1000;
+const MAX_CARDINALITY_FIELDS: usize = 100;
+
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+fn fits_mapped_type(field_type: &str, value: &serde_json::Value) -> bool {
+    if value.is_null() {
+        return true;
+    }
+
+    match field_type {
+        t if NUMERIC_TYPES.contains(&t) => value.is_number(),
+        "date" | "date_nanos" => value.is_string() || value.is_number(),
+        "boolean" => value.is_boolean(),
+        "keyword" | "constant_keyword" | "wildcard" | "text" | "match_only_text" | "ip"
+        | "version" => value.is_string(),
+        "flattened" => value.is_object(),
+        // geo types and anything exotic accept too many shapes to judge
+        _ => true,
+    }
+}
+
+// Sources can nest objects or use dotted keys ("a.b": 1), try both at every level
+fn collect_source_values<'a>(
+    value: &'a serde_json::Value,
+    segments: &[&str],
+    out: &mut Vec<&'a serde_json::Value>,
+) {
+    if segments.is_empty() {
+        match value {
+            serde_json::Value::Array(items) => out.extend(items.iter()),
+            other => out.push(other),
+        }
+        return;
+    }
+
+    match value {
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_source_values(item, segments, out);
+            }
+        }
+        serde_json::Value::Object(object) => {
+            for split in 1..=segments.len() {
+                if let Some(child) = object.get(&segments[..split].join(".")) {
+                    collect_source_values(child, &segments[split..], out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_source_paths(value: &serde_json::Value, prefix: &str, out: &mut BTreeSet<String>) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, child) in object {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                if child.is_object() {
+                    collect_source_paths(child, &path, out);
+                } else if let Some(items) = child
+                    .as_array()
+                    .filter(|items| items.iter().any(|i| i.is_object()))
+                {
+                    for item in items {
+                        collect_source_paths(item, &path, out);
+                    }
+                } else {
+                    out.insert(path);
+                }
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string());
+        }
+    }
+}
+
+// Random scores so a sample isn't just the oldest documents; a seed makes it repeatable
+pub(crate) fn random_sample_query(
+    query: Option<serde_json::Value>,
+    seed: Option<u64>,
+) -> serde_json::Value {
+    let random_score = match seed {
+        Some(seed) => serde_json::json!({ "seed": seed, "field": "_seq_no" }),
+        None => serde_json::json!({}),
+    };
+
+    serde_json::json!({
+        "function_score": {
+            "query": query.unwrap_or_else(|| serde_json::json!({ "match_all": {} })),
+            "random_score": random_score,
+            "boost_mode": "replace"
+        }
+    })
+}
+
+#[command]
+pub async fn analyze_index_quality(
+    window: Window,
+    index: String,
+    sample_size: Option<u32>,
+    timeout_ms: Option<u64>,
+) -> Result<IndexQualityReport, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let index = connection_index(transport.connection(), &index)?;
+    let sample_size = sample_size
+        .unwrap_or(DEFAULT_QUALITY_SAMPLE_SIZE)
+        .clamp(1, MAX_SAMPLE_SIZE);
+
+    let mappings = transport
+        .get_json_cached(&format!("/{}/_mapping", encode_segment(&index)))
+        .await?;
+    let mut mapped: BTreeMap<String, FlattenedField> = BTreeMap::new();
+    for index_mapping in mappings.as_object().into_iter().flat_map(|m| m.values()) {
+        for field in flatten_mapping(&index_mapping["mappings"]) {
+            mapped.entry(field.path.clone()).or_insert(field);
+        }
+    }
+
+    let sample_body = serde_json::json!({
+        "size": sample_size,
+        "track_total_hits": connection_track_total_hits(transport.connection()),
+        "query": random_sample_query(None, None)
+    });
+    let sample = transport
+        .json(
+            Method::POST,
+            &format!("/{}/_search", encode_segment(&index)),
+            Some(&sample_body),
+        )
+        .await?;
+    let sources: Vec<&serde_json::Value> = sample["hits"]["hits"]
+        .as_array()
+        .map(|hits| hits.iter().map(|hit| &hit["_source"]).collect())
+        .unwrap_or_default();
+    let sampled_docs = sources.len() as u64;
+
+    // Multi-fields and runtime fields aren't in _source, objects are covered by their leaves
+    let source_fields: Vec<&FlattenedField> = mapped
+        .values()
+        .filter(|f| {
+            f.parent.is_none()
+                && !f.runtime
+                && !matches!(f.field_type.as_str(), "object" | "nested")
+        })
+        .collect();
+
+    let mut fields: Vec<FieldQuality> = source_fields
+        .iter()
+        .map(|field| {
+            let segments: Vec<&str> = field.path.split('.').collect();
+            let mut quality = FieldQuality {
+                path: field.path.clone(),
+                field_type: field.field_type.clone(),
+                present: 0,
+                null_count: 0,
+                missing: 0,
+                empty_rate: 0.0,
+                observed_types: BTreeMap::new(),
+                mismatched: 0,
+                cardinality: None,
+                high_cardinality: false,
+            };
+
+            for source in &sources {
+                let mut values = Vec::new();
+                collect_source_values(source, &segments, &mut values);
+
+                if values.is_empty() {
+                    quality.missing += 1;
+                } else if values.iter().all(|v| v.is_null()) {
+                    quality.null_count += 1;
+                } else {
+                    quality.present += 1;
+                }
+
+                for value in values {
+                    *quality
+                        .observed_types
+                        .entry(json_type_name(value).to_string())
+                        .or_insert(0) += 1;
+                    if !fits_mapped_type(&field.field_type, value) {
+                        quality.mismatched += 1;
+                    }
+                }
+            }
+
+            if sampled_docs > 0 {
+                quality.empty_rate =
+                    (quality.null_count + quality.missing) as f64 / sampled_docs as f64;
+            }
+            quality
+        })
+        .collect();
+
+    // Cardinality is cheap to ask for on the whole index, so don't estimate it from the sample
+    let keyword_fields: Vec<String> = mapped
+        .values()
+        .filter(|f| f.field_type == "keyword" && f.doc_values)
+        .map(|f| f.path.clone())
+        .take(MAX_CARDINALITY_FIELDS)
+        .collect();
+    if !keyword_fields.is_empty() {
+        let mut aggs = serde_json::Map::new();
+        for (i, path) in keyword_fields.iter().enumerate() {
+            aggs.insert(
+                format!("c{}", i),
+                serde_json::json!({ "cardinality": { "field": path } }),
+            );
+            aggs.insert(
+                format!("v{}", i),
+                serde_json::json!({ "value_count": { "field": path } }),
+            );
+        }
+        let body = serde_json::json!({ "size": 0, "aggs": aggs });
+        let result = transport
+            .json(
+                Method::POST,
+                &format!("/{}/_search", encode_segment(&index)),
+                Some(&body),
+            )
+            .await?;
+
+        for (i, path) in keyword_fields.iter().enumerate() {
+            let cardinality = result["aggregations"][format!("c{}", i)]["value"]
+                .as_u64()
+                .unwrap_or(0);
+            let value_count = result["aggregations"][format!("v{}", i)]["value"]
+                .as_u64()
+                .unwrap_or(0);
+            let high_cardinality = cardinality >= HIGH_CARDINALITY_MIN_VALUES
+                && value_count > 0
+                && cardinality as f64 / value_count as f64 >= HIGH_CARDINALITY_RATIO;
+
+            // keyword multi-fields aren't in the list yet, add them so the figure has somewhere to go
+            match fields.iter_mut().find(|f| &f.path == path) {
+                Some(quality) => {
+                    quality.cardinality = Some(cardinality);
+                    quality.high_cardinality = high_cardinality;
+                }
+                None => fields.push(FieldQuality {
+                    path: path.clone(),
+                    field_type: "keyword".to_string(),
+                    present: 0,
+                    null_count: 0,
+                    missing: 0,
+                    empty_rate: 0.0,
+                    observed_types: BTreeMap::new(),
+                    mismatched: 0,
+                    cardinality: Some(cardinality),
+                    high_cardinality,
+                }),
+            }
+        }
+    }
+
+    let mut seen_paths = BTreeSet::new();
+    for source in &sources {
+        collect_source_paths(source, "", &mut seen_paths);
+    }
+    let unmapped_fields = seen_paths
+        .into_iter()
+        .filter(|path| !path.is_empty() && !mapped.contains_key(path))
+        .collect();
+
+    Ok(IndexQualityReport {
+        index,
+        total_docs: sample["hits"]["total"]["value"].as_u64().unwrap_or(0),
+        sampled_docs,
+        type_mismatch_fields: fields
+            .iter()
+            .filter(|f| f.mismatched > 0)
+            .map(|f| f.path.clone())
+            .collect(),
+        high_cardinality_fields: fields
+            .iter()
+            .filter(|f| f.high_cardinality)
+            .map(|f| f.path.clone())
+            .collect(),
+        fields,
+        unmapped_fields,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    // One value per key field, in the order the fields were given
+    pub key: Vec<serde_json::Value>,
+    pub count: u64,
+    // The first document is the one kept when extras are deleted
+    pub docs: Vec<DuplicateDoc>,
+}
+
+// A hit of a duplicate group, with the concrete index it lives in since the
+// searched expression may be an alias or a pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateDoc {
+    pub index: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateReport {
+    pub fields: Vec<String>,
+    pub groups: Vec<DuplicateGroup>,
+    // Documents beyond the first in every group
+    pub duplicate_docs: u64,
+    pub deleted: u64,
+    pub delete_failures: Vec<String>,
+}
+
+const DEFAULT_DUPLICATE_GROUPS: u32 = 100;
+const MAX_IDS_PER_DUPLICATE_GROUP: u32 = 100;
+
+// Deleting the extras needs a token from request_confirmation_token("find_duplicates", index),
+// where an empty index means the connection's default pattern
+#[command]
+pub async fn find_duplicates(
+    window: Window,
+    index: String,
+    fields: Vec<String>,
+    max_groups: Option<u32>,
+    delete_extras: Option<bool>,
+    confirmation_token: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<DuplicateReport, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let index = connection_index(transport.connection(), &index)?;
+
+    if fields.is_empty() {
+        return Err("At least one key field is required".to_string());
+    }
+    if delete_extras.unwrap_or(false) {
+        require_confirmation(
+            transport.connection_id(),
+            "find_duplicates",
+            &index,
+            confirmation_token.as_deref(),
+        )?;
+    }
+
+    let mut key_fields = Vec::new();
+    for field in &fields {
+        key_fields.push(
+            resolve_aggregatable_field(&transport, &index, field)
+                .await?
+                .path,
+        );
+    }
+
+    let top_hits = serde_json::json!({
+        "top_hits": { "size": MAX_IDS_PER_DUPLICATE_GROUP, "_source": false }
+    });
+    let size = max_groups.unwrap_or(DEFAULT_DUPLICATE_GROUPS).max(1);
+
+    // multi_terms handles compound keys, a plain terms agg is cheaper for a single field
+    let grouping = if key_fields.len() == 1 {
+        serde_json::json!({
+            "terms": { "field": key_fields[0], "size": size, "min_doc_count": 2 },
+            "aggs": { "docs": top_hits }
+        })
+    } else {
+        let terms: Vec<serde_json::Value> = key_fields
+            .iter()
+            .map(|field| serde_json::json!({ "field": field }))
+            .collect();
+        serde_json::json!({
+            "multi_terms": { "terms": terms, "size": size, "min_doc_count": 2 },
+            "aggs": { "docs": top_hits }
+        })
+    };
+
+    let body = serde_json::json!({
+        "size": 0,
+        "aggs": { "duplicates": grouping }
+    });
+    let result = transport
+        .json(
+            Method::POST,
+            &format!("/{}/_search", encode_segment(&index)),
+            Some(&body),
+        )
+        .await?;
+
+    let groups: Vec<DuplicateGroup> = result["aggregations"]["duplicates"]["buckets"]
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|bucket| DuplicateGroup {
+            key: match &bucket["key"] {
+                serde_json::Value::Array(values) => values.clone(),
+                single => vec![single.clone()],
+            },
+            count: bucket["doc_count"].as_u64().unwrap_or(0),
+            docs: bucket["docs"]["hits"]["hits"]
+                .as_array()
+                .unwrap_or(&vec![])
+                .iter()
+                .filter_map(|hit| {
+                    Some(DuplicateDoc {
+                        index: hit["_index"].as_str()?.to_string(),
+                        id: hit["_id"].as_str()?.to_string(),
+                    })
+                })
+                .collect(),
+        })
+        .collect();
+
+    let duplicate_docs = groups.iter().map(|group| group.count - 1).sum();
+    let mut deleted = 0;
+    let mut delete_failures = Vec::new();
+
+    // Only ids we actually fetched are deleted, very large groups may need another pass
+    if delete_extras.unwrap_or(false) {
+        let mut bulk = BulkBuilder::new(transport.clone()).refresh("wait_for");
+        let extras: Vec<&DuplicateDoc> = groups
+            .iter()
+            .flat_map(|group| group.docs.iter().skip(1))
+            .collect();
+        for (position, doc) in extras.iter().enumerate() {
+            bulk.push(BulkAction::delete(position as u64, &doc.index, &doc.id))
+                .await?;
+        }
+
+        let outcome = bulk.finish().await?;
+        deleted = outcome.succeeded;
+        delete_failures = outcome
+            .errors
+            .into_iter()
+            .map(|error| {
+                let doc = extras[error.tag as usize];
+                format!(
+                    "{}/{}: {} {}",
+                    doc.index, doc.id, error.status, error.reason
+                )
+            })
+            .collect();
+
+        if !extras.is_empty() {
+            println!(
+                "Successfully deleted {} duplicate documents from {}",
+                deleted, index
+            );
+        }
+    }
+
+    Ok(DuplicateReport {
+        fields,
+        groups,
+        duplicate_docs,
+        deleted,
+        delete_failures,
+    })
+}
+
+const DEFAULT_SAMPLE_DOCUMENTS: u32 = 10;
+
+#[command]
+pub async fn sample_documents(
+    window: Window,
+    index: String,
+    size: Option<u32>,
+    query: Option<serde_json::Value>,
+    seed: Option<u64>,
+    timeout_ms: Option<u64>,
+) -> Result<QueryResult, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let conn = transport.connection();
+    let index = connection_index(conn, &index)?;
+
+    // Without a size the connection's page size applies, then the usual sample size
+    let size = size
+        .or_else(|| {
+            conn.defaults
+                .as_ref()
+                .and_then(|defaults| defaults.page_size)
+        })
+        .unwrap_or(DEFAULT_SAMPLE_DOCUMENTS);
+    let body = serde_json::json!({
+        "size": size.clamp(1, MAX_SAMPLE_SIZE),
+        "track_total_hits": connection_track_total_hits(conn),
+        "query": random_sample_query(query, seed)
+    });
+    let result = transport
+        .json(
+            Method::POST,
+            &format!("/{}/_search", encode_segment(&index)),
+            Some(&body),
+        )
+        .await?;
+
+    parse_query_result(&result)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutocompleteBundle {
+    // Raw `_mapping` response, keyed by concrete index
+    pub mappings: serde_json::Value,
+    // The `fields` object of `_field_caps`, runtime fields included
+    pub field_caps: serde_json::Value,
+    // Alias name to the indices it points at
+    pub aliases: BTreeMap<String, Vec<String>>,
+    pub pipelines: Vec<String>,
+}
+
+// Everything the query editor needs for completion in one round trip from the frontend
+#[command]
+pub async fn get_autocomplete_bundle(
+    window: Window,
+    index_pattern: String,
+    timeout_ms: Option<u64>,
+) -> Result<AutocompleteBundle, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let pattern = encode_segment(&index_pattern);
+
+    let mappings_path = format!("/{}/_mapping", pattern);
+    let field_caps_path = format!("/{}/_field_caps?fields=*", pattern);
+    let (mappings, field_caps, aliases, pipelines) = tokio::join!(
+        transport.get_json_cached(&mappings_path),
+        transport.get_json_cached(&field_caps_path),
+        transport.get_json_cached("/_alias"),
+        transport.get_json_cached("/_ingest/pipeline"),
+    );
+
+    // Aliases and pipelines are nice to have, a user without access to them still gets field completion
+    let mut alias_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if let Ok(aliases) = aliases {
+        for (index, entry) in aliases.as_object().into_iter().flatten() {
+            for alias in entry["aliases"]
+                .as_object()
+                .into_iter()
+                .flat_map(|a| a.keys())
+            {
+                alias_map
+                    .entry(alias.clone())
+                    .or_default()
+                    .push(index.clone());
+            }
+        }
+    }
+    for indices in alias_map.values_mut() {
+        indices.sort();
+    }
+
+    let pipelines = pipelines
+        .ok()
+        .and_then(|p| p.as_object().map(|p| p.keys().cloned().collect()))
+        .unwrap_or_default();
+
+    Ok(AutocompleteBundle {
+        mappings: mappings?,
+        field_caps: field_caps?["fields"].take(),
+        aliases: alias_map,
+        pipelines,
+    })
+}
@@ -0,0 +1,773 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/audit.rs
+
+use crate::analysis::{random_sample_query, MAX_SAMPLE_SIZE};
+use crate::elasticsearch::simulate_index_name;
+use crate::transport::{encode_segment, Transport};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use tauri::{command, Window};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditFinding {
+    // Machine readable, e.g. "no_write_index"
+    pub kind: String,
+    // "critical", "warning" or "info"
+    pub severity: String,
+    // The alias, index or node the finding is about
+    pub target: String,
+    pub indices: Vec<String>,
+    pub message: String,
+    // What to do about it, when there's an obvious fix
+    pub suggestion: Option<String>,
+}
+
+impl AuditFinding {
+    fn new(
+        kind: &str,
+        severity: &str,
+        target: &str,
+        indices: Vec<String>,
+        message: String,
+    ) -> Self {
+        AuditFinding {
+            kind: kind.to_string(),
+            severity: severity.to_string(),
+            target: target.to_string(),
+            indices,
+            message,
+            suggestion: None,
+        }
+    }
+
+    fn suggest(mut self, suggestion: &str) -> Self {
+        self.suggestion = Some(suggestion.to_string());
+        self
+    }
+}
+
+// Per index settings of one alias, as returned under `aliases` by the alias API
+struct AliasTarget {
+    index: String,
+    is_write_index: Option<bool>,
+    filter: Option<serde_json::Value>,
+}
+
+#[command]
+pub async fn audit_aliases(
+    window: Window,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<AuditFinding>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+
+    let (aliases_data, indices_data) = tokio::try_join!(
+        transport.get_json("/_alias?expand_wildcards=all"),
+        transport.get_json("/_cat/indices?format=json&h=index,status&expand_wildcards=all"),
+    )?;
+
+    let closed: BTreeSet<String> = indices_data
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|row| row["status"].as_str() == Some("close"))
+        .filter_map(|row| row["index"].as_str().map(|s| s.to_string()))
+        .collect();
+
+    let mut aliases: BTreeMap<String, Vec<AliasTarget>> = BTreeMap::new();
+    for (index, entry) in aliases_data.as_object().into_iter().flatten() {
+        for (alias, settings) in entry["aliases"].as_object().into_iter().flatten() {
+            aliases.entry(alias.clone()).or_default().push(AliasTarget {
+                index: index.clone(),
+                is_write_index: settings["is_write_index"].as_bool(),
+                filter: settings.get("filter").cloned(),
+            });
+        }
+    }
+
+    let mut findings = Vec::new();
+    for (alias, targets) in &aliases {
+        let indices: Vec<String> = targets.iter().map(|t| t.index.clone()).collect();
+
+        // A single index alias takes writes implicitly, several need one marked as the write index
+        if targets.len() > 1 && !targets.iter().any(|t| t.is_write_index == Some(true)) {
+            findings.push(
+                AuditFinding::new(
+                    "no_write_index",
+                    "warning",
+                    alias,
+                    indices.clone(),
+                    format!(
+                        "Alias '{}' points at {} indices and none is the write index, so indexing through it fails",
+                        alias,
+                        targets.len()
+                    ),
+                )
+                .suggest("Set is_write_index on the index that should receive new documents"),
+            );
+        }
+
+        let closed_targets: Vec<String> = indices
+            .iter()
+            .filter(|index| closed.contains(*index))
+            .cloned()
+            .collect();
+        if !closed_targets.is_empty() {
+            let severity = if closed_targets.len() == targets.len() {
+                "critical"
+            } else {
+                "warning"
+            };
+            findings.push(
+                AuditFinding::new(
+                    "closed_index",
+                    severity,
+                    alias,
+                    closed_targets.clone(),
+                    format!(
+                        "Alias '{}' points at closed {}: {}",
+                        alias,
+                        if closed_targets.len() == 1 {
+                            "index"
+                        } else {
+                            "indices"
+                        },
+                        closed_targets.join(", ")
+                    ),
+                )
+                .suggest("Open the indices or remove them from the alias so searches don't fail"),
+            );
+        }
+
+        // Filters that differ across indices usually mean the alias was added to a new index without its filter
+        let filters: BTreeSet<String> = targets
+            .iter()
+            .map(|t| t.filter.as_ref().map(|f| f.to_string()).unwrap_or_default())
+            .collect();
+        if targets.len() > 1 && filters.len() > 1 {
+            let unfiltered: Vec<String> = targets
+                .iter()
+                .filter(|t| t.filter.is_none())
+                .map(|t| t.index.clone())
+                .collect();
+            let message = if unfiltered.is_empty() {
+                format!(
+                    "Alias '{}' uses different filters on its {} indices",
+                    alias,
+                    targets.len()
+                )
+            } else {
+                format!(
+                    "Alias '{}' is filtered on some indices but not on {}, which expose all their documents through it",
+                    alias,
+                    unfiltered.join(", ")
+                )
+            };
+            findings.push(
+                AuditFinding::new(
+                    "filter_mismatch",
+                    "warning",
+                    alias,
+                    indices.clone(),
+                    message,
+                )
+                .suggest("Re-add the alias with the same filter on every index"),
+            );
+        }
+    }
+
+    Ok(findings)
+}
+
+const DEFAULT_ILM_STUCK_MINUTES: u64 = 24 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StuckIlmIndex {
+    pub index: String,
+    pub policy: String,
+    pub phase: String,
+    pub action: String,
+    pub step: String,
+    // "error" when the step failed, "waiting" when it has sat in the same step too long
+    pub reason: String,
+    pub failed_step: Option<String>,
+    pub error: Option<String>,
+    pub step_age_ms: Option<u64>,
+    // Only indices in the ERROR step can be retried
+    pub retryable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IlmRetryResult {
+    pub index: String,
+    pub retried: bool,
+    pub error: Option<String>,
+}
+
+#[command]
+pub async fn find_stuck_ilm_indices(
+    window: Window,
+    threshold_minutes: Option<u64>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<StuckIlmIndex>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let threshold_ms = threshold_minutes.unwrap_or(DEFAULT_ILM_STUCK_MINUTES) * 60_000;
+
+    let explain = transport
+        .get_json("/_all/_ilm/explain?only_managed=true&expand_wildcards=all")
+        .await?;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let mut stuck: Vec<StuckIlmIndex> = explain["indices"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(index, state)| {
+            let text = |key: &str| state[key].as_str().unwrap_or("").to_string();
+            let step = text("step");
+            let step_age_ms = state["step_time_millis"]
+                .as_i64()
+                .map(|started| (now - started).max(0) as u64);
+
+            // Indices that finished their last phase are expected to sit still forever
+            let reason = if step == "ERROR" {
+                "error"
+            } else if step != "complete" && step_age_ms.is_some_and(|age| age > threshold_ms) {
+                "waiting"
+            } else {
+                return None;
+            };
+
+            let step_info = &state["step_info"];
+            let error = step_info["reason"]
+                .as_str()
+                .or_else(|| step_info["message"].as_str())
+                .map(|s| s.to_string());
+
+            Some(StuckIlmIndex {
+                index: index.clone(),
+                policy: text("policy"),
+                phase: text("phase"),
+                action: text("action"),
+                reason: reason.to_string(),
+                failed_step: state["failed_step"].as_str().map(|s| s.to_string()),
+                error,
+                step_age_ms,
+                retryable: step == "ERROR",
+                step,
+            })
+        })
+        .collect();
+
+    // Failures first, then the longest waiting
+    stuck.sort_by(|a, b| {
+        (a.reason != "error")
+            .cmp(&(b.reason != "error"))
+            .then_with(|| b.step_age_ms.cmp(&a.step_age_ms))
+    });
+
+    Ok(stuck)
+}
+
+#[command]
+pub async fn retry_ilm_indices(
+    window: Window,
+    indices: Vec<String>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<IlmRetryResult>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+
+    let mut results = Vec::new();
+    for index in indices {
+        let outcome = transport
+            .json(
+                Method::POST,
+                &format!("/{}/_ilm/retry", encode_segment(&index)),
+                None,
+            )
+            .await;
+        if outcome.is_ok() {
+            println!("Successfully retried ILM step for index: {}", index);
+        }
+        results.push(IlmRetryResult {
+            index,
+            retried: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+
+    Ok(results)
+}
+
+const DEFAULT_BALANCE_TOLERANCE_PERCENT: f64 = 20.0;
+// Node role letters that mean the node holds shards
+const DATA_ROLE_LETTERS: &[char] = &['d', 'h', 'w', 'c', 'f', 's'];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeShardLoad {
+    pub node: String,
+    // The node's data roles, e.g. "h" for data_hot; nodes are only compared with the same roles
+    pub data_roles: String,
+    pub shards: u64,
+    pub primaries: u64,
+    pub bytes: u64,
+    // Relative to the average of nodes with the same data roles
+    pub deviation_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardBalanceReport {
+    pub nodes: Vec<NodeShardLoad>,
+    pub total_bytes: u64,
+    pub unassigned_shards: u64,
+    // Standard deviation over mean of node sizes, the worst of the role groups
+    pub coefficient_of_variation: f64,
+    // Largest node over the average of its role group
+    pub max_to_mean_ratio: f64,
+    pub findings: Vec<AuditFinding>,
+}
+
+#[command]
+pub async fn get_shard_balance_report(
+    window: Window,
+    tolerance_percent: Option<f64>,
+    timeout_ms: Option<u64>,
+) -> Result<ShardBalanceReport, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let tolerance = tolerance_percent.unwrap_or(DEFAULT_BALANCE_TOLERANCE_PERCENT);
+
+    let (shards_data, nodes_data) = tokio::try_join!(
+        transport
+            .get_json("/_cat/shards?format=json&bytes=b&h=index,shard,prirep,state,store,node"),
+        transport.get_json("/_cat/nodes?format=json&h=name,node.role"),
+    )?;
+
+    // Data nodes without any shards still count, they are the most underloaded of all
+    let mut loads: BTreeMap<String, NodeShardLoad> = BTreeMap::new();
+    for node in nodes_data.as_array().into_iter().flatten() {
+        let roles: String = node["node.role"]
+            .as_str()
+            .unwrap_or("")
+            .chars()
+            .filter(|role| DATA_ROLE_LETTERS.contains(role))
+            .collect();
+        if roles.is_empty() {
+            continue;
+        }
+        let name = node["name"].as_str().unwrap_or("").to_string();
+        loads.insert(
+            name.clone(),
+            NodeShardLoad {
+                node: name,
+                data_roles: roles,
+                shards: 0,
+                primaries: 0,
+                bytes: 0,
+                deviation_percent: 0.0,
+            },
+        );
+    }
+
+    let mut unassigned_shards = 0;
+    for shard in shards_data.as_array().into_iter().flatten() {
+        // Relocating shards report "source -> target"; the data still lives on the source
+        let node = shard["node"]
+            .as_str()
+            .and_then(|node| node.split(" -> ").next())
+            .map(|node| node.trim().to_string());
+        let Some(load) = node.and_then(|node| loads.get_mut(&node)) else {
+            if shard["state"].as_str() == Some("UNASSIGNED") {
+                unassigned_shards += 1;
+            }
+            continue;
+        };
+
+        load.shards += 1;
+        if shard["prirep"].as_str() == Some("p") {
+            load.primaries += 1;
+        }
+        load.bytes += shard["store"]
+            .as_str()
+            .and_then(|store| store.parse().ok())
+            .unwrap_or(0);
+    }
+
+    let mut groups: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+    for load in loads.values() {
+        groups
+            .entry(load.data_roles.clone())
+            .or_default()
+            .push(load.bytes);
+    }
+
+    let mut coefficient_of_variation: f64 = 0.0;
+    let mut max_to_mean_ratio: f64 = 0.0;
+    let mut means = BTreeMap::new();
+    for (roles, sizes) in &groups {
+        let mean = sizes.iter().sum::<u64>() as f64 / sizes.len() as f64;
+        means.insert(roles.clone(), mean);
+        if mean <= 0.0 {
+            continue;
+        }
+
+        let variance = sizes
+            .iter()
+            .map(|size| (*size as f64 - mean).powi(2))
+            .sum::<f64>()
+            / sizes.len() as f64;
+        coefficient_of_variation = coefficient_of_variation.max(variance.sqrt() / mean);
+        let largest = sizes.iter().copied().max().unwrap_or(0) as f64;
+        max_to_mean_ratio = max_to_mean_ratio.max(largest / mean);
+    }
+
+    let mut findings = Vec::new();
+    for load in loads.values_mut() {
+        let mean = means.get(&load.data_roles).copied().unwrap_or(0.0);
+        if mean <= 0.0 || groups[&load.data_roles].len() < 2 {
+            continue;
+        }
+
+        load.deviation_percent = (load.bytes as f64 - mean) / mean * 100.0;
+        if load.deviation_percent > tolerance {
+            findings.push(
+                AuditFinding::new(
+                    "node_overloaded",
+                    "warning",
+                    &load.node,
+                    Vec::new(),
+                    format!(
+                        "Node '{}' holds {:.0}% more data than the average of its tier",
+                        load.node, load.deviation_percent
+                    ),
+                )
+                .suggest("Check for large shards pinned by allocation filtering, or add shards to spread big indices"),
+            );
+        } else if load.deviation_percent < -tolerance {
+            findings.push(AuditFinding::new(
+                "node_underloaded",
+                "info",
+                &load.node,
+                Vec::new(),
+                format!(
+                    "Node '{}' holds {:.0}% less data than the average of its tier",
+                    load.node, -load.deviation_percent
+                ),
+            ));
+        }
+    }
+
+    let nodes: Vec<NodeShardLoad> = loads.into_values().collect();
+
+    Ok(ShardBalanceReport {
+        total_bytes: nodes.iter().map(|node| node.bytes).sum(),
+        nodes,
+        unassigned_shards,
+        coefficient_of_variation,
+        max_to_mean_ratio,
+        findings,
+    })
+}
+
+const DEFAULT_VERIFY_SAMPLE_SIZE: u32 = 1000;
+const MAX_REPORTED_IDS: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexVerification {
+    pub source: String,
+    pub dest: String,
+    pub source_count: u64,
+    pub dest_count: u64,
+    pub counts_match: bool,
+    // How many source ids were looked up in the destination, 0 when sampling was skipped
+    pub sampled: u64,
+    // Sampled ids that aren't in the destination, capped at 100
+    pub missing_ids: Vec<String>,
+    // Sampled ids whose _source differs, only checked when asked for
+    pub changed_ids: Vec<String>,
+    // Hashes over the sampled ids (and sources when compared) on each side
+    pub source_checksum: Option<String>,
+    pub dest_checksum: Option<String>,
+    pub passed: bool,
+}
+
+fn checksum<'a>(
+    entries: impl Iterator<Item = (&'a String, Option<&'a serde_json::Value>)>,
+) -> String {
+    let mut sorted: Vec<_> = entries.collect();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for (id, source) in sorted {
+        std::hash::Hash::hash(id, &mut hasher);
+        if let Some(source) = source {
+            std::hash::Hash::hash(&source.to_string(), &mut hasher);
+        }
+    }
+    format!("{:016x}", std::hash::Hasher::finish(&hasher))
+}
+
+// Sign-off check for a migration: matching counts, plus a random sample of source ids that
+// must exist in the destination. Comparing sources only makes sense for reindexes without a script.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn verify_reindex(
+    window: Window,
+    source: String,
+    dest: String,
+    query: Option<serde_json::Value>,
+    sample_size: Option<u32>,
+    compare_sources: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<ReindexVerification, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let sample_size = sample_size
+        .unwrap_or(DEFAULT_VERIFY_SAMPLE_SIZE)
+        .min(MAX_SAMPLE_SIZE);
+    let compare_sources = compare_sources.unwrap_or(false);
+
+    // Reindexed documents only show up in counts and gets once the destination refreshes
+    transport
+        .json(
+            Method::POST,
+            &format!("/{}/_refresh", encode_segment(&dest)),
+            None,
+        )
+        .await?;
+
+    let count_body = serde_json::json!({
+        "query": query.clone().unwrap_or_else(|| serde_json::json!({ "match_all": {} }))
+    });
+    let count = |index: &str| {
+        let path = format!("/{}/_count", encode_segment(index));
+        let transport = &transport;
+        let count_body = &count_body;
+        async move {
+            transport
+                .json(Method::POST, &path, Some(count_body))
+                .await
+                .map(|data| data["count"].as_u64().unwrap_or(0))
+        }
+    };
+    let (source_count, dest_count) = tokio::try_join!(count(&source), count(&dest))?;
+
+    let mut verification = ReindexVerification {
+        source: source.clone(),
+        dest: dest.clone(),
+        source_count,
+        dest_count,
+        counts_match: source_count == dest_count,
+        sampled: 0,
+        missing_ids: Vec::new(),
+        changed_ids: Vec::new(),
+        source_checksum: None,
+        dest_checksum: None,
+        passed: false,
+    };
+
+    if sample_size > 0 && source_count > 0 {
+        let sample = transport
+            .json(
+                Method::POST,
+                &format!("/{}/_search", encode_segment(&source)),
+                Some(&serde_json::json!({
+                    "size": sample_size,
+                    "_source": compare_sources,
+                    "query": random_sample_query(query, None)
+                })),
+            )
+            .await?;
+        let source_docs: BTreeMap<String, serde_json::Value> = sample["hits"]["hits"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|hit| {
+                hit["_id"]
+                    .as_str()
+                    .map(|id| (id.to_string(), hit["_source"].clone()))
+            })
+            .collect();
+
+        let ids: Vec<&String> = source_docs.keys().collect();
+        let found = transport
+            .json(
+                Method::POST,
+                &format!(
+                    "/{}/_mget?_source={}",
+                    encode_segment(&dest),
+                    compare_sources
+                ),
+                Some(&serde_json::json!({ "ids": ids })),
+            )
+            .await?;
+        let dest_docs: BTreeMap<String, serde_json::Value> = found["docs"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|doc| doc["found"].as_bool().unwrap_or(false))
+            .filter_map(|doc| {
+                doc["_id"]
+                    .as_str()
+                    .map(|id| (id.to_string(), doc["_source"].clone()))
+            })
+            .collect();
+
+        for (id, source_doc) in &source_docs {
+            match dest_docs.get(id) {
+                None if verification.missing_ids.len() < MAX_REPORTED_IDS => {
+                    verification.missing_ids.push(id.clone())
+                }
+                Some(dest_doc)
+                    if compare_sources
+                        && dest_doc != source_doc
+                        && verification.changed_ids.len() < MAX_REPORTED_IDS =>
+                {
+                    verification.changed_ids.push(id.clone())
+                }
+                _ => {}
+            }
+        }
+
+        let with_source = |docs: &'_ BTreeMap<String, serde_json::Value>| {
+            checksum(
+                docs.iter()
+                    .map(|(id, doc)| (id, compare_sources.then_some(doc))),
+            )
+        };
+        verification.sampled = source_docs.len() as u64;
+        verification.source_checksum = Some(with_source(&source_docs));
+        verification.dest_checksum = Some(with_source(&dest_docs));
+    }
+
+    verification.passed =
+        verification.counts_match && verification.source_checksum == verification.dest_checksum;
+
+    Ok(verification)
+}
+
+// Settings every index gets at creation rather than from a template
+const INDEX_SPECIFIC_SETTINGS: &[&str] = &[
+    "index.uuid",
+    "index.creation_date",
+    "index.provided_name",
+    "index.history_uuid",
+    "index.version.",
+    "index.resize.",
+    "index.routing.allocation.initial_recovery.",
+    "index.lifecycle.indexing_complete",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateDrift {
+    // Dotted path, e.g. "index.number_of_replicas" or "properties.title.type"
+    pub key: String,
+    // None when only the other side has it
+    pub template: Option<serde_json::Value>,
+    pub index: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexTemplateDiff {
+    pub index: String,
+    // None when no template matches the index name today
+    pub template: Option<String>,
+    // Only settings the template defines; the index's own and default settings aren't drift
+    pub settings: Vec<TemplateDrift>,
+    pub mappings: Vec<TemplateDrift>,
+}
+
+// Flattens nested objects into dotted keys; scalars are compared as strings because the
+// settings API returns every value as one
+fn flatten_values(
+    prefix: &str,
+    value: &serde_json::Value,
+    out: &mut BTreeMap<String, serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, child) in object {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_values(&path, child, out);
+            }
+        }
+        serde_json::Value::String(_) | serde_json::Value::Array(_) => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+        other => {
+            out.insert(prefix.to_string(), serde_json::json!(other.to_string()));
+        }
+    }
+}
+
+fn diff_values(
+    template: &BTreeMap<String, serde_json::Value>,
+    index: &BTreeMap<String, serde_json::Value>,
+) -> Vec<TemplateDrift> {
+    let keys: BTreeSet<&String> = template.keys().chain(index.keys()).collect();
+    keys.into_iter()
+        .filter(|key| template.get(*key) != index.get(*key))
+        .map(|key| TemplateDrift {
+            key: key.clone(),
+            template: template.get(key).cloned(),
+            index: index.get(key).cloned(),
+        })
+        .collect()
+}
+
+#[command]
+pub async fn diff_index_against_template(
+    window: Window,
+    index: String,
+    timeout_ms: Option<u64>,
+) -> Result<IndexTemplateDiff, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+
+    let settings_path = format!("/{}/_settings?flat_settings=true", encode_segment(&index));
+    let mappings_path = format!("/{}/_mapping", encode_segment(&index));
+    let (settings_data, mappings_data) = tokio::try_join!(
+        transport.get_json(&settings_path),
+        transport.get_json(&mappings_path),
+    )?;
+    // What an index of this name would be created with today
+    let simulation = simulate_index_name(window, index.clone(), timeout_ms).await?;
+
+    // Aliases and patterns can resolve to a different concrete index, take whatever came back
+    let index_settings = settings_data
+        .as_object()
+        .and_then(|indices| indices.values().next())
+        .map(|entry| entry["settings"].clone())
+        .ok_or_else(|| format!("Index '{}' not found", index))?;
+    let index_mappings = mappings_data
+        .as_object()
+        .and_then(|indices| indices.values().next())
+        .map(|entry| entry["mappings"].clone())
+        .unwrap_or_default();
+
+    let mut template_settings = BTreeMap::new();
+    flatten_values("", &simulation.settings, &mut template_settings);
+    let mut current_settings = BTreeMap::new();
+    flatten_values("", &index_settings, &mut current_settings);
+    template_settings.retain(|key, _| {
+        !INDEX_SPECIFIC_SETTINGS.iter().any(|specific| {
+            key == specific || (specific.ends_with('.') && key.starts_with(specific))
+        })
+    });
+    // Every index carries shard counts, tier preference and the like whether or not a template
+    // set them, so only settings the template defines can drift
+    current_settings.retain(|key, _| template_settings.contains_key(key));
+
+    let mut template_mappings = BTreeMap::new();
+    flatten_values("", &simulation.mappings, &mut template_mappings);
+    let mut current_mappings = BTreeMap::new();
+    flatten_values("", &index_mappings, &mut current_mappings);
+
+    Ok(IndexTemplateDiff {
+        index,
+        template: simulation.template,
+        settings: diff_values(&template_settings, &current_settings),
+        mappings: diff_values(&template_mappings, &current_mappings),
+    })
+}
@@ -0,0 +1,449 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/bulk.rs
+
+use crate::elasticsearch::describe_error_body;
+use crate::storage::app_settings;
+use crate::transport::{retry_after, RequestExt, Transport};
+use reqwest::header::{HeaderValue, CONTENT_ENCODING};
+use reqwest::Method;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+const MAX_BULK_RETRIES: u32 = 5;
+const RETRY_BASE_DELAY_MS: u64 = 500;
+const MAX_REJECTION_DELAY_MS: u64 = 30_000;
+// How long one builder keeps pausing for a cluster that rejects writes before giving up on them
+const MAX_REJECTION_PAUSE_MS: u64 = 15 * 60 * 1000;
+const GZIP_MIN_BODY_BYTES: usize = 64 * 1024;
+
+// One bulk operation. `tag` comes back with any error so callers can map it to a row or id.
+pub(crate) struct BulkAction {
+    pub tag: u64,
+    pub action: serde_json::Value,
+    pub source: Option<serde_json::Value>,
+}
+
+impl BulkAction {
+    pub(crate) fn index(
+        tag: u64,
+        index: &str,
+        id: Option<&str>,
+        source: serde_json::Value,
+    ) -> Self {
+        let action = match id {
+            Some(id) => serde_json::json!({ "index": { "_index": index, "_id": id } }),
+            None => serde_json::json!({ "index": { "_index": index } }),
+        };
+        BulkAction {
+            tag,
+            action,
+            source: Some(source),
+        }
+    }
+
+    // Fails with a 409 instead of overwriting when a document with the id already exists
+    pub(crate) fn create(tag: u64, index: &str, id: &str, source: serde_json::Value) -> Self {
+        BulkAction {
+            tag,
+            action: serde_json::json!({ "create": { "_index": index, "_id": id } }),
+            source: Some(source),
+        }
+    }
+
+    pub(crate) fn delete(tag: u64, index: &str, id: &str) -> Self {
+        BulkAction {
+            tag,
+            action: serde_json::json!({ "delete": { "_index": index, "_id": id } }),
+            source: None,
+        }
+    }
+}
+
+pub(crate) struct BulkItemError {
+    pub tag: u64,
+    pub status: u16,
+    pub reason: String,
+}
+
+#[derive(Default)]
+pub(crate) struct BulkOutcome {
+    pub succeeded: u64,
+    pub errors: Vec<BulkItemError>,
+}
+
+// An action already rendered to its NDJSON lines, so its size is known before it is queued
+struct EncodedAction {
+    tag: u64,
+    lines: String,
+}
+
+// A `_bulk` request that failed as a whole. `status` is 0 when it never got a response.
+struct BulkRejection {
+    status: u16,
+    reason: String,
+    retry_after: Option<Duration>,
+}
+
+// A full write thread pool shows up as 429 on the request or as this error on single items
+impl BulkRejection {
+    fn unsent(reason: String) -> Self {
+        BulkRejection {
+            status: 0,
+            reason,
+            retry_after: None,
+        }
+    }
+}
+
+fn is_rejection(status: u16, reason: &str) -> bool {
+    status == 429 || reason.starts_with("es_rejected_execution_exception")
+}
+
+// Collects actions and sends them as `_bulk` requests that stay under the bulk_max_bytes setting.
+// Chunks rejected with 413 are split in half. When the cluster pushes back (429 or rejected
+// execution, for the whole request or single items) the builder pauses for `Retry-After` or a
+// growing delay and resumes, up to MAX_REJECTION_PAUSE_MS in total. Every other item failure
+// ends up in the outcome. A request that fails as a whole (no response, 5xx, pause budget used up)
+// is returned as an error instead, since none of its actions can be told apart from the others.
+pub(crate) struct BulkBuilder {
+    transport: Transport,
+    max_bytes: usize,
+    refresh: Option<&'static str>,
+    pending: Vec<EncodedAction>,
+    pending_bytes: usize,
+    paused_ms: u64,
+    outcome: BulkOutcome,
+}
+
+impl BulkBuilder {
+    pub(crate) fn new(transport: Transport) -> Self {
+        BulkBuilder {
+            transport,
+            max_bytes: app_settings().bulk_max_bytes.max(1),
+            refresh: None,
+            pending: Vec::new(),
+            pending_bytes: 0,
+            paused_ms: 0,
+            outcome: BulkOutcome::default(),
+        }
+    }
+
+    // "true" or "wait_for", passed through as the `refresh` parameter of every request
+    pub(crate) fn refresh(mut self, refresh: &'static str) -> Self {
+        self.refresh = Some(refresh);
+        self
+    }
+
+    pub(crate) async fn push(&mut self, action: BulkAction) -> Result<(), String> {
+        let mut lines = action.action.to_string();
+        lines.push('\n');
+        if let Some(source) = &action.source {
+            lines.push_str(&source.to_string());
+            lines.push('\n');
+        }
+
+        if !self.pending.is_empty() && self.pending_bytes + lines.len() > self.max_bytes {
+            self.flush().await?;
+        }
+
+        self.pending_bytes += lines.len();
+        self.pending.push(EncodedAction {
+            tag: action.tag,
+            lines,
+        });
+        Ok(())
+    }
+
+    pub(crate) async fn flush(&mut self) -> Result<(), String> {
+        let chunk = std::mem::take(&mut self.pending);
+        self.pending_bytes = 0;
+
+        // Each entry is a chunk, its attempt number and how long to wait before sending it
+        let mut queue: VecDeque<(Vec<EncodedAction>, u32, Duration)> = VecDeque::new();
+        if !chunk.is_empty() {
+            queue.push_back((chunk, 0, Duration::ZERO));
+        }
+
+        while let Some((chunk, attempt, delay)) = queue.pop_front() {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+
+            match self.send(&chunk).await {
+                Ok(failures) => {
+                    let mut rejected = Vec::new();
+                    let mut failed_positions = failures.into_iter().peekable();
+
+                    for (position, action) in chunk.into_iter().enumerate() {
+                        let failure = match failed_positions.peek() {
+                            Some((failed, _, _)) if *failed == position => failed_positions.next(),
+                            _ => None,
+                        };
+                        match failure {
+                            None => self.outcome.succeeded += 1,
+                            Some((_, status, reason)) if is_rejection(status, &reason) => {
+                                rejected.push((action, status, reason))
+                            }
+                            Some((_, status, reason)) => self.outcome.errors.push(BulkItemError {
+                                tag: action.tag,
+                                status,
+                                reason,
+                            }),
+                        }
+                    }
+
+                    if !rejected.is_empty() {
+                        match self.pause_for(attempt, None) {
+                            Some(delay) => {
+                                let retry = rejected.into_iter().map(|(action, _, _)| action);
+                                queue.push_back((retry.collect(), attempt + 1, delay));
+                            }
+                            None => {
+                                for (action, status, reason) in rejected {
+                                    self.outcome.errors.push(BulkItemError {
+                                        tag: action.tag,
+                                        status,
+                                        reason,
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+                // A single action bigger than http.max_content_length can't be split any further
+                Err(rejection) if rejection.status == 413 && chunk.len() > 1 => {
+                    let mut first = chunk;
+                    let second = first.split_off(first.len() / 2);
+                    queue.push_front((second, attempt, Duration::ZERO));
+                    queue.push_front((first, attempt, Duration::ZERO));
+                }
+                // Only that document is too large, the rest of the batch is fine
+                Err(rejection) if rejection.status == 413 => {
+                    for action in chunk {
+                        self.outcome.errors.push(BulkItemError {
+                            tag: action.tag,
+                            status: rejection.status,
+                            reason: rejection.reason.clone(),
+                        });
+                    }
+                }
+                Err(rejection) => {
+                    let pause = if is_rejection(rejection.status, &rejection.reason) {
+                        self.pause_for(attempt, rejection.retry_after)
+                    } else {
+                        None
+                    };
+
+                    match pause {
+                        Some(delay) => queue.push_back((chunk, attempt + 1, delay)),
+                        // Nothing says whether any of it was written, so callers must not treat
+                        // these actions as done
+                        None if rejection.status == 0 => {
+                            return Err(format!("Bulk request failed: {}", rejection.reason))
+                        }
+                        None => return Err(rejection.reason),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // How long to pause before resending rejected actions, or None once the pause budget is used
+    // up. The delay grows with each attempt until MAX_BULK_RETRIES and then stays at the cap.
+    fn pause_for(&mut self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        let backoff = RETRY_BASE_DELAY_MS
+            .saturating_mul(1 << attempt.min(MAX_BULK_RETRIES))
+            .min(MAX_REJECTION_DELAY_MS);
+        let delay = retry_after.map_or(backoff, |delay| delay.as_millis() as u64);
+
+        if self.paused_ms + delay > MAX_REJECTION_PAUSE_MS {
+            return None;
+        }
+        self.paused_ms += delay;
+
+        println!(
+            "Cluster rejected bulk writes, pausing for {}ms before resuming",
+            delay
+        );
+
+        Some(Duration::from_millis(delay))
+    }
+
+    pub(crate) async fn finish(mut self) -> Result<BulkOutcome, String> {
+        self.flush().await?;
+        Ok(self.outcome)
+    }
+
+    // Sends one `_bulk` request and returns (position, status, reason) for the items that
+    // failed, in request order
+    async fn send(
+        &self,
+        chunk: &[EncodedAction],
+    ) -> Result<Vec<(usize, u16, String)>, BulkRejection> {
+        let bulk_body: String = chunk.iter().map(|action| action.lines.as_str()).collect();
+        let path = match self.refresh {
+            Some(refresh) => format!("/_bulk?refresh={}", refresh),
+            None => "/_bulk".to_string(),
+        };
+
+        let mut request = self
+            .transport
+            .ndjson_request(Method::POST, &path)
+            .map_err(BulkRejection::unsent)?;
+
+        // Bulk bodies are highly repetitive JSON, so compressing large ones saves most of the upload
+        request = if bulk_body.len() >= GZIP_MIN_BODY_BYTES {
+            request
+                .header(CONTENT_ENCODING, HeaderValue::from_static("gzip"))
+                .body(gzip_bytes(bulk_body.as_bytes()).map_err(BulkRejection::unsent)?)
+        } else {
+            request.body(bulk_body)
+        };
+
+        let response = request
+            .send_with_retry()
+            .await
+            .map_err(|e| BulkRejection::unsent(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let retry_after = retry_after(&response);
+            let error_text = match response.text().await {
+                Ok(text) => describe_error_body(&text),
+                Err(_) => "Unable to read error response".to_string(),
+            };
+
+            return Err(BulkRejection {
+                status: status.as_u16(),
+                reason: format!(
+                    "Bulk request failed - Status: {}, Response: {}",
+                    status, error_text
+                ),
+                retry_after,
+            });
+        }
+
+        let result: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| BulkRejection::unsent(e.to_string()))?;
+
+        // Each item is keyed by its operation type ("index", "delete", ...)
+        let failures = result["items"]
+            .as_array()
+            .unwrap_or(&vec![])
+            .iter()
+            .enumerate()
+            .filter_map(|(position, item)| {
+                let item = item.as_object()?.values().next()?;
+                let error = &item["error"];
+                if error.is_null() {
+                    None
+                } else {
+                    Some((
+                        position,
+                        item["status"].as_u64().unwrap_or(0) as u16,
+                        format!(
+                            "{}: {}",
+                            error["type"].as_str().unwrap_or("error"),
+                            error["reason"].as_str().unwrap_or("unknown reason")
+                        ),
+                    ))
+                }
+            })
+            .collect();
+
+        Ok(failures)
+    }
+}
+
+pub(crate) fn gzip_bytes(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    std::io::Write::write_all(&mut encoder, data).map_err(|e| e.to_string())?;
+    encoder.finish().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elasticsearch::ElasticsearchConnection;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    // A `_bulk` endpoint that takes at most two actions per request and never one marked "huge"
+    async fn serve_bulk(listener: tokio::net::TcpListener) {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            let mut stream = BufReader::new(stream);
+
+            let mut content_length = 0;
+            let mut line = String::new();
+            while stream.read_line(&mut line).await.unwrap_or(0) > 0 && line != "\r\n" {
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+                line.clear();
+            }
+            let mut body = vec![0; content_length];
+            stream.read_exact(&mut body).await.unwrap();
+            let body = String::from_utf8(body).unwrap();
+
+            let actions = body.lines().count() / 2;
+            let (status, reply) = if actions > 2 || body.contains("huge") {
+                ("413 Payload Too Large", String::new())
+            } else {
+                let items = vec![serde_json::json!({ "index": { "status": 201 } }); actions];
+                ("200 OK", serde_json::json!({ "items": items }).to_string())
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                reply.len(),
+                reply
+            );
+            stream
+                .get_mut()
+                .write_all(response.as_bytes())
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn splits_chunks_rejected_with_413() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(serve_bulk(listener));
+
+        let conn = ElasticsearchConnection {
+            id: "test".to_string(),
+            name: "test".to_string(),
+            host: "127.0.0.1".to_string(),
+            port,
+            username: None,
+            password: None,
+            ssl: None,
+            api_key: None,
+            auth_type: "none".to_string(),
+            defaults: None,
+        };
+        let mut bulk = BulkBuilder::new(Transport::new(reqwest::Client::new(), conn));
+        for tag in 0..5 {
+            let value = if tag == 2 { "huge" } else { "small" };
+            let source = serde_json::json!({ "value": value });
+            bulk.push(BulkAction::index(tag, "docs", None, source))
+                .await
+                .unwrap();
+        }
+        let outcome = bulk.finish().await.unwrap();
+
+        assert_eq!(outcome.succeeded, 4);
+        assert_eq!(outcome.errors.len(), 1);
+        assert_eq!(outcome.errors[0].tag, 2);
+        assert_eq!(outcome.errors[0].status, 413);
+    }
+}
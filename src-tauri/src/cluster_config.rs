@@ -0,0 +1,456 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/cluster_config.rs
+
+use crate::elasticsearch::describe_error_response;
+use crate::export::resolve_export_path;
+use crate::transport::{encode_segment, Transport};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::{command, Window};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const CLUSTER_SETTINGS_FILE: &str = "cluster_settings.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterConfigManifest {
+    pub cluster_name: String,
+    pub version: String,
+    pub exported_at: String,
+    // Section -> number of objects written
+    pub sections: BTreeMap<String, usize>,
+    // Section -> why it couldn't be read (feature disabled, missing privileges, ...)
+    pub skipped: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterConfigExportSummary {
+    pub dir: String,
+    pub manifest: ClusterConfigManifest,
+}
+
+// One object per file; the real name is kept inside since file names get sanitised
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ConfigObject {
+    name: String,
+    body: serde_json::Value,
+}
+
+// Directory name, GET path (objects are PUT back to `path/name`) and how to pull
+// (name, body) pairs out of the GET response
+struct ConfigSection {
+    dir: &'static str,
+    path: &'static str,
+    extract: fn(&serde_json::Value) -> Vec<ConfigObject>,
+}
+
+fn from_named_list(list_key: &str, body_key: &str, data: &serde_json::Value) -> Vec<ConfigObject> {
+    data[list_key]
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .map(|item| ConfigObject {
+            name: item["name"].as_str().unwrap_or("").to_string(),
+            body: item[body_key].clone(),
+        })
+        .collect()
+}
+
+fn from_keyed_map(
+    data: &serde_json::Value,
+    body: impl Fn(&serde_json::Value) -> serde_json::Value,
+) -> Vec<ConfigObject> {
+    data.as_object()
+        .map(|objects| {
+            objects
+                .iter()
+                .map(|(name, value)| ConfigObject {
+                    name: name.clone(),
+                    body: body(value),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+const CONFIG_SECTIONS: &[ConfigSection] = &[
+    ConfigSection {
+        dir: "index_templates",
+        path: "/_index_template",
+        extract: |data| from_named_list("index_templates", "index_template", data),
+    },
+    ConfigSection {
+        dir: "component_templates",
+        path: "/_component_template",
+        extract: |data| from_named_list("component_templates", "component_template", data),
+    },
+    ConfigSection {
+        dir: "ilm_policies",
+        path: "/_ilm/policy",
+        // version, modified_date and in_use_by are read-only, PUT only takes the policy
+        extract: |data| {
+            from_keyed_map(
+                data,
+                |value| serde_json::json!({ "policy": value["policy"] }),
+            )
+        },
+    },
+    ConfigSection {
+        dir: "slm_policies",
+        path: "/_slm/policy",
+        extract: |data| from_keyed_map(data, |value| value["policy"].clone()),
+    },
+    ConfigSection {
+        dir: "ingest_pipelines",
+        path: "/_ingest/pipeline",
+        extract: |data| from_keyed_map(data, |value| value.clone()),
+    },
+    ConfigSection {
+        dir: "roles",
+        path: "/_security/role",
+        // transient_metadata is computed by ES and rejected on PUT
+        extract: |data| {
+            from_keyed_map(data, |value| {
+                let mut role = value.clone();
+                if let Some(role) = role.as_object_mut() {
+                    role.remove("transient_metadata");
+                }
+                role
+            })
+        },
+    },
+];
+
+// Built-in objects that ES recreates and manages itself
+fn is_managed(object: &ConfigObject) -> bool {
+    let body = &object.body;
+    body["_meta"]["managed"].as_bool().unwrap_or(false)
+        || body["policy"]["_meta"]["managed"]
+            .as_bool()
+            .unwrap_or(false)
+        || body["template"]["_meta"]["managed"]
+            .as_bool()
+            .unwrap_or(false)
+        || body["metadata"]["_reserved"].as_bool().unwrap_or(false)
+}
+
+fn config_file_name(name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect();
+    format!("{}.json", safe)
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    std::fs::write(path, contents).map_err(|e| format!("Unable to write {}: {}", path.display(), e))
+}
+
+#[command]
+pub async fn export_cluster_config(
+    window: Window,
+    dir: String,
+    include_managed: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<ClusterConfigExportSummary, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let dir = resolve_export_path(dir);
+    let dir_path = Path::new(&dir);
+    std::fs::create_dir_all(dir_path).map_err(|e| format!("Unable to create '{}': {}", dir, e))?;
+
+    let root = transport.get_json("/").await?;
+    let mut manifest = ClusterConfigManifest {
+        cluster_name: root["cluster_name"].as_str().unwrap_or("").to_string(),
+        version: root["version"]["number"].as_str().unwrap_or("").to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        sections: BTreeMap::new(),
+        skipped: BTreeMap::new(),
+    };
+
+    // Defaults are left out so the file only holds what someone actually changed
+    let settings = transport.get_json("/_cluster/settings").await?;
+    write_json(
+        &dir_path.join(CLUSTER_SETTINGS_FILE),
+        &serde_json::json!({
+            "persistent": settings["persistent"],
+            "transient": settings["transient"]
+        }),
+    )?;
+    manifest.sections.insert("cluster_settings".to_string(), 1);
+
+    for section in CONFIG_SECTIONS {
+        // A disabled feature or missing privilege only costs that one section
+        let data = match transport.get_json(section.path).await {
+            Ok(data) => data,
+            Err(e) => {
+                manifest.skipped.insert(section.dir.to_string(), e);
+                continue;
+            }
+        };
+
+        let objects: Vec<ConfigObject> = (section.extract)(&data)
+            .into_iter()
+            .filter(|object| include_managed.unwrap_or(false) || !is_managed(object))
+            .collect();
+
+        let section_dir = dir_path.join(section.dir);
+        std::fs::create_dir_all(&section_dir)
+            .map_err(|e| format!("Unable to create '{}': {}", section_dir.display(), e))?;
+        for object in &objects {
+            write_json(&section_dir.join(config_file_name(&object.name)), object)?;
+        }
+
+        manifest
+            .sections
+            .insert(section.dir.to_string(), objects.len());
+    }
+
+    write_json(&dir_path.join(MANIFEST_FILE), &manifest)?;
+
+    println!("Exported cluster configuration to: {}", dir);
+
+    Ok(ClusterConfigExportSummary { dir, manifest })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigImportItem {
+    // "cluster_settings" or one of the archive directories, e.g. "ilm_policies"
+    pub section: String,
+    pub name: String,
+    // "new", "changed" or "unchanged" compared to the target cluster
+    pub status: String,
+    // "created", "updated", "skipped", "conflict" or "failed"; prefixed "would_" on dry runs
+    pub action: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterConfigImportReport {
+    pub dry_run: bool,
+    pub source_cluster: Option<String>,
+    pub items: Vec<ConfigImportItem>,
+    pub applied: usize,
+    pub conflicts: usize,
+    pub failed: usize,
+}
+
+fn read_section_objects(section_dir: &Path) -> Result<Vec<ConfigObject>, String> {
+    if !section_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries: Vec<std::path::PathBuf> = std::fs::read_dir(section_dir)
+        .map_err(|e| format!("Unable to read '{}': {}", section_dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    entries.sort();
+
+    entries
+        .iter()
+        .map(|path| {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|e| format!("Unable to read '{}': {}", path.display(), e))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| format!("Invalid config file '{}': {}", path.display(), e))
+        })
+        .collect()
+}
+
+// Picks the action for one object; `selected` holds "section/name" keys when the user narrowed it down
+fn plan_action(
+    key: &str,
+    status: &str,
+    selected: Option<&Vec<String>>,
+    overwrite: bool,
+) -> &'static str {
+    if status == "unchanged" || selected.is_some_and(|keys| !keys.iter().any(|k| k == key)) {
+        "skipped"
+    } else if status == "changed" && !overwrite {
+        "conflict"
+    } else if status == "new" {
+        "created"
+    } else {
+        "updated"
+    }
+}
+
+// A section as the target has it now, or None when it answers 404 because it has none of these yet
+async fn get_current_section(
+    transport: &Transport,
+    path: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let response = transport.send(Method::GET, path, None).await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(format!(
+            "request to {} failed: {}",
+            path,
+            describe_error_response(response).await
+        ));
+    }
+
+    response.json().await.map(Some).map_err(|e| e.to_string())
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn import_cluster_config(
+    window: Window,
+    dir: String,
+    dry_run: Option<bool>,
+    selected: Option<Vec<String>>,
+    overwrite: Option<bool>,
+    include_cluster_settings: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<ClusterConfigImportReport, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let dir = resolve_export_path(dir);
+    let dir_path = Path::new(&dir);
+    // Nothing is written unless the caller explicitly turns the dry run off
+    let dry_run = dry_run.unwrap_or(true);
+    let overwrite = overwrite.unwrap_or(false);
+
+    let manifest: Option<ClusterConfigManifest> =
+        std::fs::read_to_string(dir_path.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok());
+    if manifest.is_none() && !dir_path.join(CLUSTER_SETTINGS_FILE).exists() {
+        return Err(format!(
+            "'{}' doesn't look like a cluster configuration export",
+            dir
+        ));
+    }
+
+    let mut items = Vec::new();
+
+    if include_cluster_settings.unwrap_or(false) {
+        let contents = std::fs::read_to_string(dir_path.join(CLUSTER_SETTINGS_FILE))
+            .map_err(|e| format!("Unable to read {}: {}", CLUSTER_SETTINGS_FILE, e))?;
+        let archived: serde_json::Value =
+            serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+        let current = transport.get_json("/_cluster/settings").await?;
+
+        // Only persistent settings are applied, transient ones don't survive a restart anyway
+        let status = if current["persistent"] == archived["persistent"] {
+            "unchanged"
+        } else {
+            "changed"
+        };
+        let action = plan_action(
+            "cluster_settings/persistent",
+            status,
+            selected.as_ref(),
+            overwrite,
+        );
+        let mut item = ConfigImportItem {
+            section: "cluster_settings".to_string(),
+            name: "persistent".to_string(),
+            status: status.to_string(),
+            action: action.to_string(),
+            error: None,
+        };
+
+        if !dry_run && action == "updated" {
+            let body = serde_json::json!({ "persistent": archived["persistent"] });
+            if let Err(e) = transport
+                .json(Method::PUT, "/_cluster/settings", Some(&body))
+                .await
+            {
+                item.action = "failed".to_string();
+                item.error = Some(e);
+            }
+        }
+        items.push(item);
+    }
+
+    for section in CONFIG_SECTIONS {
+        let archived = read_section_objects(&dir_path.join(section.dir))?;
+        if archived.is_empty() {
+            continue;
+        }
+
+        let current: BTreeMap<String, serde_json::Value> =
+            match get_current_section(&transport, section.path).await {
+                Ok(Some(data)) => (section.extract)(&data)
+                    .into_iter()
+                    .map(|object| (object.name, object.body))
+                    .collect(),
+                Ok(None) => BTreeMap::new(),
+                Err(e) => {
+                    for object in archived {
+                        items.push(ConfigImportItem {
+                            section: section.dir.to_string(),
+                            name: object.name,
+                            status: "unknown".to_string(),
+                            action: "failed".to_string(),
+                            error: Some(e.clone()),
+                        });
+                    }
+                    continue;
+                }
+            };
+
+        for object in archived {
+            let status = match current.get(&object.name) {
+                None => "new",
+                Some(body) if *body == object.body => "unchanged",
+                Some(_) => "changed",
+            };
+            let key = format!("{}/{}", section.dir, object.name);
+            let action = plan_action(&key, status, selected.as_ref(), overwrite);
+            let mut item = ConfigImportItem {
+                section: section.dir.to_string(),
+                name: object.name.clone(),
+                status: status.to_string(),
+                action: action.to_string(),
+                error: None,
+            };
+
+            if !dry_run && matches!(action, "created" | "updated") {
+                let path = format!("{}/{}", section.path, encode_segment(&object.name));
+                if let Err(e) = transport.json(Method::PUT, &path, Some(&object.body)).await {
+                    item.action = "failed".to_string();
+                    item.error = Some(e);
+                }
+            }
+            items.push(item);
+        }
+    }
+
+    let applied = items
+        .iter()
+        .filter(|item| matches!(item.action.as_str(), "created" | "updated"))
+        .count();
+    let conflicts = items
+        .iter()
+        .filter(|item| item.action == "conflict")
+        .count();
+    let failed = items.iter().filter(|item| item.action == "failed").count();
+
+    if dry_run {
+        for item in &mut items {
+            if matches!(item.action.as_str(), "created" | "updated") {
+                item.action = format!("would_{}", item.action.trim_end_matches('d'));
+            }
+        }
+    } else {
+        println!("Applied {} configuration objects from: {}", applied, dir);
+    }
+
+    Ok(ClusterConfigImportReport {
+        dry_run,
+        source_cluster: manifest.map(|m| m.cluster_name),
+        items,
+        applied: if dry_run { 0 } else { applied },
+        conflicts,
+        failed,
+    })
+}
@@ -0,0 +1,128 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/confirmations.rs
+
+use crate::elasticsearch::current_connection;
+use crate::storage::app_settings;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tauri::{command, Window};
+
+const TOKEN_TTL_MS: i64 = 60_000;
+
+// Operations that can't be undone and only run with a token the user asked for just before.
+// Each is named after its command; find_duplicates only needs one to delete the extras.
+const CONFIRMED_ACTIONS: &[&str] = &[
+    "reset_features",
+    "delete_elasticsearch_index",
+    "delete_all_documents_in_index",
+    "delete_elasticsearch_documents",
+    "delete_snapshot",
+    "delete_snapshot_repository",
+    "start_delete_by_query_task",
+    "find_duplicates",
+];
+
+struct PendingConfirmation {
+    token: String,
+    connection_id: String,
+    action: String,
+    target: String,
+    expires_at: i64,
+}
+
+static PENDING: Lazy<Mutex<Vec<PendingConfirmation>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmationToken {
+    pub token: String,
+    pub action: String,
+    pub target: String,
+    pub expires_at: i64,
+}
+
+fn new_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| "Unable to generate a confirmation token".to_string())?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+// Issues a single-use token for one action on one target of the window's connection. The UI
+// asks for it once the user has confirmed, and passes it to the destructive command.
+#[command]
+pub fn request_confirmation_token(
+    window: Window,
+    action: String,
+    target: String,
+) -> Result<ConfirmationToken, String> {
+    if !CONFIRMED_ACTIONS.contains(&action.as_str()) {
+        return Err(format!("'{}' doesn't need a confirmation token", action));
+    }
+
+    let (conn, _) = current_connection(window.label())?;
+    let now = chrono::Utc::now().timestamp_millis();
+    let token = ConfirmationToken {
+        token: new_token()?,
+        action,
+        target,
+        expires_at: now + TOKEN_TTL_MS,
+    };
+
+    let mut pending = PENDING.lock();
+    pending.retain(|p| p.expires_at > now);
+    pending.push(PendingConfirmation {
+        token: token.token.clone(),
+        connection_id: conn.id,
+        action: token.action.clone(),
+        target: token.target.clone(),
+        expires_at: token.expires_at,
+    });
+
+    Ok(token)
+}
+
+// Checks and uses up a token; it only matches the connection, action and target it was issued for
+pub(crate) fn consume_confirmation_token(
+    connection_id: &str,
+    action: &str,
+    target: &str,
+    token: &str,
+) -> Result<(), String> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut pending = PENDING.lock();
+    pending.retain(|p| p.expires_at > now);
+
+    let position = pending
+        .iter()
+        .position(|p| {
+            p.token == token
+                && p.connection_id == connection_id
+                && p.action == action
+                && p.target == target
+        })
+        .ok_or("Confirmation token is invalid or has expired, confirm the operation again")?;
+    pending.remove(position);
+
+    Ok(())
+}
+
+// For destructive commands that take the token as an optional argument. A token that is passed
+// is always checked; going without one is only allowed with confirm_before_delete turned off.
+pub(crate) fn require_confirmation(
+    connection_id: &str,
+    action: &str,
+    target: &str,
+    token: Option<&str>,
+) -> Result<(), String> {
+    match token {
+        Some(token) => consume_confirmation_token(connection_id, action, target, token),
+        None if !app_settings().confirm_before_delete => Ok(()),
+        None => Err(format!(
+            "{} on '{}' needs a confirmation token, request one once the user has confirmed",
+            action, target
+        )),
+    }
+}
@@ -0,0 +1,627 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/demo.rs
+
+use crate::elasticsearch::{connect_to_elasticsearch, ElasticsearchConnection};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{command, Window};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const DEMO_CONNECTION_ID: &str = "demo";
+const DEMO_CLUSTER_NAME: &str = "elastiko-demo";
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+struct DemoServer {
+    port: u16,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static DEMO_SERVER: Lazy<Mutex<Option<DemoServer>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemoModeStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    pub connection: Option<ElasticsearchConnection>,
+}
+
+struct DemoIndex {
+    name: &'static str,
+    health: &'static str,
+    mappings: Value,
+    documents: Vec<Value>,
+}
+
+// Fixed fixtures, the server never changes them so every demo session looks the same
+static FIXTURES: Lazy<Vec<DemoIndex>> = Lazy::new(|| {
+    vec![
+        DemoIndex {
+            name: "demo-products",
+            health: "green",
+            mappings: json!({
+                "properties": {
+                    "name": { "type": "text", "fields": { "keyword": { "type": "keyword" } } },
+                    "category": { "type": "keyword" },
+                    "price": { "type": "double" },
+                    "in_stock": { "type": "boolean" },
+                    "created_at": { "type": "date" }
+                }
+            }),
+            documents: vec![
+                json!({ "_id": "p1", "name": "Mechanical keyboard", "category": "peripherals", "price": 89.0, "in_stock": true, "created_at": "2024-01-12T09:30:00Z" }),
+                json!({ "_id": "p2", "name": "Wireless mouse", "category": "peripherals", "price": 29.5, "in_stock": true, "created_at": "2024-01-15T14:10:00Z" }),
+                json!({ "_id": "p3", "name": "27 inch monitor", "category": "displays", "price": 249.0, "in_stock": false, "created_at": "2024-02-02T11:00:00Z" }),
+                json!({ "_id": "p4", "name": "USB-C dock", "category": "accessories", "price": 119.0, "in_stock": true, "created_at": "2024-02-20T16:45:00Z" }),
+                json!({ "_id": "p5", "name": "Noise cancelling headphones", "category": "audio", "price": 199.0, "in_stock": true, "created_at": "2024-03-05T08:20:00Z" }),
+                json!({ "_id": "p6", "name": "Webcam 1080p", "category": "peripherals", "price": 59.0, "in_stock": false, "created_at": "2024-03-18T13:05:00Z" }),
+            ],
+        },
+        DemoIndex {
+            name: "demo-logs",
+            health: "yellow",
+            mappings: json!({
+                "properties": {
+                    "@timestamp": { "type": "date" },
+                    "level": { "type": "keyword" },
+                    "service": { "type": "keyword" },
+                    "message": { "type": "text" },
+                    "duration_ms": { "type": "long" }
+                }
+            }),
+            documents: vec![
+                json!({ "_id": "l1", "@timestamp": "2024-04-01T10:00:00Z", "level": "info", "service": "api", "message": "GET /orders completed", "duration_ms": 42 }),
+                json!({ "_id": "l2", "@timestamp": "2024-04-01T10:00:05Z", "level": "warn", "service": "api", "message": "Slow query on orders table", "duration_ms": 1830 }),
+                json!({ "_id": "l3", "@timestamp": "2024-04-01T10:00:09Z", "level": "error", "service": "billing", "message": "Payment provider timeout", "duration_ms": 30000 }),
+                json!({ "_id": "l4", "@timestamp": "2024-04-01T10:01:12Z", "level": "info", "service": "worker", "message": "Processed 120 jobs", "duration_ms": 950 }),
+                json!({ "_id": "l5", "@timestamp": "2024-04-01T10:02:30Z", "level": "info", "service": "api", "message": "POST /orders completed", "duration_ms": 77 }),
+                json!({ "_id": "l6", "@timestamp": "2024-04-01T10:03:44Z", "level": "error", "service": "api", "message": "Upstream returned 502", "duration_ms": 120 }),
+                json!({ "_id": "l7", "@timestamp": "2024-04-01T10:04:01Z", "level": "debug", "service": "worker", "message": "Queue depth 3", "duration_ms": 1 }),
+            ],
+        },
+        DemoIndex {
+            name: "demo-customers",
+            health: "green",
+            mappings: json!({
+                "properties": {
+                    "name": { "type": "text" },
+                    "email": { "type": "keyword" },
+                    "country": { "type": "keyword" },
+                    "lifetime_value": { "type": "double" },
+                    "location": { "type": "geo_point" }
+                }
+            }),
+            documents: vec![
+                json!({ "_id": "c1", "name": "Ada Lovelace", "email": "ada@example.com", "country": "GB", "lifetime_value": 1520.0, "location": { "lat": 51.5, "lon": -0.12 } }),
+                json!({ "_id": "c2", "name": "Grace Hopper", "email": "grace@example.com", "country": "US", "lifetime_value": 980.5, "location": { "lat": 38.9, "lon": -77.03 } }),
+                json!({ "_id": "c3", "name": "Alan Turing", "email": "alan@example.com", "country": "GB", "lifetime_value": 2210.0, "location": { "lat": 52.2, "lon": 0.12 } }),
+                json!({ "_id": "c4", "name": "Margaret Hamilton", "email": "margaret@example.com", "country": "US", "lifetime_value": 640.0, "location": { "lat": 42.36, "lon": -71.06 } }),
+            ],
+        },
+    ]
+});
+
+fn demo_connection(port: u16) -> ElasticsearchConnection {
+    ElasticsearchConnection {
+        id: DEMO_CONNECTION_ID.to_string(),
+        name: "Demo cluster".to_string(),
+        host: "127.0.0.1".to_string(),
+        port,
+        username: None,
+        password: None,
+        ssl: Some(false),
+        api_key: None,
+        auth_type: "none".to_string(),
+        defaults: None,
+    }
+}
+
+fn error_body(status: u16, error_type: &str, reason: String) -> (u16, Value) {
+    (
+        status,
+        json!({
+            "error": {
+                "root_cause": [{ "type": error_type, "reason": reason }],
+                "type": error_type,
+                "reason": reason
+            },
+            "status": status
+        }),
+    )
+}
+
+fn find_index(name: &str) -> Result<&'static DemoIndex, (u16, Value)> {
+    FIXTURES
+        .iter()
+        .find(|index| index.name == name)
+        .ok_or_else(|| {
+            error_body(
+                404,
+                "index_not_found_exception",
+                format!("no such index [{}]", name),
+            )
+        })
+}
+
+// Index expressions are comma separated and may use `*`, `_all` or be empty
+fn resolve_indices(expression: &str) -> Vec<&'static DemoIndex> {
+    let patterns: Vec<&str> = expression.split(',').filter(|p| !p.is_empty()).collect();
+    FIXTURES
+        .iter()
+        .filter(|index| {
+            patterns.is_empty()
+                || patterns
+                    .iter()
+                    .any(|pattern| match pattern.strip_suffix('*') {
+                        _ if *pattern == "_all" || *pattern == "*" => true,
+                        Some(prefix) => index.name.starts_with(prefix),
+                        None => index.name == *pattern,
+                    })
+        })
+        .collect()
+}
+
+fn source_of(doc: &Value) -> Value {
+    let mut source = doc.clone();
+    if let Some(object) = source.as_object_mut() {
+        object.remove("_id");
+    }
+    source
+}
+
+fn value_matches(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::String(actual), Value::String(wanted)) => {
+            actual.to_lowercase().contains(&wanted.to_lowercase())
+        }
+        (Value::Array(items), _) => items.iter().any(|item| value_matches(item, expected)),
+        (actual, wanted) => actual == wanted,
+    }
+}
+
+fn field_value<'a>(source: &'a Value, field: &str) -> Option<&'a Value> {
+    let field = field.strip_suffix(".keyword").unwrap_or(field);
+    source.get(field).or_else(|| {
+        field
+            .split('.')
+            .try_fold(source, |value, part| value.get(part))
+    })
+}
+
+// Enough of the query DSL for the UI's common searches, anything else matches every document
+fn matches_query(source: &Value, query: &Value) -> bool {
+    let Some((kind, params)) = query.as_object().and_then(|q| q.iter().next()) else {
+        return true;
+    };
+
+    match kind.as_str() {
+        "term" | "match" | "match_phrase" => params
+            .as_object()
+            .and_then(|p| p.iter().next())
+            .map(|(field, expected)| {
+                let expected = expected
+                    .get("query")
+                    .or_else(|| expected.get("value"))
+                    .unwrap_or(expected);
+                field_value(source, field)
+                    .map(|value| value_matches(value, expected))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true),
+        "terms" => params
+            .as_object()
+            .and_then(|p| p.iter().next())
+            .map(|(field, expected)| {
+                let options = expected.as_array().cloned().unwrap_or_default();
+                field_value(source, field)
+                    .map(|value| options.iter().any(|option| value == option))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true),
+        "exists" => params["field"]
+            .as_str()
+            .map(|field| field_value(source, field).is_some())
+            .unwrap_or(true),
+        "query_string" | "simple_query_string" => {
+            let text = params["query"].as_str().unwrap_or("*").trim();
+            text.is_empty()
+                || text == "*"
+                || source
+                    .to_string()
+                    .to_lowercase()
+                    .contains(&text.to_lowercase())
+        }
+        "bool" => {
+            let clauses = |name: &str| -> Vec<Value> {
+                match &params[name] {
+                    Value::Array(items) => items.clone(),
+                    Value::Null => Vec::new(),
+                    single => vec![single.clone()],
+                }
+            };
+            let required = clauses("must")
+                .into_iter()
+                .chain(clauses("filter"))
+                .all(|clause| matches_query(source, &clause));
+            let excluded = clauses("must_not")
+                .iter()
+                .any(|clause| matches_query(source, clause));
+            let should = clauses("should");
+            required
+                && !excluded
+                && (should.is_empty() || should.iter().any(|clause| matches_query(source, clause)))
+        }
+        _ => true,
+    }
+}
+
+fn search(expression: &str, body: &Value) -> (u16, Value) {
+    let indices = resolve_indices(expression);
+    if indices.is_empty() {
+        return error_body(
+            404,
+            "index_not_found_exception",
+            format!("no such index [{}]", expression),
+        );
+    }
+
+    let query = body.get("query").cloned().unwrap_or(Value::Null);
+    let from = body["from"].as_u64().unwrap_or(0) as usize;
+    let size = body["size"].as_u64().unwrap_or(10) as usize;
+
+    let matching: Vec<Value> = indices
+        .iter()
+        .flat_map(|index| {
+            index.documents.iter().filter_map(|doc| {
+                let source = source_of(doc);
+                matches_query(&source, &query).then(|| {
+                    json!({
+                        "_index": index.name,
+                        "_id": doc["_id"],
+                        "_score": 1.0,
+                        "_source": source
+                    })
+                })
+            })
+        })
+        .collect();
+
+    (
+        200,
+        json!({
+            "took": 1,
+            "timed_out": false,
+            "_shards": { "total": indices.len(), "successful": indices.len(), "skipped": 0, "failed": 0 },
+            "hits": {
+                "total": { "value": matching.len(), "relation": "eq" },
+                "max_score": if matching.is_empty() { Value::Null } else { json!(1.0) },
+                "hits": matching.into_iter().skip(from).take(size).collect::<Vec<_>>()
+            }
+        }),
+    )
+}
+
+fn count(expression: &str, body: &Value) -> (u16, Value) {
+    let indices = resolve_indices(expression);
+    let query = body.get("query").cloned().unwrap_or(Value::Null);
+    let total = indices
+        .iter()
+        .flat_map(|index| index.documents.iter())
+        .filter(|doc| matches_query(&source_of(doc), &query))
+        .count();
+
+    (
+        200,
+        json!({
+            "count": total,
+            "_shards": { "total": indices.len(), "successful": indices.len(), "skipped": 0, "failed": 0 }
+        }),
+    )
+}
+
+fn cluster_health() -> Value {
+    let yellow = FIXTURES.iter().any(|index| index.health == "yellow");
+    json!({
+        "cluster_name": DEMO_CLUSTER_NAME,
+        "status": if yellow { "yellow" } else { "green" },
+        "timed_out": false,
+        "number_of_nodes": 1,
+        "number_of_data_nodes": 1,
+        "active_primary_shards": FIXTURES.len(),
+        "active_shards": FIXTURES.len(),
+        "relocating_shards": 0,
+        "initializing_shards": 0,
+        "unassigned_shards": FIXTURES.iter().filter(|index| index.health == "yellow").count(),
+        "delayed_unassigned_shards": 0,
+        "number_of_pending_tasks": 0,
+        "number_of_in_flight_fetch": 0,
+        "task_max_waiting_in_queue_millis": 0,
+        "active_shards_percent_as_number": 100.0
+    })
+}
+
+fn cat_indices() -> Value {
+    Value::Array(
+        FIXTURES
+            .iter()
+            .map(|index| {
+                let size_kb = index
+                    .documents
+                    .iter()
+                    .map(|doc| doc.to_string().len())
+                    .sum::<usize>()
+                    / 1024
+                    + 4;
+                json!({
+                    "health": index.health,
+                    "status": "open",
+                    "index": index.name,
+                    "uuid": format!("{}-uuid", index.name),
+                    "pri": "1",
+                    "rep": if index.health == "yellow" { "1" } else { "0" },
+                    "docs.count": index.documents.len().to_string(),
+                    "docs.deleted": "0",
+                    "store.size": format!("{}kb", size_kb),
+                    "pri.store.size": format!("{}kb", size_kb)
+                })
+            })
+            .collect(),
+    )
+}
+
+fn cat_nodes() -> Value {
+    json!([{
+        "ip": "127.0.0.1",
+        "heap.percent": "35",
+        "ram.percent": "62",
+        "cpu": "4",
+        "load_1m": "0.42",
+        "load_5m": "0.38",
+        "load_15m": "0.31",
+        "node.role": "cdfhilmrstw",
+        "master": "*",
+        "name": "demo-node-1"
+    }])
+}
+
+fn index_section(expression: &str, section: &str) -> (u16, Value) {
+    let indices = resolve_indices(expression);
+    if indices.is_empty() {
+        return find_index(expression)
+            .map(|_| (200, json!({})))
+            .unwrap_or_else(|e| e);
+    }
+
+    let mut body = serde_json::Map::new();
+    for index in indices {
+        let value = match section {
+            "_mapping" => json!({ "mappings": index.mappings }),
+            _ => json!({
+                "settings": {
+                    "index": {
+                        "number_of_shards": "1",
+                        "number_of_replicas": if index.health == "yellow" { "1" } else { "0" },
+                        "provided_name": index.name,
+                        "uuid": format!("{}-uuid", index.name)
+                    }
+                }
+            }),
+        };
+        body.insert(index.name.to_string(), value);
+    }
+    (200, Value::Object(body))
+}
+
+fn get_document(index: &str, id: &str) -> (u16, Value) {
+    let index = match find_index(index) {
+        Ok(index) => index,
+        Err(e) => return e,
+    };
+    match index.documents.iter().find(|doc| doc["_id"] == id) {
+        Some(doc) => (
+            200,
+            json!({
+                "_index": index.name,
+                "_id": id,
+                "_version": 1,
+                "found": true,
+                "_source": source_of(doc)
+            }),
+        ),
+        None => (
+            404,
+            json!({ "_index": index.name, "_id": id, "found": false }),
+        ),
+    }
+}
+
+fn route(method: &str, target: &str, body: &Value) -> (u16, Value) {
+    let path = target.split('?').next().unwrap_or("/");
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            percent_encoding::percent_decode_str(s)
+                .decode_utf8_lossy()
+                .into_owned()
+        })
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let read = method == "GET" || method == "HEAD" || method == "POST";
+
+    match (read, segments.as_slice()) {
+        (true, []) => (
+            200,
+            json!({
+                "name": "demo-node-1",
+                "cluster_name": DEMO_CLUSTER_NAME,
+                "cluster_uuid": "demo-cluster-uuid",
+                "version": { "number": "8.13.0", "build_flavor": "default", "lucene_version": "9.10.0" },
+                "tagline": "You Know, for Search"
+            }),
+        ),
+        (true, ["_cluster", "health", ..]) => (200, cluster_health()),
+        (true, ["_cat", "indices", ..]) => (200, cat_indices()),
+        (true, ["_cat", "nodes"]) => (200, cat_nodes()),
+        (true, ["_search"]) => search("", body),
+        (true, ["_count"]) => count("", body),
+        (true, [index, "_search"]) => search(index, body),
+        (true, [index, "_count"]) => count(index, body),
+        (true, [index, section @ ("_mapping" | "_settings")]) => index_section(index, section),
+        (true, [index, "_doc", id]) if method != "POST" => get_document(index, id),
+        (true, [index]) if method != "POST" => match find_index(index) {
+            Ok(_) => index_section(index, "_mapping"),
+            Err(e) => e,
+        },
+        (false, _) | (true, [_, "_doc", ..]) | (true, [_]) => error_body(
+            405,
+            "illegal_argument_exception",
+            "The demo cluster is read-only".to_string(),
+        ),
+        _ => error_body(
+            400,
+            "illegal_argument_exception",
+            format!("The demo cluster does not support {} {}", method, path),
+        ),
+    }
+}
+
+fn header_end(buffer: &[u8]) -> Option<usize> {
+    buffer
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map(|i| i + 4)
+}
+
+async fn handle_connection(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+
+    let head_len = loop {
+        if let Some(end) = header_end(&buffer) {
+            break end;
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 || buffer.len() > MAX_REQUEST_BYTES {
+            return Ok(());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_len]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or("GET").to_string();
+    let target = request_line.next().unwrap_or("/").to_string();
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0)
+        .min(MAX_REQUEST_BYTES);
+
+    while buffer.len() < head_len + content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let body_end = buffer.len().min(head_len + content_length);
+    let body: Value = serde_json::from_slice(&buffer[head_len..body_end]).unwrap_or(Value::Null);
+    let (status, payload) = route(&method, &target, &body);
+
+    let payload = payload.to_string();
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Error",
+    };
+    let mut response = format!(
+        "HTTP/1.1 {} {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nx-elastic-product: Elasticsearch\r\nconnection: close\r\n\r\n",
+        status,
+        reason,
+        payload.len()
+    );
+    if method != "HEAD" {
+        response.push_str(&payload);
+    }
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn ensure_server() -> Result<u16, String> {
+    if let Some(server) = DEMO_SERVER.lock().as_ref() {
+        return Ok(server.port);
+    }
+
+    // Port 0 lets the OS pick a free port, the connection is pointed at whatever we got
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| format!("Failed to start the demo server: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = handle_connection(stream).await {
+                            println!("Demo server request failed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    println!("Demo server stopped accepting connections: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut server = DEMO_SERVER.lock();
+    // Another window may have started one while we were binding, keep the first
+    if let Some(existing) = server.as_ref() {
+        task.abort();
+        return Ok(existing.port);
+    }
+    *server = Some(DemoServer { port, task });
+
+    println!("Demo server listening on 127.0.0.1:{}", port);
+    Ok(port)
+}
+
+#[command]
+pub async fn start_demo_mode(window: Window) -> Result<serde_json::Value, String> {
+    let port = ensure_server().await?;
+    connect_to_elasticsearch(window, demo_connection(port), None).await
+}
+
+#[command]
+pub fn stop_demo_mode() -> Result<bool, String> {
+    match DEMO_SERVER.lock().take() {
+        Some(server) => {
+            server.task.abort();
+            println!("Stopped demo server on port {}", server.port);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[command]
+pub fn get_demo_mode_status() -> Result<DemoModeStatus, String> {
+    let port = DEMO_SERVER.lock().as_ref().map(|s| s.port);
+
+    Ok(DemoModeStatus {
+        running: port.is_some(),
+        port,
+        connection: port.map(demo_connection),
+    })
+}
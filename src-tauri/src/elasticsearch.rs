@@ -2,21 +2,35 @@
 // elastico/src-tauri/src/elasticsearch.rs
 
 use serde::{Deserialize, Serialize};
-use reqwest::Client as ReqwestClient;
+use reqwest::{Client as ReqwestClient, Method};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
-use tauri::command;
-use std::collections::HashMap;
+use tauri::{command, AppHandle, Emitter, Window};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use parking_lot::Mutex;
 use once_cell::sync::Lazy;
 use base64::{Engine as _, engine::general_purpose::STANDARD};
+use crate::confirmations::{consume_confirmation_token, require_confirmation};
+use crate::large_responses::release_large_responses;
+use crate::search_contexts::release_search_contexts;
+use crate::secrets;
+use crate::storage::{app_settings, data_dir};
+use crate::tasks::TaskHandle;
+use crate::transport::{cancellable, encode_query, encode_segment, encode_segments, ConnectTimingLayer, RequestExt, TimedResolver, Transport};
 
-// Shared client state
-static CONNECTION: Lazy<Mutex<Option<ElasticsearchConnection>>> = Lazy::new(|| Mutex::new(None));
+// Shared client state; each app window has its own active connection, keyed by window label
+static CONNECTIONS: Lazy<Mutex<HashMap<String, ElasticsearchConnection>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 // Initialize the client with accept_invalid_certs set to true
 static CLIENT: Lazy<Mutex<Option<ReqwestClient>>> = Lazy::new(|| {
     // Create a client builder that accepts invalid certificates
     let client_builder = reqwest::ClientBuilder::new()
         .danger_accept_invalid_certs(true)
+        // Ask for compressed bodies and decode them transparently. Elasticsearch only compresses
+        // when http.compression is on, which 8.x leaves off for HTTPS unless set explicitly.
+        .gzip(true)
+        .deflate(true)
+        // Time DNS lookups and the connect and TLS handshake of each new connection
+        .dns_resolver(std::sync::Arc::new(TimedResolver))
+        .connector_layer(ConnectTimingLayer)
         .timeout(std::time::Duration::from_secs(30)); // Also add a reasonable timeout
     
     match client_builder.build() {
@@ -24,6 +38,8 @@ static CLIENT: Lazy<Mutex<Option<ReqwestClient>>> = Lazy::new(|| {
         Err(_) => Mutex::new(Some(ReqwestClient::new())), // Fallback to default if builder fails
     }
 });
+// Security service tokens by connection id, kept in memory only and never handed to the frontend
+static TOKEN_SESSIONS: Lazy<Mutex<HashMap<String, TokenSession>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ElasticsearchConnection {
@@ -35,7 +51,47 @@ pub struct ElasticsearchConnection {
     pub password: Option<String>,
     pub ssl: Option<bool>,
     pub api_key: Option<String>,
-    pub auth_type: String, // "none", "basic", or "apiKey"
+    pub auth_type: String, // "none", "basic", "apiKey", or "token"
+    // Search defaults for this connection; missing in connections saved by older versions
+    #[serde(default)]
+    pub defaults: Option<ConnectionDefaults>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConnectionDefaults {
+    // Overrides the app-wide default page size for queries and samples without a size
+    pub page_size: Option<u32>,
+    // true, false or a hit count to count up to; used by queries, samples, field stats, the
+    // quality check and the top values and time series charts. Exports always count every hit
+    // to report progress.
+    pub track_total_hits: Option<serde_json::Value>,
+    pub timeout_ms: Option<u64>,
+    // Searched when a query, sample, chart, analysis or export is run without an index. Index
+    // backups (export_index) need a concrete index and don't use it.
+    pub index_pattern: Option<String>,
+}
+
+// An explicit timeout wins over the connection default; None leaves it to the app settings
+pub(crate) fn connection_timeout(conn: &ElasticsearchConnection, timeout_ms: Option<u64>) -> Option<u64> {
+    timeout_ms.or_else(|| conn.defaults.as_ref().and_then(|defaults| defaults.timeout_ms))
+}
+
+// An empty index falls back to the connection's default pattern
+pub(crate) fn connection_index(conn: &ElasticsearchConnection, index: &str) -> Result<String, String> {
+    match index.trim() {
+        "" => conn.defaults.as_ref()
+            .and_then(|defaults| defaults.index_pattern.clone())
+            .filter(|p| !p.trim().is_empty())
+            .ok_or_else(|| "No index given and the connection has no default index pattern".to_string()),
+        _ => Ok(index.to_string()),
+    }
+}
+
+// track_total_hits for searches that report a total, exact unless the connection says otherwise
+pub(crate) fn connection_track_total_hits(conn: &ElasticsearchConnection) -> serde_json::Value {
+    conn.defaults.as_ref()
+        .and_then(|defaults| defaults.track_total_hits.clone())
+        .unwrap_or(serde_json::Value::Bool(true))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,12 +137,33 @@ pub struct ClusterHealth {
     pub pending_tasks: u32,
 }
 
-fn get_base_url(conn: &ElasticsearchConnection) -> String {
+pub(crate) fn get_base_url(conn: &ElasticsearchConnection) -> String {
     let protocol = if conn.ssl.unwrap_or(false) { "https" } else { "http" };
     format!("{}://{}:{}", protocol, conn.host, conn.port)
 }
 
-fn create_auth_headers(conn: &ElasticsearchConnection) -> Result<HeaderMap, String> {
+// Snapshot of a window's active connection and the client for modules outside this file
+pub(crate) fn current_connection(window_label: &str) -> Result<(ElasticsearchConnection, ReqwestClient), String> {
+    let conn_guard = CONNECTIONS.lock();
+    let client_guard = CLIENT.lock();
+    
+    let conn = conn_guard.get(window_label).ok_or("Not connected to Elasticsearch")?.clone();
+    let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
+    
+    Ok((conn, client))
+}
+
+// The client every connection shares, for work that isn't tied to a window
+pub(crate) fn shared_client() -> Result<ReqwestClient, String> {
+    CLIENT.lock().clone().ok_or_else(|| "HTTP client not available".to_string())
+}
+
+// An open connection by id, whichever window it's active in
+pub(crate) fn connection_by_id(connection_id: &str) -> Option<ElasticsearchConnection> {
+    CONNECTIONS.lock().values().find(|conn| conn.id == connection_id).cloned()
+}
+
+pub(crate) fn create_auth_headers(conn: &ElasticsearchConnection) -> Result<HeaderMap, String> {
     let mut headers = HeaderMap::new();
     headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
     
@@ -102,37 +179,88 @@ fn create_auth_headers(conn: &ElasticsearchConnection) -> Result<HeaderMap, Stri
             headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("ApiKey {}", api_key))
                 .map_err(|e| e.to_string())?);
         }
+    } else if conn.auth_type == "token" {
+        let access_token = TOKEN_SESSIONS.lock().get(&conn.id).map(|session| session.access_token.clone())
+            .ok_or("No security token for this connection, request a new one")?;
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", access_token))
+            .map_err(|e| e.to_string())?);
     }
     
     Ok(headers)
 }
 
+// Boils an Elasticsearch error body down to "type: reason", plus the root cause when it adds
+// something; bodies that aren't Elasticsearch errors come back unchanged
+pub(crate) fn describe_error_body(body: &str) -> String {
+    let parsed: serde_json::Value = match serde_json::from_str(body) {
+        Ok(parsed) => parsed,
+        Err(_) => return body.to_string()
+    };
+    let error = &parsed["error"];
+    
+    // Very old clusters report the error as a plain string
+    if let Some(message) = error.as_str() {
+        return message.to_string();
+    }
+    
+    let summarize = |cause: &serde_json::Value| -> Option<String> {
+        let reason = cause["reason"].as_str()?;
+        Some(match cause["type"].as_str() {
+            Some(error_type) => format!("{}: {}", error_type, reason),
+            None => reason.to_string()
+        })
+    };
+    
+    let mut description = match summarize(error) {
+        Some(description) => description,
+        None => return body.to_string()
+    };
+    
+    // e.g. "search_phase_execution_exception: all shards failed" only makes sense with its root cause
+    match error["root_cause"].get(0).and_then(summarize) {
+        Some(root_cause) if root_cause != description => {
+            description.push_str(&format!(" (root cause: {})", root_cause));
+        }
+        Some(_) => {}
+        None => {
+            if let Some(caused_by) = summarize(&error["caused_by"]) {
+                description.push_str(&format!(" (caused by: {})", caused_by));
+            }
+        }
+    }
+    
+    description
+}
+
+// Status line plus the parsed error body, for failures that used to report only the status code
+pub(crate) async fn describe_error_response(response: reqwest::Response) -> String {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    describe_error_status(status, &text)
+}
+
+// Same as describe_error_response, for callers that already read the body
+pub(crate) fn describe_error_status(status: reqwest::StatusCode, text: &str) -> String {
+    if text.trim().is_empty() {
+        status.to_string()
+    } else {
+        format!("{} - {}", status, describe_error_body(text))
+    }
+}
+
 #[command]
-pub async fn connect_to_elasticsearch(connection: ElasticsearchConnection) -> Result<serde_json::Value, String> {
+pub async fn connect_to_elasticsearch(window: Window, connection: ElasticsearchConnection, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
     // Get a client from our Mutex, then drop the guard immediately
     let client = {
         let client_guard = CLIENT.lock();
         client_guard.as_ref().ok_or("HTTP client not available")?.clone()
     };
     
-    let url = format!("{}", get_base_url(&connection));
+    // Not registered for this window yet, so the transport is built from the given connection
+    let transport = Transport::new(client, connection.clone()).with_timeout(timeout_ms);
     
     // Try to ping the Elasticsearch server
-    let mut request = client.get(&format!("{}/_cluster/health", url));
-    
-    // Add authentication if needed
-    if connection.auth_type == "basic" {
-        if let (Some(username), Some(password)) = (&connection.username, &connection.password) {
-            request = request.basic_auth(username, Some(password));
-        }
-    } else if connection.auth_type == "apiKey" {
-        if let Some(api_key) = &connection.api_key {
-            request = request.header(AUTHORIZATION, format!("ApiKey {}", api_key));
-        }
-    }
-    
-    // Send the request
-    let response = match request.send().await {
+    let response = match transport.send(Method::GET, "/_cluster/health", None).await {
         Ok(resp) => resp,
         Err(e) => {
             // Provide a more user-friendly error message
@@ -157,9 +285,8 @@ pub async fn connect_to_elasticsearch(connection: ElasticsearchConnection) -> Re
         
         println!("Successfully connected to Elasticsearch cluster: {}, status: {}", cluster_name, cluster_status);
         
-        // Save the connection
-        let mut conn = CONNECTION.lock();
-        *conn = Some(connection.clone());
+        // Save the connection for this window only
+        CONNECTIONS.lock().insert(window.label().to_string(), connection.clone());
         
         // Return a rich response with connection details
         let result = serde_json::json!({
@@ -174,7 +301,7 @@ pub async fn connect_to_elasticsearch(connection: ElasticsearchConnection) -> Re
     } else {
         let status = response.status();
         let error_text = match response.text().await {
-            Ok(text) => text,
+            Ok(text) => describe_error_body(&text),
             Err(_) => "Unable to read error response".to_string()
         };
         
@@ -183,33 +310,46 @@ pub async fn connect_to_elasticsearch(connection: ElasticsearchConnection) -> Re
 }
 
 #[command]
-pub fn disconnect_from_elasticsearch() -> Result<bool, String> {
-    let mut conn = CONNECTION.lock();
-    *conn = None;
+pub fn disconnect_from_elasticsearch(window: Window) -> Result<bool, String> {
+    forget_window_connection(window.label());
     Ok(true)
 }
 
-#[command]
-pub async fn get_elasticsearch_indices() -> Result<Vec<ElasticsearchIndex>, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
+// Called on disconnect and when a window closes so its connection doesn't linger. Scrolls and
+// PITs the app left open are closed once no other window is using the same connection.
+pub(crate) fn forget_window_connection(window_label: &str) {
+    let removed = {
+        let mut connections = CONNECTIONS.lock();
+        let removed = connections.remove(window_label);
+        removed.filter(|conn| !connections.values().any(|other| other.id == conn.id))
     };
+    let client = CLIENT.lock().clone();
     
-    let url = format!("{}/_cat/indices?format=json&v=true", get_base_url(&conn));
-    let headers = create_auth_headers(&conn)?;
+    if let Some(conn) = &removed {
+        release_large_responses(&conn.id);
+    }
+    
+    if let (Some(conn), Some(client)) = (removed, client) {
+        tauri::async_runtime::spawn(async move {
+            let cleanup = release_search_contexts(&Transport::new(client, conn)).await;
+            for failure in cleanup.failed {
+                println!("Failed to close search context on disconnect: {}", failure);
+            }
+        });
+    }
+}
+
+#[command]
+pub async fn get_elasticsearch_indices(window: Window, timeout_ms: Option<u64>) -> Result<Vec<ElasticsearchIndex>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_cat/indices?format=json&v=true";
     
     // Send the request
-    let response = client.get(&url).headers(headers).send().await.map_err(|e| e.to_string())?;
+    let response = transport.send(Method::GET, path, None).await?;
     
     if !response.status().is_success() {
-        return Err(format!("Failed to get indices: {}", response.status()));
+        return Err(format!("Failed to get indices: {}", describe_error_response(response).await));
     }
     
     let indices_data: Vec<HashMap<String, String>> = response.json().await.map_err(|e| e.to_string())?;
@@ -230,39 +370,8 @@ pub async fn get_elasticsearch_indices() -> Result<Vec<ElasticsearchIndex>, Stri
     Ok(indices)
 }
 
-#[command]
-pub async fn execute_elasticsearch_query(index: String, query: String) -> Result<QueryResult, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
-    };
-    
-    let url = format!("{}/{}/_search", get_base_url(&conn), index);
-    let headers = create_auth_headers(&conn)?;
-    
-    // Parse and validate the query
-    let query_json: serde_json::Value = serde_json::from_str(&query).map_err(|e| e.to_string())?;
-    
-    // Send the request
-    let response = client.post(&url)
-        .headers(headers)
-        .json(&query_json)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    
-    if !response.status().is_success() {
-        return Err(format!("Failed to execute query: {}", response.status()));
-    }
-    
-    let response_body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
-    
+// Shared by every command that hands raw search hits to the results grid
+pub(crate) fn parse_query_result(response_body: &serde_json::Value) -> Result<QueryResult, String> {
     // Extract values from the response
     let hits = response_body["hits"]["hits"].as_array()
         .ok_or("Invalid response format")?.clone();
@@ -292,27 +401,134 @@ pub async fn execute_elasticsearch_query(index: String, query: String) -> Result
     })
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchOptions {
+    // Override whatever the query body says
+    pub size: Option<u32>,
+    pub from: Option<u32>,
+    pub track_total_hits: Option<serde_json::Value>,
+    // Sent as URL parameters
+    pub routing: Option<String>,
+    pub preference: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchRequestPreview {
+    pub method: String,
+    pub url: String,
+    // Authorization is redacted
+    pub headers: BTreeMap<String, String>,
+    pub body: serde_json::Value,
+    // None means the app-wide default timeout applies
+    pub timeout_ms: Option<u64>,
+}
+
+// Builds the path and body execute_elasticsearch_query sends. Explicit options win over the
+// query body, which wins over the connection defaults and then the app settings.
+fn build_search_request(conn: &ElasticsearchConnection, index: &str, query: &str, options: &SearchOptions) -> Result<(String, serde_json::Value), String> {
+    let defaults = conn.defaults.clone().unwrap_or_default();
+    
+    let index = connection_index(conn, index)?;
+    
+    let mut params = Vec::new();
+    if let Some(routing) = options.routing.as_deref().filter(|r| !r.is_empty()) {
+        params.push(format!("routing={}", encode_query(routing)));
+    }
+    if let Some(preference) = options.preference.as_deref().filter(|p| !p.is_empty()) {
+        params.push(format!("preference={}", encode_query(preference)));
+    }
+    
+    let mut path = format!("/{}/_search", encode_segment(&index));
+    if !params.is_empty() {
+        path.push('?');
+        path.push_str(&params.join("&"));
+    }
+    
+    // Parse and validate the query
+    let mut query_json: serde_json::Value = serde_json::from_str(query).map_err(|e| e.to_string())?;
+    
+    if let Some(body) = query_json.as_object_mut() {
+        if let Some(size) = options.size {
+            body.insert("size".to_string(), serde_json::json!(size));
+        }
+        if let Some(from) = options.from {
+            body.insert("from".to_string(), serde_json::json!(from));
+        }
+        if let Some(track_total_hits) = &options.track_total_hits {
+            body.insert("track_total_hits".to_string(), track_total_hits.clone());
+        }
+        
+        // Queries without an explicit size get the connection's page size, then the app's, instead of ES's default of 10
+        body.entry("size").or_insert_with(|| serde_json::json!(defaults.page_size.unwrap_or_else(|| app_settings().default_page_size)));
+        if let Some(track_total_hits) = &defaults.track_total_hits {
+            body.entry("track_total_hits").or_insert_with(|| track_total_hits.clone());
+        }
+    }
+    
+    Ok((path, query_json))
+}
+
+// Passing a request_id lets cancel_request abort the search, also while its hits are being read
 #[command]
-pub async fn get_elasticsearch_cluster_health() -> Result<ClusterHealth, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
+pub async fn execute_elasticsearch_query(window: Window, index: String, query: String, options: Option<SearchOptions>, request_id: Option<String>, timeout_ms: Option<u64>) -> Result<QueryResult, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let (path, query_json) = build_search_request(transport.connection(), &index, &query, &options.unwrap_or_default())?;
+    
+    let response_body: serde_json::Value = cancellable(request_id, async {
+        // Send the request
+        let response = transport.send(Method::POST, &path, Some(&query_json)).await?;
         
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
+        if !response.status().is_success() {
+            return Err(format!("Failed to execute query: {}", describe_error_response(response).await));
+        }
         
-        (conn, client)
-    };
+        response.json().await.map_err(|e| e.to_string())
+    }).await?;
+    
+    parse_query_result(&response_body)
+}
+
+#[command]
+pub fn preview_search_request(window: Window, index: String, query: String, options: Option<SearchOptions>, timeout_ms: Option<u64>) -> Result<SearchRequestPreview, String> {
+    let conn = CONNECTIONS.lock().get(window.label()).ok_or("Not connected to Elasticsearch")?.clone();
+    
+    let (path, body) = build_search_request(&conn, &index, &query, &options.unwrap_or_default())?;
+    
+    let headers = create_auth_headers(&conn)?
+        .iter()
+        .map(|(name, value)| {
+            let value = if name == AUTHORIZATION {
+                // Keep the scheme so the user can still see which kind of auth is used
+                let scheme = value.to_str().unwrap_or("").split(' ').next().unwrap_or("");
+                format!("{} <redacted>", scheme)
+            } else {
+                value.to_str().unwrap_or("").to_string()
+            };
+            (name.to_string(), value)
+        })
+        .collect();
+    
+    Ok(SearchRequestPreview {
+        method: "POST".to_string(),
+        url: format!("{}{}", get_base_url(&conn), path),
+        headers,
+        body,
+        timeout_ms: connection_timeout(&conn, timeout_ms),
+    })
+}
+
+#[command]
+pub async fn get_elasticsearch_cluster_health(window: Window, timeout_ms: Option<u64>) -> Result<ClusterHealth, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
     
-    let url = format!("{}/_cluster/health", get_base_url(&conn));
-    let headers = create_auth_headers(&conn)?;
+    let path = "/_cluster/health";
     
     // Send the request
-    let response = client.get(&url).headers(headers).send().await.map_err(|e| e.to_string())?;
+    let response = transport.send(Method::GET, path, None).await?;
     
     if !response.status().is_success() {
-        return Err(format!("Failed to get cluster health: {}", response.status()));
+        return Err(format!("Failed to get cluster health: {}", describe_error_response(response).await));
     }
     
     let health_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
@@ -331,33 +547,23 @@ pub async fn get_elasticsearch_cluster_health() -> Result<ClusterHealth, String>
     })
 }
 
+// Needs a token from request_confirmation_token("delete_elasticsearch_index", index)
 #[command]
-pub async fn delete_elasticsearch_index(index: String) -> Result<bool, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
-    };
+pub async fn delete_elasticsearch_index(window: Window, index: String, confirmation_token: Option<String>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    require_confirmation(transport.connection_id(), "delete_elasticsearch_index", &index, confirmation_token.as_deref())?;
+    ensure_privileges(&transport, &[], &["delete_index"], &[&index]).await?;
     
-    let url = format!("{}/{}", get_base_url(&conn), index);
-    let headers = create_auth_headers(&conn)?;
+    let path = format!("/{}", encode_segment(&index));
     
     // Send the DELETE request
-    let response = client.delete(&url)
-        .headers(headers)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let response = transport.send(Method::DELETE, &path, None).await?;
     
     if !response.status().is_success() {
         let status = response.status();
         let body = match response.text().await {
-            Ok(text) => text,
+            Ok(text) => describe_error_body(&text),
             Err(_) => "Could not read error response".to_string()
         };
         
@@ -370,21 +576,15 @@ pub async fn delete_elasticsearch_index(index: String) -> Result<bool, String> {
     Ok(true)
 }
 
+// Needs a token from request_confirmation_token("delete_all_documents_in_index", index)
 #[command]
-pub async fn delete_all_documents_in_index(index: String) -> Result<u64, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
-    };
+pub async fn delete_all_documents_in_index(window: Window, index: String, confirmation_token: Option<String>, timeout_ms: Option<u64>) -> Result<u64, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
     
-    let url = format!("{}/{}/_delete_by_query", get_base_url(&conn), index);
-    let headers = create_auth_headers(&conn)?;
+    require_confirmation(transport.connection_id(), "delete_all_documents_in_index", &index, confirmation_token.as_deref())?;
+    ensure_privileges(&transport, &[], &["read", "delete"], &[&index]).await?;
+    
+    let path = format!("/{}/_delete_by_query", encode_segment(&index));
     
     // Create a query that matches all documents
     let query_json = serde_json::json!({
@@ -394,13 +594,7 @@ pub async fn delete_all_documents_in_index(index: String) -> Result<u64, String>
     });
     
     // Send the request
-    let response = client
-        .post(&url)
-        .headers(headers)
-        .json(&query_json)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let response = transport.send(Method::POST, &path, Some(&query_json)).await?;
     
     if response.status().is_success() {
         let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
@@ -411,7 +605,7 @@ pub async fn delete_all_documents_in_index(index: String) -> Result<u64, String>
     } else {
         let status = response.status();
         let error_text = match response.text().await {
-            Ok(text) => text,
+            Ok(text) => describe_error_body(&text),
             Err(_) => "Unable to read error response".to_string()
         };
         
@@ -420,20 +614,10 @@ pub async fn delete_all_documents_in_index(index: String) -> Result<u64, String>
 }
 
 #[command]
-pub async fn create_elasticsearch_index(index: String, shards: u32, replicas: u32) -> Result<bool, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
-    };
+pub async fn create_elasticsearch_index(window: Window, index: String, shards: u32, replicas: u32, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
     
-    let url = format!("{}/{}", get_base_url(&conn), index);
-    let headers = create_auth_headers(&conn)?;
+    let path = format!("/{}", encode_segment(&index));
     
     // Create the index with specified settings
     let settings_json = serde_json::json!({
@@ -444,13 +628,7 @@ pub async fn create_elasticsearch_index(index: String, shards: u32, replicas: u3
     });
     
     // Send the request
-    let response = client
-        .put(&url)
-        .headers(headers)
-        .json(&settings_json)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let response = transport.send(Method::PUT, &path, Some(&settings_json)).await?;
     
     if response.status().is_success() {
         println!("Successfully created index: {}", index);
@@ -458,7 +636,7 @@ pub async fn create_elasticsearch_index(index: String, shards: u32, replicas: u3
     } else {
         let status = response.status();
         let error_text = match response.text().await {
-            Ok(text) => text,
+            Ok(text) => describe_error_body(&text),
             Err(_) => "Unable to read error response".to_string()
         };
         
@@ -467,39 +645,22 @@ pub async fn create_elasticsearch_index(index: String, shards: u32, replicas: u3
 }
 
 #[command]
-pub async fn create_elasticsearch_document(index: String, document: String, id: Option<String>) -> Result<serde_json::Value, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
-    };
+pub async fn create_elasticsearch_document(window: Window, index: String, document: String, id: Option<String>, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
     
     // Parse the document JSON
     let document_json: serde_json::Value = serde_json::from_str(&document)
         .map_err(|e| format!("Invalid document JSON: {}", e))?;
     
     // Create URL based on whether we have an ID or not
-    let url = if let Some(doc_id) = id {
-        format!("{}/{}/{}/_create", get_base_url(&conn), index, doc_id)
+    let path = if let Some(doc_id) = id {
+        format!("/{}/{}/_create", encode_segment(&index), encode_segment(&doc_id))
     } else {
-        format!("{}/{}/_doc", get_base_url(&conn), index)
+        format!("/{}/_doc", encode_segment(&index))
     };
     
-    let headers = create_auth_headers(&conn)?;
-    
     // Send the request
-    let response = client
-        .post(&url)
-        .headers(headers)
-        .json(&document_json)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let response = transport.send(Method::POST, &path, Some(&document_json)).await?;
     
     if response.status().is_success() {
         let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
@@ -509,7 +670,7 @@ pub async fn create_elasticsearch_document(index: String, document: String, id:
     } else {
         let status = response.status();
         let error_text = match response.text().await {
-            Ok(text) => text,
+            Ok(text) => describe_error_body(&text),
             Err(_) => "Unable to read error response".to_string()
         };
         
@@ -518,83 +679,38 @@ pub async fn create_elasticsearch_document(index: String, document: String, id:
 }
 
 #[command]
-pub async fn get_elasticsearch_index_mappings(index: String) -> Result<serde_json::Value, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
-    };
+pub async fn get_elasticsearch_index_mappings(window: Window, index: String, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
     
-    let url = format!("{}/{}/_mapping", get_base_url(&conn), index);
-    let headers = create_auth_headers(&conn)?;
+    let path = format!("/{}/_mapping", encode_segment(&index));
     
-    // Send the request
-    let response = client.get(&url)
-        .headers(headers)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    
-    if !response.status().is_success() {
-        return Err(format!("Failed to get index mappings: {}", response.status()));
-    }
-    
-    let mappings: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    // Served from the cache until it expires or something is written to the cluster
+    let mappings = transport.get_json_cached(&path).await
+        .map_err(|e| format!("Failed to get index mappings: {}", e))?;
     
     println!("Successfully retrieved mappings for index: {}", index);
     Ok(mappings)
 }
 
 #[command]
-pub async fn get_elasticsearch_index_settings(index: String) -> Result<serde_json::Value, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
-    };
-    
-    let url = format!("{}/{}/_settings", get_base_url(&conn), index);
-    let headers = create_auth_headers(&conn)?;
-    
-    // Send the request
-    let response = client.get(&url)
-        .headers(headers)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+pub async fn get_elasticsearch_index_settings(window: Window, index: String, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
     
-    if !response.status().is_success() {
-        return Err(format!("Failed to get index settings: {}", response.status()));
-    }
+    let path = format!("/{}/_settings", encode_segment(&index));
     
-    let settings: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    // Served from the cache until it expires or something is written to the cluster
+    let settings = transport.get_json_cached(&path).await
+        .map_err(|e| format!("Failed to get index settings: {}", e))?;
     
     println!("Successfully retrieved settings for index: {}", index);
     Ok(settings)
 }
 
+// Needs a token from request_confirmation_token("delete_elasticsearch_documents", index)
 #[command]
-pub async fn delete_elasticsearch_documents(index: String, doc_ids: Vec<String>) -> Result<u64, String> {
-    // Get connection and client info, then drop the guards
-    let (conn, client) = {
-        let conn_guard = CONNECTION.lock();
-        let client_guard = CLIENT.lock();
-        
-        let conn = conn_guard.as_ref().ok_or("Not connected to Elasticsearch")?.clone();
-        let client = client_guard.as_ref().ok_or("HTTP client not available")?.clone();
-        
-        (conn, client)
-    };
+pub async fn delete_elasticsearch_documents(window: Window, index: String, doc_ids: Vec<String>, confirmation_token: Option<String>, timeout_ms: Option<u64>) -> Result<u64, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    require_confirmation(transport.connection_id(), "delete_elasticsearch_documents", &index, confirmation_token.as_deref())?;
     
     if doc_ids.is_empty() {
         return Ok(0); // No documents to delete
@@ -616,13 +732,6 @@ pub async fn delete_elasticsearch_documents(index: String, doc_ids: Vec<String>)
     }
     
     // Perform the bulk delete
-    let url = format!("{}/_bulk", get_base_url(&conn));
-    let mut headers = create_auth_headers(&conn)?;
-    
-    // Remove existing Content-Type header if present and add the correct one
-    headers.remove(CONTENT_TYPE);
-    headers.insert("Content-Type", HeaderValue::from_static("application/x-ndjson"));
-    
     // Convert bulk operations to newline-delimited JSON (NDJSON)
     let mut bulk_body = String::new();
     for op in bulk_operations {
@@ -631,11 +740,9 @@ pub async fn delete_elasticsearch_documents(index: String, doc_ids: Vec<String>)
     }
     
     // Send the request
-    let response = client
-        .post(&url)
-        .headers(headers)
+    let response = transport.ndjson_request(Method::POST, "/_bulk")?
         .body(bulk_body)
-        .send()
+        .send_with_retry()
         .await
         .map_err(|e| e.to_string())?;
     
@@ -658,10 +765,5323 @@ pub async fn delete_elasticsearch_documents(index: String, doc_ids: Vec<String>)
     } else {
         let status = response.status();
         let error_text = match response.text().await {
-            Ok(text) => text,
+            Ok(text) => describe_error_body(&text),
             Err(_) => "Unable to read error response".to_string()
         };
         
         Err(format!("Failed to delete documents - Status: {}, Response: {}", status, error_text))
     }
-} 
\ No newline at end of file
+} 
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeAllocation {
+    pub node: String,
+    pub host: String,
+    pub ip: String,
+    pub shards: u32,
+    pub disk_indices: String,
+    pub disk_used: String,
+    pub disk_avail: String,
+    pub disk_total: String,
+    pub disk_percent: u32,
+}
+
+#[command]
+pub async fn get_node_allocation(window: Window, timeout_ms: Option<u64>) -> Result<Vec<NodeAllocation>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_cat/allocation?format=json&v=true";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get node allocation: {}", describe_error_response(response).await));
+    }
+    
+    // The UNASSIGNED row reports null for every disk column, so values are optional
+    let allocation_data: Vec<HashMap<String, Option<String>>> = response.json().await.map_err(|e| e.to_string())?;
+    
+    let allocation = allocation_data.into_iter().map(|row| {
+        let field = |key: &str| row.get(key).cloned().flatten();
+        
+        NodeAllocation {
+            node: field("node").unwrap_or_default(),
+            host: field("host").unwrap_or_default(),
+            ip: field("ip").unwrap_or_default(),
+            shards: field("shards").and_then(|v| v.parse().ok()).unwrap_or(0),
+            disk_indices: field("disk.indices").unwrap_or_default(),
+            disk_used: field("disk.used").unwrap_or_default(),
+            disk_avail: field("disk.avail").unwrap_or_default(),
+            disk_total: field("disk.total").unwrap_or_default(),
+            disk_percent: field("disk.percent").and_then(|v| v.parse().ok()).unwrap_or(0),
+        }
+    }).collect();
+    
+    Ok(allocation)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatNode {
+    pub name: String,
+    pub ip: String,
+    pub heap_percent: u32,
+    pub ram_percent: u32,
+    pub cpu: u32,
+    pub load_1m: f64,
+    pub load_5m: f64,
+    pub load_15m: f64,
+    pub roles: String,
+    pub is_master: bool,
+}
+
+#[command]
+pub async fn get_cat_nodes(window: Window, timeout_ms: Option<u64>) -> Result<Vec<CatNode>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Only request the columns we display so the call stays cheap enough to poll
+    let path = "/_cat/nodes?format=json&h=name,ip,heap.percent,ram.percent,cpu,load_1m,load_5m,load_15m,node.role,master";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get nodes: {}", describe_error_response(response).await));
+    }
+    
+    // Load averages are null on platforms that don't report them
+    let nodes_data: Vec<HashMap<String, Option<String>>> = response.json().await.map_err(|e| e.to_string())?;
+    
+    let nodes = nodes_data.into_iter().map(|node| {
+        let field = |key: &str| node.get(key).cloned().flatten();
+        
+        CatNode {
+            name: field("name").unwrap_or_default(),
+            ip: field("ip").unwrap_or_default(),
+            heap_percent: field("heap.percent").and_then(|v| v.parse().ok()).unwrap_or(0),
+            ram_percent: field("ram.percent").and_then(|v| v.parse().ok()).unwrap_or(0),
+            cpu: field("cpu").and_then(|v| v.parse().ok()).unwrap_or(0),
+            load_1m: field("load_1m").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+            load_5m: field("load_5m").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+            load_15m: field("load_15m").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+            roles: field("node.role").unwrap_or_default(),
+            // The elected master is marked with "*", everything else with "-"
+            is_master: field("master").map(|m| m == "*").unwrap_or(false),
+        }
+    }).collect();
+    
+    Ok(nodes)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadPoolStats {
+    pub node_name: String,
+    pub name: String,
+    pub active: u64,
+    pub queue: u64,
+    pub queue_size: Option<u64>,
+    pub rejected: u64,
+    pub completed: u64,
+}
+
+#[command]
+pub async fn get_thread_pool_stats(window: Window, pools: Option<Vec<String>>, timeout_ms: Option<u64>) -> Result<Vec<ThreadPoolStats>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Restrict to the requested pools (e.g. write, search) when any are given
+    let pool_filter = match pools {
+        Some(pools) if !pools.is_empty() => format!("/{}", encode_segments(&pools)),
+        _ => String::new(),
+    };
+    
+    let path = format!(
+        "/_cat/thread_pool{}?format=json&h=node_name,name,active,queue,queue_size,rejected,completed",
+        pool_filter
+    );
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get thread pool stats: {}", describe_error_response(response).await));
+    }
+    
+    // Scaling pools report a null queue_size because their queue is unbounded
+    let pools_data: Vec<HashMap<String, Option<String>>> = response.json().await.map_err(|e| e.to_string())?;
+    
+    let stats = pools_data.into_iter().map(|pool| {
+        let field = |key: &str| pool.get(key).cloned().flatten();
+        
+        ThreadPoolStats {
+            node_name: field("node_name").unwrap_or_default(),
+            name: field("name").unwrap_or_default(),
+            active: field("active").and_then(|v| v.parse().ok()).unwrap_or(0),
+            queue: field("queue").and_then(|v| v.parse().ok()).unwrap_or(0),
+            queue_size: field("queue_size").and_then(|v| v.parse().ok()),
+            rejected: field("rejected").and_then(|v| v.parse().ok()).unwrap_or(0),
+            completed: field("completed").and_then(|v| v.parse().ok()).unwrap_or(0),
+        }
+    }).collect();
+    
+    Ok(stats)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FielddataUsage {
+    pub node: String,
+    pub host: String,
+    pub ip: String,
+    pub field: String,
+    pub size_in_bytes: u64,
+}
+
+#[command]
+pub async fn get_fielddata_usage(window: Window, fields: Option<Vec<String>>, timeout_ms: Option<u64>) -> Result<Vec<FielddataUsage>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let field_filter = match fields {
+        Some(fields) if !fields.is_empty() => format!("/{}", encode_segments(&fields)),
+        _ => String::new(),
+    };
+    
+    // Ask for raw byte counts so the results can be sorted by size
+    let path = format!("/_cat/fielddata{}?format=json&bytes=b", field_filter);
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get fielddata usage: {}", describe_error_response(response).await));
+    }
+    
+    let fielddata: Vec<HashMap<String, String>> = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut usage: Vec<FielddataUsage> = fielddata.into_iter().map(|entry| {
+        FielddataUsage {
+            node: entry.get("node").cloned().unwrap_or_default(),
+            host: entry.get("host").cloned().unwrap_or_default(),
+            ip: entry.get("ip").cloned().unwrap_or_default(),
+            field: entry.get("field").cloned().unwrap_or_default(),
+            size_in_bytes: entry.get("size").and_then(|v| v.parse().ok()).unwrap_or(0),
+        }
+    }).collect();
+    
+    // Largest consumers first
+    usage.sort_by_key(|u| std::cmp::Reverse(u.size_in_bytes));
+    
+    Ok(usage)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterPlugin {
+    pub node: String,
+    pub component: String,
+    pub version: String,
+    pub description: String,
+}
+
+#[command]
+pub async fn get_cluster_plugins(window: Window, timeout_ms: Option<u64>) -> Result<Vec<ClusterPlugin>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_cat/plugins?format=json&h=name,component,version,description";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get cluster plugins: {}", describe_error_response(response).await));
+    }
+    
+    let plugins_data: Vec<HashMap<String, Option<String>>> = response.json().await.map_err(|e| e.to_string())?;
+    
+    // One row is returned per plugin per node, so mixed installs show up clearly
+    let plugins = plugins_data.into_iter().map(|plugin| {
+        let field = |key: &str| plugin.get(key).cloned().flatten();
+        
+        ClusterPlugin {
+            node: field("name").unwrap_or_default(),
+            component: field("component").unwrap_or_default(),
+            version: field("version").unwrap_or_default(),
+            description: field("description").unwrap_or_default(),
+        }
+    }).collect();
+    
+    Ok(plugins)
+}
+
+#[command]
+pub async fn get_remote_cluster_info(window: Window, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_remote/info";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get remote cluster info: {}", describe_error_response(response).await));
+    }
+    
+    let remote_info: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(remote_info)
+}
+
+// Remote clusters live under `cluster.remote.<alias>.*` in the persistent cluster settings
+// `persistent` holds the cluster settings to write for the remote cluster `alias`
+async fn update_remote_cluster_settings(window: &Window, alias: &str, persistent: serde_json::Value, timeout_ms: Option<u64>) -> Result<(), String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    ensure_privileges(&transport, &["manage"], &[], &[]).await?;
+    
+    let settings_json = serde_json::json!({
+        "persistent": persistent
+    });
+    
+    // Send the request
+    let response = transport.send(Method::PUT, "/_cluster/settings", Some(&settings_json)).await?;
+    
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to update remote cluster '{}' - Status: {}, Response: {}", alias, status, error_text))
+    }
+}
+
+#[command]
+pub async fn add_remote_cluster(window: Window, alias: String, seeds: Vec<String>, skip_unavailable: Option<bool>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    if seeds.is_empty() {
+        return Err("At least one seed node is required to add a remote cluster".to_string());
+    }
+    
+    let remote_settings = serde_json::json!({
+        "cluster": {
+            "remote": {
+                alias.as_str(): {
+                    "seeds": seeds,
+                    "skip_unavailable": skip_unavailable.unwrap_or(false)
+                }
+            }
+        }
+    });
+    
+    update_remote_cluster_settings(&window, &alias, remote_settings, timeout_ms).await?;
+    
+    println!("Successfully added remote cluster: {}", alias);
+    Ok(true)
+}
+
+#[command]
+pub async fn remove_remote_cluster(window: Window, alias: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    // Nulling every key under the alias removes the registration; leaving proxy-only settings
+    // such as server_name behind would be rejected once the mode is gone
+    let remote_settings = serde_json::json!({
+        format!("cluster.remote.{}.*", alias): null
+    });
+    
+    update_remote_cluster_settings(&window, &alias, remote_settings, timeout_ms).await?;
+    
+    println!("Successfully removed remote cluster: {}", alias);
+    Ok(true)
+}
+
+#[command]
+pub async fn get_voting_config_exclusions(window: Window, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Current exclusions are only exposed through the cluster state coordination metadata
+    let path = "/_cluster/state/metadata?filter_path=metadata.cluster_coordination.voting_config_exclusions";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get voting config exclusions: {}", describe_error_response(response).await));
+    }
+    
+    let state: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let exclusions = state["metadata"]["cluster_coordination"]["voting_config_exclusions"].clone();
+    
+    if exclusions.is_null() {
+        Ok(serde_json::json!([]))
+    } else {
+        Ok(exclusions)
+    }
+}
+
+#[command]
+pub async fn add_voting_config_exclusions(window: Window, node_names: Vec<String>, timeout: Option<String>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    if node_names.is_empty() {
+        return Err("At least one node name is required".to_string());
+    }
+    
+    let mut path = format!("/_cluster/voting_config_exclusions?node_names={}", encode_segments(&node_names));
+    
+    if let Some(timeout) = timeout {
+        path.push_str(&format!("&timeout={}", encode_query(&timeout)));
+    }
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, None).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully added voting config exclusions for: {}", node_names.join(", "));
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to add voting config exclusions - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn clear_voting_config_exclusions(window: Window, wait_for_removal: Option<bool>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // By default ES waits until the excluded nodes have left the cluster before clearing
+    let path = format!(
+        "/_cluster/voting_config_exclusions?wait_for_removal={}",
+        wait_for_removal.unwrap_or(true)
+    );
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully cleared voting config exclusions");
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to clear voting config exclusions - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn put_node_shutdown(
+    window: Window,
+    node_id: String,
+    shutdown_type: String,
+    reason: String,
+    allocation_delay: Option<String>,
+    target_node_name: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    if !["restart", "remove", "replace"].contains(&shutdown_type.as_str()) {
+        return Err(format!("Invalid shutdown type '{}': expected restart, remove, or replace", shutdown_type));
+    }
+    
+    // allocation_delay only applies to restarts, target_node_name only to replacements
+    let mut shutdown_json = serde_json::json!({
+        "type": shutdown_type,
+        "reason": reason
+    });
+    
+    if shutdown_type == "restart" {
+        if let Some(delay) = allocation_delay {
+            shutdown_json["allocation_delay"] = serde_json::Value::String(delay);
+        }
+    }
+    
+    if shutdown_type == "replace" {
+        let target = target_node_name.ok_or("A target node name is required for a replace shutdown")?;
+        shutdown_json["target_node_name"] = serde_json::Value::String(target);
+    }
+    
+    let path = format!("/_nodes/{}/shutdown", encode_segment(&node_id));
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&shutdown_json)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully registered {} shutdown for node: {}", shutdown_type, node_id);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to register node shutdown - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn get_node_shutdown_status(window: Window, node_id: Option<String>, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Without a node id, report every node that has a shutdown registered
+    let path = match node_id {
+        Some(node_id) => format!("/_nodes/{}/shutdown", encode_segment(&node_id)),
+        None => "/_nodes/shutdown".to_string(),
+    };
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get node shutdown status: {}", describe_error_response(response).await));
+    }
+    
+    let status: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(status)
+}
+
+#[command]
+pub async fn delete_node_shutdown(window: Window, node_id: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_nodes/{}/shutdown", encode_segment(&node_id));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete shutdown for node '{}': {} - {}", node_id, status, body));
+    }
+    
+    println!("Successfully removed shutdown registration for node: {}", node_id);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecationIssue {
+    pub level: String,
+    pub message: String,
+    pub url: String,
+    pub details: Option<String>,
+    pub resolve_during_rolling_upgrade: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecationInfo {
+    #[serde(default)]
+    pub cluster_settings: Vec<DeprecationIssue>,
+    #[serde(default)]
+    pub node_settings: Vec<DeprecationIssue>,
+    #[serde(default)]
+    pub index_settings: HashMap<String, Vec<DeprecationIssue>>,
+    #[serde(default)]
+    pub ml_settings: Vec<DeprecationIssue>,
+}
+
+#[command]
+pub async fn get_deprecation_info(window: Window, timeout_ms: Option<u64>) -> Result<DeprecationInfo, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_migration/deprecations";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get deprecation info: {}", describe_error_response(response).await));
+    }
+    
+    let deprecations: DeprecationInfo = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(deprecations)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XpackFeature {
+    pub available: bool,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseInfo {
+    pub status: String,
+    pub license_type: String,
+    pub uid: String,
+    pub issued_to: String,
+    pub issue_date: String,
+    pub expiry_date: Option<String>,
+    pub max_nodes: Option<u64>,
+    pub features: HashMap<String, XpackFeature>,
+}
+
+#[command]
+pub async fn get_license_info(window: Window, timeout_ms: Option<u64>) -> Result<LicenseInfo, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Send the license request
+    let response = transport.send(Method::GET, "/_license", None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get license info: {}", describe_error_response(response).await));
+    }
+    
+    let license_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let license = &license_data["license"];
+    
+    // The license itself doesn't say which features it unlocks, _xpack does
+    let response = transport.send(Method::GET, "/_xpack?categories=features", None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get X-Pack features: {}", describe_error_response(response).await));
+    }
+    
+    let xpack_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let features = xpack_data["features"].as_object()
+        .map(|features| {
+            features.iter().map(|(name, feature)| {
+                (name.clone(), XpackFeature {
+                    available: feature["available"].as_bool().unwrap_or(false),
+                    enabled: feature["enabled"].as_bool().unwrap_or(false),
+                })
+            }).collect()
+        })
+        .unwrap_or_default();
+    
+    Ok(LicenseInfo {
+        status: license["status"].as_str().unwrap_or("").to_string(),
+        license_type: license["type"].as_str().unwrap_or("").to_string(),
+        uid: license["uid"].as_str().unwrap_or("").to_string(),
+        issued_to: license["issued_to"].as_str().unwrap_or("").to_string(),
+        issue_date: license["issue_date"].as_str().unwrap_or("").to_string(),
+        // Basic licenses never expire, so there is no expiry date to report
+        expiry_date: license["expiry_date"].as_str().map(|d| d.to_string()),
+        max_nodes: license["max_nodes"].as_u64(),
+        features,
+    })
+}
+
+#[command]
+pub async fn start_trial(window: Window, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_license/start_trial?acknowledge=true";
+    
+    // Send the request
+    let response = transport.send(Method::POST, path, None).await?;
+    
+    if response.status().is_success() {
+        let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        
+        // ES answers 200 even when a trial was already used, so check the flag
+        if result["trial_was_started"].as_bool().unwrap_or(false) {
+            println!("Successfully started trial license");
+            Ok(true)
+        } else {
+            Err(format!(
+                "Trial license was not started: {}",
+                result["error_message"].as_str().unwrap_or("unknown reason")
+            ))
+        }
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to start trial license - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn start_basic(window: Window, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_license/start_basic?acknowledge=true";
+    
+    // Send the request
+    let response = transport.send(Method::POST, path, None).await?;
+    
+    if response.status().is_success() {
+        let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        
+        if result["basic_was_started"].as_bool().unwrap_or(false) {
+            println!("Successfully started basic license");
+            Ok(true)
+        } else {
+            Err(format!(
+                "Basic license was not started: {}",
+                result["error_message"].as_str().unwrap_or("unknown reason")
+            ))
+        }
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to start basic license - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthImpact {
+    pub severity: u32,
+    pub description: String,
+    pub impact_areas: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthDiagnosis {
+    pub id: String,
+    pub cause: String,
+    pub action: String,
+    pub help_url: String,
+    pub affected_resources: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthIndicator {
+    pub name: String,
+    pub status: String,
+    pub symptom: String,
+    pub impacts: Vec<HealthImpact>,
+    pub diagnosis: Vec<HealthDiagnosis>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub cluster_name: String,
+    pub status: String,
+    pub indicators: Vec<HealthIndicator>,
+}
+
+#[command]
+pub async fn get_health_report(window: Window, indicator: Option<String>, timeout_ms: Option<u64>) -> Result<HealthReport, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = match indicator {
+        Some(indicator) => format!("/_health_report/{}", encode_segment(&indicator)),
+        None => "/_health_report".to_string(),
+    };
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    // Older clusters don't know the endpoint at all: some answer 404, others 400 because they
+    // read `_health_report` as an index name or find no handler for it
+    let unsupported = "The health report API requires Elasticsearch 8.7 or later".to_string();
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(unsupported);
+    }
+    if response.status() == reqwest::StatusCode::BAD_REQUEST {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        let unknown_endpoint = body.contains("no handler found for uri")
+            || (body.contains("invalid_index_name_exception") && body.contains("[_health_report]"));
+        if unknown_endpoint {
+            return Err(unsupported);
+        }
+        return Err(format!("Failed to get health report: {}", describe_error_status(status, &body)));
+    }
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get health report: {}", describe_error_response(response).await));
+    }
+    
+    let report: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let indicators = report["indicators"].as_object()
+        .map(|indicators| {
+            indicators.iter().map(|(name, indicator)| {
+                let impacts = indicator["impacts"].as_array().unwrap_or(&vec![]).iter().map(|impact| {
+                    HealthImpact {
+                        severity: impact["severity"].as_u64().unwrap_or(0) as u32,
+                        description: impact["description"].as_str().unwrap_or("").to_string(),
+                        impact_areas: impact["impact_areas"].as_array().unwrap_or(&vec![]).iter()
+                            .filter_map(|area| area.as_str().map(|a| a.to_string()))
+                            .collect(),
+                    }
+                }).collect();
+                
+                let diagnosis = indicator["diagnosis"].as_array().unwrap_or(&vec![]).iter().map(|diagnosis| {
+                    HealthDiagnosis {
+                        id: diagnosis["id"].as_str().unwrap_or("").to_string(),
+                        cause: diagnosis["cause"].as_str().unwrap_or("").to_string(),
+                        action: diagnosis["action"].as_str().unwrap_or("").to_string(),
+                        help_url: diagnosis["help_url"].as_str().unwrap_or("").to_string(),
+                        affected_resources: diagnosis["affected_resources"].clone(),
+                    }
+                }).collect();
+                
+                HealthIndicator {
+                    name: name.clone(),
+                    status: indicator["status"].as_str().unwrap_or("unknown").to_string(),
+                    symptom: indicator["symptom"].as_str().unwrap_or("").to_string(),
+                    impacts,
+                    diagnosis,
+                }
+            }).collect()
+        })
+        .unwrap_or_default();
+    
+    Ok(HealthReport {
+        cluster_name: report["cluster_name"].as_str().unwrap_or("").to_string(),
+        // A single-indicator report has no top-level status
+        status: report["status"].as_str().unwrap_or("unknown").to_string(),
+        indicators,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDiff {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub in_both: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingDifference {
+    pub key: String,
+    pub value_a: Option<serde_json::Value>,
+    pub value_b: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterSummary {
+    pub connection_name: String,
+    pub cluster_name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterComparison {
+    pub cluster_a: ClusterSummary,
+    pub cluster_b: ClusterSummary,
+    pub same_version: bool,
+    pub plugins: ListDiff,
+    pub indices: ListDiff,
+    pub templates: ListDiff,
+    pub settings: Vec<SettingDifference>,
+}
+
+struct ClusterProfile {
+    summary: ClusterSummary,
+    plugins: BTreeSet<String>,
+    indices: BTreeSet<String>,
+    templates: BTreeSet<String>,
+    settings: BTreeMap<String, serde_json::Value>,
+}
+
+// GET a JSON document from an explicit connection rather than the active one
+pub(crate) async fn fetch_connection_json(client: &ReqwestClient, conn: &ElasticsearchConnection, path: &str, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
+    Transport::new(client.clone(), conn.clone())
+        .with_timeout(timeout_ms)
+        .get_json(path)
+        .await
+        .map_err(|e| format!("{}: {}", conn.name, e))
+}
+
+async fn gather_cluster_profile(client: &ReqwestClient, conn: &ElasticsearchConnection, timeout_ms: Option<u64>) -> Result<ClusterProfile, String> {
+    let root = fetch_connection_json(client, conn, "/", timeout_ms).await?;
+    let plugins = fetch_connection_json(client, conn, "/_cat/plugins?format=json&h=component", timeout_ms).await?;
+    let indices = fetch_connection_json(client, conn, "/_cat/indices?format=json&h=index", timeout_ms).await?;
+    let templates = Transport::new(client.clone(), conn.clone())
+        .with_timeout(timeout_ms)
+        .get_json_cached("/_index_template")
+        .await
+        .map_err(|e| format!("{}: {}", conn.name, e))?;
+    let settings = fetch_connection_json(client, conn, "/_cluster/settings?flat_settings=true", timeout_ms).await?;
+    
+    let column = |rows: &serde_json::Value, key: &str| -> BTreeSet<String> {
+        rows.as_array().unwrap_or(&vec![]).iter()
+            .filter_map(|row| row[key].as_str().map(|v| v.to_string()))
+            .collect()
+    };
+    
+    // System and hidden indices differ between any two clusters, so leave them out
+    let indices = column(&indices, "index").into_iter()
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    
+    let templates = templates["index_templates"].as_array().unwrap_or(&vec![]).iter()
+        .filter_map(|template| template["name"].as_str().map(|n| n.to_string()))
+        .collect();
+    
+    // Transient settings win over persistent ones, matching how ES applies them
+    let mut merged_settings = BTreeMap::new();
+    for scope in ["persistent", "transient"] {
+        if let Some(values) = settings[scope].as_object() {
+            for (key, value) in values {
+                merged_settings.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    
+    Ok(ClusterProfile {
+        summary: ClusterSummary {
+            connection_name: conn.name.clone(),
+            cluster_name: root["cluster_name"].as_str().unwrap_or("").to_string(),
+            version: root["version"]["number"].as_str().unwrap_or("").to_string(),
+        },
+        plugins: column(&plugins, "component"),
+        indices,
+        templates,
+        settings: merged_settings,
+    })
+}
+
+fn diff_lists(a: &BTreeSet<String>, b: &BTreeSet<String>) -> ListDiff {
+    ListDiff {
+        only_in_a: a.difference(b).cloned().collect(),
+        only_in_b: b.difference(a).cloned().collect(),
+        in_both: a.intersection(b).cloned().collect(),
+    }
+}
+
+#[command]
+pub async fn compare_clusters(conn_a: ElasticsearchConnection, conn_b: ElasticsearchConnection, timeout_ms: Option<u64>) -> Result<ClusterComparison, String> {
+    // Get a client from our Mutex, then drop the guard immediately
+    let client = {
+        let client_guard = CLIENT.lock();
+        client_guard.as_ref().ok_or("HTTP client not available")?.clone()
+    };
+    
+    let (profile_a, profile_b) = tokio::try_join!(
+        gather_cluster_profile(&client, &conn_a, timeout_ms),
+        gather_cluster_profile(&client, &conn_b, timeout_ms)
+    )?;
+    
+    let setting_keys: BTreeSet<&String> = profile_a.settings.keys().chain(profile_b.settings.keys()).collect();
+    let settings = setting_keys.into_iter()
+        .filter_map(|key| {
+            let value_a = profile_a.settings.get(key).cloned();
+            let value_b = profile_b.settings.get(key).cloned();
+            
+            if value_a == value_b {
+                None
+            } else {
+                Some(SettingDifference { key: key.clone(), value_a, value_b })
+            }
+        })
+        .collect();
+    
+    Ok(ClusterComparison {
+        same_version: profile_a.summary.version == profile_b.summary.version,
+        plugins: diff_lists(&profile_a.plugins, &profile_b.plugins),
+        indices: diff_lists(&profile_a.indices, &profile_b.indices),
+        templates: diff_lists(&profile_a.templates, &profile_b.templates),
+        settings,
+        cluster_a: profile_a.summary,
+        cluster_b: profile_b.summary,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FsRepositorySettings {
+    pub location: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_snapshot_bytes_per_sec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_restore_bytes_per_sec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readonly: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3RepositorySettings {
+    pub bucket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_side_encryption: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readonly: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcsRepositorySettings {
+    pub bucket: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readonly: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AzureRepositorySettings {
+    pub container: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readonly: Option<bool>,
+}
+
+// Serializes straight into the `{ "type": ..., "settings": { ... } }` body ES expects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "settings", rename_all = "lowercase")]
+pub enum SnapshotRepositoryConfig {
+    Fs(FsRepositorySettings),
+    S3(S3RepositorySettings),
+    Gcs(GcsRepositorySettings),
+    Azure(AzureRepositorySettings),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotRepository {
+    pub name: String,
+    pub repository_type: String,
+    pub settings: serde_json::Value,
+}
+
+#[command]
+pub async fn list_snapshot_repositories(window: Window, timeout_ms: Option<u64>) -> Result<Vec<SnapshotRepository>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_snapshot";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list snapshot repositories: {}", describe_error_response(response).await));
+    }
+    
+    let repositories_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    // Settings come back as strings regardless of type, so they are returned as-is
+    let mut repositories: Vec<SnapshotRepository> = repositories_data.as_object()
+        .map(|repositories| {
+            repositories.iter().map(|(name, repository)| {
+                SnapshotRepository {
+                    name: name.clone(),
+                    repository_type: repository["type"].as_str().unwrap_or("").to_string(),
+                    settings: repository["settings"].clone(),
+                }
+            }).collect()
+        })
+        .unwrap_or_default();
+    
+    repositories.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    Ok(repositories)
+}
+
+#[command]
+pub async fn register_snapshot_repository(window: Window, name: String, repository: SnapshotRepositoryConfig, verify: Option<bool>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!(
+        "/_snapshot/{}?verify={}",
+        encode_segment(&name),
+        verify.unwrap_or(true)
+    );
+    
+    // Send the request
+    let response = transport.request(Method::PUT, &path)?.json(&repository).send_with_retry().await.map_err(|e| e.to_string())?;
+    
+    if response.status().is_success() {
+        println!("Successfully registered snapshot repository: {}", name);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to register snapshot repository - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn verify_snapshot_repository(window: Window, name: String, timeout_ms: Option<u64>) -> Result<Vec<String>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_snapshot/{}/_verify", encode_segment(&name));
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, None).await?;
+    
+    if response.status().is_success() {
+        let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        
+        // Report the names of the nodes that could access the repository
+        let nodes = result["nodes"].as_object()
+            .map(|nodes| {
+                nodes.values()
+                    .filter_map(|node| node["name"].as_str().map(|n| n.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        
+        Ok(nodes)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to verify snapshot repository - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+// Needs a token from request_confirmation_token("delete_snapshot_repository", name)
+#[command]
+pub async fn delete_snapshot_repository(window: Window, name: String, confirmation_token: Option<String>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    require_confirmation(transport.connection_id(), "delete_snapshot_repository", &name, confirmation_token.as_deref())?;
+    
+    let path = format!("/_snapshot/{}", encode_segment(&name));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete snapshot repository '{}': {} - {}", name, status, body));
+    }
+    
+    // Only the registration is removed; snapshot data in the repository is untouched
+    println!("Successfully deleted snapshot repository: {}", name);
+    
+    Ok(true)
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_snapshot(
+    window: Window,
+    repository: String,
+    snapshot: String,
+    indices: Option<Vec<String>>,
+    include_global_state: Option<bool>,
+    wait: Option<bool>,
+    partial: Option<bool>,
+    ignore_unavailable: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let wait = wait.unwrap_or(false);
+    
+    let path = format!(
+        "/_snapshot/{}/{}?wait_for_completion={}",
+        encode_segment(&repository),
+        encode_segment(&snapshot),
+        wait
+    );
+    
+    // partial lets the snapshot succeed even if some primaries are unavailable
+    let mut snapshot_json = serde_json::json!({
+        "include_global_state": include_global_state.unwrap_or(true),
+        "partial": partial.unwrap_or(false),
+        "ignore_unavailable": ignore_unavailable.unwrap_or(false)
+    });
+    
+    if let Some(indices) = indices {
+        if !indices.is_empty() {
+            snapshot_json["indices"] = serde_json::Value::String(indices.join(","));
+        }
+    }
+    
+    let mut request = transport.request(Method::PUT, &path)?.json(&snapshot_json);
+    
+    // Waiting for a large snapshot can take far longer than the default client timeout
+    if wait && timeout_ms.is_none() {
+        request = request.timeout(std::time::Duration::from_secs(60 * 60));
+    }
+    
+    // Send the request
+    let response = request.send_with_retry().await.map_err(|e| e.to_string())?;
+    
+    if response.status().is_success() {
+        let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        
+        println!("Successfully started snapshot {} in repository: {}", snapshot, repository);
+        Ok(result)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to create snapshot - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn restore_snapshot(
+    window: Window,
+    repository: String,
+    snapshot: String,
+    indices: Option<Vec<String>>,
+    rename_pattern: Option<String>,
+    rename_replacement: Option<String>,
+    index_settings_overrides: Option<serde_json::Value>,
+    wait: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let wait = wait.unwrap_or(false);
+    
+    let path = format!(
+        "/_snapshot/{}/{}/_restore?wait_for_completion={}",
+        encode_segment(&repository),
+        encode_segment(&snapshot),
+        wait
+    );
+    
+    let mut restore_json = serde_json::json!({
+        "include_global_state": false
+    });
+    
+    if let Some(indices) = indices {
+        if !indices.is_empty() {
+            restore_json["indices"] = serde_json::Value::String(indices.join(","));
+        }
+    }
+    
+    // Renaming lets the restored indices sit next to the live ones instead of clashing
+    match (rename_pattern, rename_replacement) {
+        (Some(pattern), Some(replacement)) => {
+            restore_json["rename_pattern"] = serde_json::Value::String(pattern);
+            restore_json["rename_replacement"] = serde_json::Value::String(replacement);
+        }
+        (None, None) => {}
+        _ => return Err("Both a rename pattern and a rename replacement are required to rename indices".to_string()),
+    }
+    
+    if let Some(overrides) = index_settings_overrides {
+        if !overrides.is_object() {
+            return Err("Index settings overrides must be a JSON object".to_string());
+        }
+        restore_json["index_settings"] = overrides;
+    }
+    
+    let mut request = transport.request(Method::POST, &path)?.json(&restore_json);
+    
+    // Waiting for a large restore can take far longer than the default client timeout
+    if wait && timeout_ms.is_none() {
+        request = request.timeout(std::time::Duration::from_secs(60 * 60));
+    }
+    
+    // Send the request
+    let response = request.send_with_retry().await.map_err(|e| e.to_string())?;
+    
+    if response.status().is_success() {
+        let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        
+        println!("Successfully started restore of snapshot {} from repository: {}", snapshot, repository);
+        Ok(result)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to restore snapshot - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotProgress {
+    pub operation: String, // "snapshot" or "restore"
+    pub repository: String,
+    pub snapshot: String,
+    pub state: String,
+    pub shards_done: u64,
+    pub shards_failed: u64,
+    pub shards_total: u64,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub percent: f64,
+    pub finished: bool,
+}
+
+fn progress_percent(done: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (done as f64 / total as f64 * 100.0).min(100.0)
+    }
+}
+
+pub(crate) async fn fetch_snapshot_progress(
+    transport: &Transport,
+    repository: &str,
+    snapshot: &str,
+) -> Result<SnapshotProgress, String> {
+    // Send the request
+    let response = transport.send(Method::GET, &format!("/_snapshot/{}/{}/_status", encode_segment(repository), encode_segment(snapshot)), None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get snapshot status: {}", describe_error_response(response).await));
+    }
+    
+    let status_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let status = &status_data["snapshots"][0];
+    
+    if status.is_null() {
+        return Err(format!("Snapshot '{}' was not found in repository '{}'", snapshot, repository));
+    }
+    
+    let shards = &status["shards_stats"];
+    let stats = &status["stats"];
+    
+    // ES 7.8+ nests sizes under processed/total, older versions use flat *_size_in_bytes keys
+    let bytes_done = stats["processed"]["size_in_bytes"].as_u64()
+        .or_else(|| stats["processed_size_in_bytes"].as_u64())
+        .unwrap_or(0);
+    let bytes_total = stats["total"]["size_in_bytes"].as_u64()
+        .or_else(|| stats["total_size_in_bytes"].as_u64())
+        .unwrap_or(0);
+    
+    let state = status["state"].as_str().unwrap_or("UNKNOWN").to_string();
+    let shards_done = shards["done"].as_u64().unwrap_or(0);
+    let shards_total = shards["total"].as_u64().unwrap_or(0);
+    
+    Ok(SnapshotProgress {
+        operation: "snapshot".to_string(),
+        repository: repository.to_string(),
+        snapshot: snapshot.to_string(),
+        finished: !matches!(state.as_str(), "INIT" | "STARTED" | "IN_PROGRESS"),
+        state,
+        shards_done,
+        shards_failed: shards["failed"].as_u64().unwrap_or(0),
+        shards_total,
+        bytes_done,
+        bytes_total,
+        percent: progress_percent(shards_done, shards_total),
+    })
+}
+
+async fn fetch_restore_progress(
+    transport: &Transport,
+    repository: &str,
+    snapshot: &str,
+    indices: &[String],
+) -> Result<SnapshotProgress, String> {
+    let path = format!("/{}/_recovery", encode_segments(indices));
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get restore progress: {}", describe_error_response(response).await));
+    }
+    
+    let recovery_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut shards_done = 0;
+    let mut shards_total = 0;
+    let mut bytes_done = 0;
+    let mut bytes_total = 0;
+    
+    // Restored shards show up as recoveries of type SNAPSHOT
+    for index in recovery_data.as_object().into_iter().flat_map(|indices| indices.values()) {
+        for shard in index["shards"].as_array().unwrap_or(&vec![]) {
+            if shard["type"].as_str() != Some("SNAPSHOT") {
+                continue;
+            }
+            
+            shards_total += 1;
+            if shard["stage"].as_str() == Some("DONE") {
+                shards_done += 1;
+            }
+            
+            bytes_done += shard["index"]["size"]["recovered_in_bytes"].as_u64().unwrap_or(0);
+            bytes_total += shard["index"]["size"]["total_in_bytes"].as_u64().unwrap_or(0);
+        }
+    }
+    
+    // Nothing restoring means the restore never started or its shards have since moved on as
+    // other recovery types, either way there is nothing left to wait for
+    if shards_total == 0 {
+        return Err(format!("No shards of {} are being restored from snapshot {}", indices.join(", "), snapshot));
+    }
+    
+    let finished = shards_done == shards_total;
+    
+    Ok(SnapshotProgress {
+        operation: "restore".to_string(),
+        repository: repository.to_string(),
+        snapshot: snapshot.to_string(),
+        state: if finished { "SUCCESS".to_string() } else { "IN_PROGRESS".to_string() },
+        shards_done,
+        shards_failed: 0,
+        shards_total,
+        bytes_done,
+        bytes_total,
+        percent: progress_percent(shards_done, shards_total),
+        finished,
+    })
+}
+
+#[command]
+pub async fn get_snapshot_status(window: Window, repository: String, snapshot: String, timeout_ms: Option<u64>) -> Result<SnapshotProgress, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    fetch_snapshot_progress(&transport, &repository, &snapshot).await
+}
+
+// Tracking gives up after this long, so a poll that never sees the operation finish can't run forever
+const MAX_PROGRESS_TRACKING_SECS: u64 = 24 * 60 * 60;
+
+// Polls as an app task, so it shows up in the task list and cancel_app_task stops it. Returns the task id.
+#[command]
+pub async fn track_snapshot_progress(
+    window: Window,
+    app: AppHandle,
+    repository: String,
+    snapshot: String,
+    interval_ms: Option<u64>,
+    timeout_ms: Option<u64>,
+) -> Result<String, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let interval = std::time::Duration::from_millis(interval_ms.unwrap_or_else(|| app_settings().progress_poll_interval_ms));
+    let task = TaskHandle::start(&app, "track_snapshot", format!("Track snapshot {} in {}", snapshot, repository));
+    
+    // Poll in the background and push each sample to the UI as a `snapshot-progress` event
+    let tracker = task.clone();
+    tauri::async_runtime::spawn(async move {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(MAX_PROGRESS_TRACKING_SECS);
+        let result: Result<(), String> = async {
+            loop {
+                let progress = fetch_snapshot_progress(&transport, &repository, &snapshot).await?;
+                tracker.set_progress(progress.percent);
+                let finished = progress.finished;
+                let _ = app.emit("snapshot-progress", progress);
+                if finished {
+                    return Ok(());
+                }
+                if std::time::Instant::now() >= deadline {
+                    return Err(format!("Snapshot didn't finish within {} hours", MAX_PROGRESS_TRACKING_SECS / 3600));
+                }
+                
+                tokio::time::sleep(interval).await;
+                if tracker.is_cancelled() {
+                    return Err("Cancelled".to_string());
+                }
+            }
+        }
+        .await;
+        
+        if let Err(e) = &result {
+            println!("Stopped tracking snapshot {}: {}", snapshot, e);
+        }
+        let _ = tracker.finish(result);
+    });
+    
+    Ok(task.id().to_string())
+}
+
+// Same as track_snapshot_progress, for the indices a restore writes to
+#[command]
+pub async fn track_restore_progress(
+    window: Window,
+    app: AppHandle,
+    repository: String,
+    snapshot: String,
+    indices: Vec<String>,
+    interval_ms: Option<u64>,
+    timeout_ms: Option<u64>,
+) -> Result<String, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    if indices.is_empty() {
+        return Err("The restored index names are required to track restore progress".to_string());
+    }
+    
+    let interval = std::time::Duration::from_millis(interval_ms.unwrap_or_else(|| app_settings().progress_poll_interval_ms));
+    let task = TaskHandle::start(&app, "track_restore", format!("Track restore of {} from {}", snapshot, repository));
+    
+    // Restores report through shard recoveries, so poll those for the (renamed) target indices
+    let tracker = task.clone();
+    tauri::async_runtime::spawn(async move {
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(MAX_PROGRESS_TRACKING_SECS);
+        let result: Result<(), String> = async {
+            loop {
+                let progress = fetch_restore_progress(&transport, &repository, &snapshot, &indices).await?;
+                tracker.set_progress(progress.percent);
+                let finished = progress.finished;
+                let _ = app.emit("snapshot-progress", progress);
+                if finished {
+                    return Ok(());
+                }
+                if std::time::Instant::now() >= deadline {
+                    return Err(format!("Restore didn't finish within {} hours", MAX_PROGRESS_TRACKING_SECS / 3600));
+                }
+                
+                tokio::time::sleep(interval).await;
+                if tracker.is_cancelled() {
+                    return Err("Cancelled".to_string());
+                }
+            }
+        }
+        .await;
+        
+        if let Err(e) = &result {
+            println!("Stopped tracking restore of snapshot {}: {}", snapshot, e);
+        }
+        let _ = tracker.finish(result);
+    });
+    
+    Ok(task.id().to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    pub snapshot: String,
+    pub uuid: String,
+    pub state: String,
+    pub indices: Vec<String>,
+    pub include_global_state: bool,
+    pub start_time: String,
+    pub end_time: String,
+    pub duration_in_millis: u64,
+    pub shards_total: u32,
+    pub shards_successful: u32,
+    pub shards_failed: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFailure {
+    pub index: String,
+    pub shard_id: u32,
+    pub node_id: String,
+    pub status: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotIndexDetails {
+    pub name: String,
+    pub shards_total: u64,
+    pub shards_done: u64,
+    pub size_in_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDetails {
+    pub info: SnapshotInfo,
+    pub indices: Vec<SnapshotIndexDetails>,
+    pub failures: Vec<SnapshotFailure>,
+}
+
+fn parse_snapshot_info(snapshot: &serde_json::Value) -> SnapshotInfo {
+    SnapshotInfo {
+        snapshot: snapshot["snapshot"].as_str().unwrap_or("").to_string(),
+        uuid: snapshot["uuid"].as_str().unwrap_or("").to_string(),
+        state: snapshot["state"].as_str().unwrap_or("").to_string(),
+        indices: snapshot["indices"].as_array().unwrap_or(&vec![]).iter()
+            .filter_map(|index| index.as_str().map(|i| i.to_string()))
+            .collect(),
+        include_global_state: snapshot["include_global_state"].as_bool().unwrap_or(false),
+        start_time: snapshot["start_time"].as_str().unwrap_or("").to_string(),
+        end_time: snapshot["end_time"].as_str().unwrap_or("").to_string(),
+        duration_in_millis: snapshot["duration_in_millis"].as_u64().unwrap_or(0),
+        shards_total: snapshot["shards"]["total"].as_u64().unwrap_or(0) as u32,
+        shards_successful: snapshot["shards"]["successful"].as_u64().unwrap_or(0) as u32,
+        shards_failed: snapshot["shards"]["failed"].as_u64().unwrap_or(0) as u32,
+    }
+}
+
+#[command]
+pub async fn list_snapshots(window: Window, repository: String, timeout_ms: Option<u64>) -> Result<Vec<SnapshotInfo>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_snapshot/{}/_all", encode_segment(&repository));
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list snapshots: {}", describe_error_response(response).await));
+    }
+    
+    let snapshots_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut snapshots: Vec<SnapshotInfo> = snapshots_data["snapshots"].as_array().unwrap_or(&vec![]).iter()
+        .map(parse_snapshot_info)
+        .collect();
+    
+    // Newest snapshots first, which is what people usually want to restore
+    snapshots.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+    
+    Ok(snapshots)
+}
+
+#[command]
+pub async fn get_snapshot_details(window: Window, repository: String, snapshot: String, timeout_ms: Option<u64>) -> Result<SnapshotDetails, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Send the request for the snapshot summary and its failures
+    let path = format!("/_snapshot/{}/{}", encode_segment(&repository), encode_segment(&snapshot));
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get snapshot '{}': {}", snapshot, describe_error_response(response).await));
+    }
+    
+    let snapshot_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let snapshot_json = &snapshot_data["snapshots"][0];
+    
+    if snapshot_json.is_null() {
+        return Err(format!("Snapshot '{}' was not found in repository '{}'", snapshot, repository));
+    }
+    
+    let failures = snapshot_json["failures"].as_array().unwrap_or(&vec![]).iter()
+        .map(|failure| SnapshotFailure {
+            index: failure["index"].as_str().unwrap_or("").to_string(),
+            shard_id: failure["shard_id"].as_u64().unwrap_or(0) as u32,
+            node_id: failure["node_id"].as_str().unwrap_or("").to_string(),
+            status: failure["status"].as_str().unwrap_or("").to_string(),
+            reason: failure["reason"].as_str().unwrap_or("").to_string(),
+        })
+        .collect();
+    
+    // Per-index shard counts and sizes are only available from the status endpoint
+    let response = transport.send(Method::GET, &format!("{}/_status", path), None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get snapshot status: {}", describe_error_response(response).await));
+    }
+    
+    let status_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut indices: Vec<SnapshotIndexDetails> = status_data["snapshots"][0]["indices"].as_object()
+        .map(|indices| {
+            indices.iter().map(|(name, index)| {
+                let stats = &index["stats"];
+                
+                SnapshotIndexDetails {
+                    name: name.clone(),
+                    shards_total: index["shards_stats"]["total"].as_u64().unwrap_or(0),
+                    shards_done: index["shards_stats"]["done"].as_u64().unwrap_or(0),
+                    size_in_bytes: stats["total"]["size_in_bytes"].as_u64()
+                        .or_else(|| stats["total_size_in_bytes"].as_u64())
+                        .unwrap_or(0),
+                }
+            }).collect()
+        })
+        .unwrap_or_default();
+    
+    indices.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    Ok(SnapshotDetails {
+        info: parse_snapshot_info(snapshot_json),
+        indices,
+        failures,
+    })
+}
+
+// Needs a token from request_confirmation_token("delete_snapshot", "<repository>/<snapshot>")
+#[command]
+pub async fn delete_snapshot(window: Window, repository: String, snapshot: String, confirmation_token: Option<String>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    require_confirmation(transport.connection_id(), "delete_snapshot", &format!("{}/{}", repository, snapshot), confirmation_token.as_deref())?;
+    
+    let path = format!("/_snapshot/{}/{}", encode_segment(&repository), encode_segment(&snapshot));
+    let mut request = transport.request(Method::DELETE, &path)?;
+    
+    // Deleting from a large repository can outlast the default client timeout
+    if timeout_ms.is_none() {
+        request = request.timeout(std::time::Duration::from_secs(10 * 60));
+    }
+    
+    let response = request.send_with_retry().await.map_err(|e| e.to_string())?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete snapshot '{}': {} - {}", snapshot, status, body));
+    }
+    
+    println!("Successfully deleted snapshot {} from repository: {}", snapshot, repository);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn clone_snapshot(
+    window: Window,
+    repository: String,
+    source_snapshot: String,
+    target_snapshot: String,
+    indices: Vec<String>,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // The clone API requires an explicit index list, even if it is just "*"
+    if indices.is_empty() {
+        return Err("At least one index is required to clone a snapshot".to_string());
+    }
+    
+    let path = format!(
+        "/_snapshot/{}/{}/_clone/{}",
+        encode_segment(&repository),
+        encode_segment(&source_snapshot),
+        encode_segment(&target_snapshot)
+    );
+    
+    let clone_json = serde_json::json!({
+        "indices": indices.join(",")
+    });
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&clone_json)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully cloned snapshot {} to {} in repository: {}", source_snapshot, target_snapshot, repository);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to clone snapshot - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrokLineResult {
+    pub line: String,
+    pub matched: bool,
+    pub fields: serde_json::Value,
+    pub error: Option<String>,
+}
+
+#[command]
+pub async fn debug_grok(
+    window: Window,
+    pattern: String,
+    sample_lines: Vec<String>,
+    pattern_definitions: Option<HashMap<String, String>>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<GrokLineResult>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    if sample_lines.is_empty() {
+        return Ok(vec![]);
+    }
+    
+    let path = "/_ingest/pipeline/_simulate";
+    
+    // Run the pattern through a throwaway pipeline so the cluster's own grok engine does the matching
+    let simulate_json = serde_json::json!({
+        "pipeline": {
+            "processors": [{
+                "grok": {
+                    "field": "message",
+                    "patterns": [pattern],
+                    "pattern_definitions": pattern_definitions.unwrap_or_default()
+                }
+            }]
+        },
+        "docs": sample_lines.iter().map(|line| serde_json::json!({ "_source": { "message": line } })).collect::<Vec<_>>()
+    });
+    
+    // Send the request
+    let response = transport.send(Method::POST, path, Some(&simulate_json)).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        return Err(format!("Failed to simulate grok pattern - Status: {}, Response: {}", status, error_text));
+    }
+    
+    let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let docs = result["docs"].as_array().ok_or("Invalid response format")?;
+    
+    // Simulation returns one doc per input line, in order
+    let lines = sample_lines.into_iter().zip(docs.iter()).map(|(line, doc)| {
+        if let Some(reason) = doc["error"]["reason"].as_str() {
+            return GrokLineResult {
+                line,
+                matched: false,
+                fields: serde_json::json!({}),
+                error: Some(reason.to_string()),
+            };
+        }
+        
+        // Everything except the original message was captured by the pattern
+        let mut fields = doc["doc"]["_source"].clone();
+        if let Some(source) = fields.as_object_mut() {
+            source.remove("message");
+        }
+        
+        GrokLineResult {
+            line,
+            matched: true,
+            fields,
+            error: None,
+        }
+    }).collect();
+    
+    Ok(lines)
+}
+
+#[command]
+pub async fn get_grok_patterns(window: Window, timeout_ms: Option<u64>) -> Result<BTreeMap<String, String>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_ingest/processor/grok";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get grok patterns: {}", describe_error_response(response).await));
+    }
+    
+    let patterns_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let patterns = patterns_data["patterns"].as_object()
+        .map(|patterns| {
+            patterns.iter()
+                .filter_map(|(name, regex)| regex.as_str().map(|r| (name.clone(), r.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    
+    Ok(patterns)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogstashPipeline {
+    pub id: String,
+    pub description: String,
+    pub last_modified: String,
+    pub username: String,
+    pub pipeline: String,
+    pub pipeline_settings: serde_json::Value,
+}
+
+fn parse_logstash_pipelines(pipelines_data: &serde_json::Value) -> Vec<LogstashPipeline> {
+    let mut pipelines: Vec<LogstashPipeline> = pipelines_data.as_object()
+        .map(|pipelines| {
+            pipelines.iter().map(|(id, pipeline)| {
+                LogstashPipeline {
+                    id: id.clone(),
+                    description: pipeline["description"].as_str().unwrap_or("").to_string(),
+                    last_modified: pipeline["last_modified"].as_str().unwrap_or("").to_string(),
+                    username: pipeline["username"].as_str().unwrap_or("").to_string(),
+                    pipeline: pipeline["pipeline"].as_str().unwrap_or("").to_string(),
+                    pipeline_settings: pipeline["pipeline_settings"].clone(),
+                }
+            }).collect()
+        })
+        .unwrap_or_default();
+    
+    pipelines.sort_by(|a, b| a.id.cmp(&b.id));
+    pipelines
+}
+
+#[command]
+pub async fn list_logstash_pipelines(window: Window, timeout_ms: Option<u64>) -> Result<Vec<LogstashPipeline>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_logstash/pipeline";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list Logstash pipelines: {}", describe_error_response(response).await));
+    }
+    
+    let pipelines_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(parse_logstash_pipelines(&pipelines_data))
+}
+
+#[command]
+pub async fn get_logstash_pipeline(window: Window, id: String, timeout_ms: Option<u64>) -> Result<LogstashPipeline, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_logstash/pipeline/{}", encode_segment(&id));
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get Logstash pipeline '{}': {}", id, describe_error_response(response).await));
+    }
+    
+    let pipelines_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    parse_logstash_pipelines(&pipelines_data)
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Logstash pipeline '{}' was not found", id))
+}
+
+#[command]
+pub async fn put_logstash_pipeline(
+    window: Window,
+    id: String,
+    pipeline: String,
+    description: Option<String>,
+    pipeline_settings: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_logstash/pipeline/{}", encode_segment(&id));
+    
+    // Logstash requires every one of these fields, even when they carry no real information
+    let pipeline_json = serde_json::json!({
+        "description": description.unwrap_or_default(),
+        "last_modified": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "pipeline_metadata": {
+            "type": "logstash_pipeline",
+            "version": 1
+        },
+        "username": transport.connection().username.clone().unwrap_or_else(|| "elastico".to_string()),
+        "pipeline": pipeline,
+        "pipeline_settings": pipeline_settings.unwrap_or_else(|| serde_json::json!({}))
+    });
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&pipeline_json)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully saved Logstash pipeline: {}", id);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to save Logstash pipeline - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn delete_logstash_pipeline(window: Window, id: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_logstash/pipeline/{}", encode_segment(&id));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete Logstash pipeline '{}': {} - {}", id, status, body));
+    }
+    
+    println!("Successfully deleted Logstash pipeline: {}", id);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyInfo {
+    pub id: String,
+    pub name: String,
+    pub username: String,
+    pub realm: String,
+    pub creation: u64,
+    pub expiration: Option<u64>,
+    pub invalidated: bool,
+    pub role_descriptors: serde_json::Value,
+    pub metadata: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedApiKey {
+    pub id: String,
+    pub name: String,
+    pub api_key: String,
+    // Base64 of "id:api_key", ready to use as a connection's API key
+    pub encoded: String,
+    pub expiration: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidatedApiKeys {
+    pub invalidated: Vec<String>,
+    pub previously_invalidated: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+fn parse_api_keys(keys_data: &serde_json::Value) -> Vec<ApiKeyInfo> {
+    keys_data["api_keys"].as_array()
+        .map(|keys| {
+            keys.iter().map(|key| {
+                ApiKeyInfo {
+                    id: key["id"].as_str().unwrap_or("").to_string(),
+                    name: key["name"].as_str().unwrap_or("").to_string(),
+                    username: key["username"].as_str().unwrap_or("").to_string(),
+                    realm: key["realm"].as_str().unwrap_or("").to_string(),
+                    creation: key["creation"].as_u64().unwrap_or(0),
+                    expiration: key["expiration"].as_u64(),
+                    invalidated: key["invalidated"].as_bool().unwrap_or(false),
+                    role_descriptors: key["role_descriptors"].clone(),
+                    metadata: key["metadata"].clone(),
+                }
+            }).collect()
+        })
+        .unwrap_or_default()
+}
+
+#[command]
+pub async fn create_api_key(
+    window: Window,
+    name: String,
+    role_descriptors: Option<serde_json::Value>,
+    expiration: Option<String>,
+    metadata: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<CreatedApiKey, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_security/api_key";
+    
+    // Without role descriptors the key inherits a snapshot of the creating user's privileges
+    let mut key_json = serde_json::json!({ "name": name });
+    if let Some(role_descriptors) = role_descriptors {
+        key_json["role_descriptors"] = role_descriptors;
+    }
+    if let Some(expiration) = expiration.filter(|e| !e.is_empty()) {
+        key_json["expiration"] = serde_json::json!(expiration);
+    }
+    if let Some(metadata) = metadata {
+        key_json["metadata"] = metadata;
+    }
+    
+    // Send the request
+    let response = transport.send(Method::POST, path, Some(&key_json)).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        return Err(format!("Failed to create API key - Status: {}, Response: {}", status, error_text));
+    }
+    
+    let key_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let id = key_data["id"].as_str().unwrap_or("").to_string();
+    let api_key = key_data["api_key"].as_str().unwrap_or("").to_string();
+    
+    // Clusters before 7.16 don't return the encoded form
+    let encoded = key_data["encoded"].as_str()
+        .map(|encoded| encoded.to_string())
+        .unwrap_or_else(|| STANDARD.encode(format!("{}:{}", id, api_key)));
+    
+    println!("Successfully created API key: {}", name);
+    
+    Ok(CreatedApiKey {
+        id,
+        name: key_data["name"].as_str().unwrap_or(&name).to_string(),
+        api_key,
+        encoded,
+        expiration: key_data["expiration"].as_u64(),
+    })
+}
+
+#[command]
+pub async fn list_api_keys(
+    window: Window,
+    name: Option<String>,
+    username: Option<String>,
+    owner_only: Option<bool>,
+    active_only: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<ApiKeyInfo>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_security/api_key";
+    
+    let mut params: Vec<(&str, String)> = Vec::new();
+    if let Some(name) = name.filter(|n| !n.is_empty()) {
+        params.push(("name", name));
+    }
+    if let Some(username) = username.filter(|u| !u.is_empty()) {
+        params.push(("username", username));
+    }
+    if owner_only.unwrap_or(false) {
+        params.push(("owner", "true".to_string()));
+    }
+    if active_only.unwrap_or(false) {
+        params.push(("active_only", "true".to_string()));
+    }
+    
+    // Send the request
+    let response = transport.request(Method::GET, path)?.query(&params).send_with_retry().await.map_err(|e| e.to_string())?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list API keys: {}", describe_error_response(response).await));
+    }
+    
+    let keys_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(parse_api_keys(&keys_data))
+}
+
+#[command]
+pub async fn query_api_keys(
+    window: Window,
+    query: Option<serde_json::Value>,
+    from: Option<u32>,
+    size: Option<u32>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<ApiKeyInfo>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_security/_query/api_key";
+    
+    let mut query_json = serde_json::json!({
+        "from": from.unwrap_or(0),
+        "size": size.unwrap_or(100),
+        "sort": [{ "creation": "desc" }]
+    });
+    if let Some(query) = query {
+        query_json["query"] = query;
+    }
+    
+    // Send the request
+    let response = transport.send(Method::POST, path, Some(&query_json)).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        return Err(format!("Failed to query API keys - Status: {}, Response: {}", status, error_text));
+    }
+    
+    let keys_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(parse_api_keys(&keys_data))
+}
+
+#[command]
+pub async fn invalidate_api_keys(window: Window, ids: Vec<String>, timeout_ms: Option<u64>) -> Result<InvalidatedApiKeys, String> {
+    if ids.is_empty() {
+        return Err("No API keys selected to invalidate".to_string());
+    }
+    
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_security/api_key";
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, path, Some(&serde_json::json!({ "ids": ids }))).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to invalidate API keys: {} - {}", status, body));
+    }
+    
+    let result_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let id_list = |key: &str| -> Vec<String> {
+        result_data[key].as_array()
+            .map(|ids| ids.iter().filter_map(|id| id.as_str().map(|s| s.to_string())).collect())
+            .unwrap_or_default()
+    };
+    
+    let errors = result_data["error_details"].as_array()
+        .map(|details| {
+            details.iter()
+                .map(|detail| detail["reason"].as_str().unwrap_or("Unknown error").to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    
+    let invalidated = InvalidatedApiKeys {
+        invalidated: id_list("invalidated_api_keys"),
+        previously_invalidated: id_list("previously_invalidated_api_keys"),
+        errors,
+    };
+    
+    println!("Successfully invalidated {} API keys", invalidated.invalidated.len());
+    
+    Ok(invalidated)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityUser {
+    pub username: String,
+    pub roles: Vec<String>,
+    pub full_name: Option<String>,
+    pub email: Option<String>,
+    pub enabled: bool,
+    pub metadata: serde_json::Value,
+    // Built-in users such as "elastic" are reserved and can't be deleted
+    pub reserved: bool,
+}
+
+#[command]
+pub async fn list_users(window: Window, timeout_ms: Option<u64>) -> Result<Vec<SecurityUser>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_security/user";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list users: {}", describe_error_response(response).await));
+    }
+    
+    let users_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut users: Vec<SecurityUser> = users_data.as_object()
+        .map(|users| {
+            users.iter().map(|(username, user)| {
+                SecurityUser {
+                    username: username.clone(),
+                    roles: user["roles"].as_array()
+                        .map(|roles| roles.iter().filter_map(|r| r.as_str().map(|s| s.to_string())).collect())
+                        .unwrap_or_default(),
+                    full_name: user["full_name"].as_str().map(|s| s.to_string()),
+                    email: user["email"].as_str().map(|s| s.to_string()),
+                    enabled: user["enabled"].as_bool().unwrap_or(true),
+                    metadata: user["metadata"].clone(),
+                    reserved: user["metadata"]["_reserved"].as_bool().unwrap_or(false),
+                }
+            }).collect()
+        })
+        .unwrap_or_default();
+    
+    users.sort_by(|a, b| a.username.cmp(&b.username));
+    
+    Ok(users)
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn put_user(
+    window: Window,
+    username: String,
+    password: Option<String>,
+    roles: Vec<String>,
+    full_name: Option<String>,
+    email: Option<String>,
+    metadata: Option<serde_json::Value>,
+    enabled: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_security/user/{}", encode_segment(&username));
+    
+    let mut user_json = serde_json::json!({
+        "roles": roles,
+        "enabled": enabled.unwrap_or(true)
+    });
+    // Leaving the password out keeps the existing one when updating a user
+    if let Some(password) = password.filter(|p| !p.is_empty()) {
+        user_json["password"] = serde_json::json!(password);
+    }
+    if let Some(full_name) = full_name {
+        user_json["full_name"] = serde_json::json!(full_name);
+    }
+    if let Some(email) = email {
+        user_json["email"] = serde_json::json!(email);
+    }
+    if let Some(metadata) = metadata {
+        user_json["metadata"] = metadata;
+    }
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&user_json)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully saved user: {}", username);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to save user - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn change_user_password(window: Window, username: String, password: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_security/user/{}/_password", encode_segment(&username));
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, Some(&serde_json::json!({ "password": password }))).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully changed password for user: {}", username);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to change password - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn set_user_enabled(window: Window, username: String, enabled: bool, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let action = if enabled { "_enable" } else { "_disable" };
+    let path = format!("/_security/user/{}/{}", encode_segment(&username), action);
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, None).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully {} user: {}", if enabled { "enabled" } else { "disabled" }, username);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to {} user - Status: {}, Response: {}", if enabled { "enable" } else { "disable" }, status, error_text))
+    }
+}
+
+#[command]
+pub async fn delete_user(window: Window, username: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_security/user/{}", encode_segment(&username));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete user '{}': {} - {}", username, status, body));
+    }
+    
+    println!("Successfully deleted user: {}", username);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldSecurity {
+    #[serde(default)]
+    pub grant: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub except: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexPrivileges {
+    pub names: Vec<String>,
+    pub privileges: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_security: Option<FieldSecurity>,
+    // Document level security query, either a query object or its JSON string form
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_restricted_indices: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationPrivileges {
+    pub application: String,
+    pub privileges: Vec<String>,
+    pub resources: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoleDescriptor {
+    #[serde(default)]
+    pub cluster: Vec<String>,
+    #[serde(default)]
+    pub indices: Vec<IndexPrivileges>,
+    #[serde(default)]
+    pub applications: Vec<ApplicationPrivileges>,
+    #[serde(default)]
+    pub run_as: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    // Privileges on indices and clusters reached through cross-cluster search/replication
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_indices: Vec<RemoteIndexPrivileges>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_cluster: Vec<RemoteClusterPrivileges>,
+    // Global privileges such as `application.manage`, passed through untouched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global: Option<serde_json::Value>,
+    // Anything newer clusters add, so saving a role never drops what it doesn't know about
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteIndexPrivileges {
+    pub clusters: Vec<String>,
+    #[serde(flatten)]
+    pub privileges: IndexPrivileges,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteClusterPrivileges {
+    pub clusters: Vec<String>,
+    pub privileges: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityRole {
+    pub name: String,
+    pub descriptor: RoleDescriptor,
+    // Built-in roles such as "superuser" are reserved and can't be changed
+    pub reserved: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleMapping {
+    pub name: String,
+    pub enabled: bool,
+    pub roles: Vec<String>,
+    pub role_templates: Vec<serde_json::Value>,
+    // Nested any/all/field/except rule tree, passed through untouched
+    pub rules: serde_json::Value,
+    pub metadata: serde_json::Value,
+}
+
+#[command]
+pub async fn list_roles(window: Window, timeout_ms: Option<u64>) -> Result<Vec<SecurityRole>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_security/role";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list roles: {}", describe_error_response(response).await));
+    }
+    
+    let roles_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut roles = Vec::new();
+    if let Some(roles_map) = roles_data.as_object() {
+        for (name, role) in roles_map {
+            let descriptor: RoleDescriptor = serde_json::from_value(role.clone())
+                .map_err(|e| format!("Failed to parse role '{}': {}", name, e))?;
+            
+            roles.push(SecurityRole {
+                name: name.clone(),
+                descriptor,
+                reserved: role["metadata"]["_reserved"].as_bool().unwrap_or(false),
+            });
+        }
+    }
+    
+    roles.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    Ok(roles)
+}
+
+#[command]
+pub async fn put_role(window: Window, name: String, descriptor: RoleDescriptor, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_security/role/{}", encode_segment(&name));
+    
+    // Send the request
+    let response = transport.request(Method::PUT, &path)?.json(&descriptor).send_with_retry().await.map_err(|e| e.to_string())?;
+    
+    if response.status().is_success() {
+        println!("Successfully saved role: {}", name);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to save role - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn delete_role(window: Window, name: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_security/role/{}", encode_segment(&name));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete role '{}': {} - {}", name, status, body));
+    }
+    
+    println!("Successfully deleted role: {}", name);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn list_role_mappings(window: Window, timeout_ms: Option<u64>) -> Result<Vec<RoleMapping>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_security/role_mapping";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list role mappings: {}", describe_error_response(response).await));
+    }
+    
+    let mappings_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut mappings: Vec<RoleMapping> = mappings_data.as_object()
+        .map(|mappings| {
+            mappings.iter().map(|(name, mapping)| {
+                RoleMapping {
+                    name: name.clone(),
+                    enabled: mapping["enabled"].as_bool().unwrap_or(false),
+                    roles: mapping["roles"].as_array()
+                        .map(|roles| roles.iter().filter_map(|r| r.as_str().map(|s| s.to_string())).collect())
+                        .unwrap_or_default(),
+                    role_templates: mapping["role_templates"].as_array().cloned().unwrap_or_default(),
+                    rules: mapping["rules"].clone(),
+                    metadata: mapping["metadata"].clone(),
+                }
+            }).collect()
+        })
+        .unwrap_or_default();
+    
+    mappings.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    Ok(mappings)
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn put_role_mapping(
+    window: Window,
+    name: String,
+    roles: Vec<String>,
+    rules: serde_json::Value,
+    enabled: Option<bool>,
+    role_templates: Option<Vec<serde_json::Value>>,
+    metadata: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_security/role_mapping/{}", encode_segment(&name));
+    
+    // Elasticsearch accepts either fixed roles or templates, not both
+    let mut mapping_json = serde_json::json!({
+        "enabled": enabled.unwrap_or(true),
+        "rules": rules
+    });
+    match role_templates.filter(|t| !t.is_empty()) {
+        Some(templates) => mapping_json["role_templates"] = serde_json::json!(templates),
+        None => mapping_json["roles"] = serde_json::json!(roles),
+    }
+    if let Some(metadata) = metadata {
+        mapping_json["metadata"] = metadata;
+    }
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&mapping_json)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully saved role mapping: {}", name);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to save role mapping - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn delete_role_mapping(window: Window, name: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_security/role_mapping/{}", encode_segment(&name));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete role mapping '{}': {} - {}", name, status, body));
+    }
+    
+    println!("Successfully deleted role mapping: {}", name);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrentUserInfo {
+    pub username: String,
+    pub roles: Vec<String>,
+    pub full_name: Option<String>,
+    pub email: Option<String>,
+    pub enabled: bool,
+    pub authentication_realm: String,
+    pub authentication_realm_type: String,
+    pub lookup_realm: String,
+    // "realm", "api_key", "token" or "anonymous"
+    pub authentication_type: String,
+    pub api_key_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivilegeCheck {
+    pub username: String,
+    pub has_all_requested: bool,
+    pub cluster: BTreeMap<String, bool>,
+    // Index pattern -> privilege -> granted
+    pub index: BTreeMap<String, BTreeMap<String, bool>>,
+}
+
+#[command]
+pub async fn get_current_user_info(window: Window, timeout_ms: Option<u64>) -> Result<CurrentUserInfo, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_security/_authenticate";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get current user: {}", describe_error_response(response).await));
+    }
+    
+    let user_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(CurrentUserInfo {
+        username: user_data["username"].as_str().unwrap_or("").to_string(),
+        roles: user_data["roles"].as_array()
+            .map(|roles| roles.iter().filter_map(|r| r.as_str().map(|s| s.to_string())).collect())
+            .unwrap_or_default(),
+        full_name: user_data["full_name"].as_str().map(|s| s.to_string()),
+        email: user_data["email"].as_str().map(|s| s.to_string()),
+        enabled: user_data["enabled"].as_bool().unwrap_or(true),
+        authentication_realm: user_data["authentication_realm"]["name"].as_str().unwrap_or("").to_string(),
+        authentication_realm_type: user_data["authentication_realm"]["type"].as_str().unwrap_or("").to_string(),
+        lookup_realm: user_data["lookup_realm"]["name"].as_str().unwrap_or("").to_string(),
+        authentication_type: user_data["authentication_type"].as_str().unwrap_or("").to_string(),
+        api_key_name: user_data["api_key"]["name"].as_str().map(|s| s.to_string()),
+    })
+}
+
+async fn fetch_privileges(
+    transport: &Transport,
+    cluster: &[String],
+    index_privileges: &[String],
+    indices: &[String],
+) -> Result<PrivilegeCheck, String> {    
+    let mut check_json = serde_json::json!({ "cluster": cluster });
+    if !index_privileges.is_empty() && !indices.is_empty() {
+        check_json["index"] = serde_json::json!([{
+            "names": indices,
+            "privileges": index_privileges
+        }]);
+    }
+    
+    // Send the request
+    let response = transport.send(Method::POST, "/_security/user/_has_privileges", Some(&check_json)).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        return Err(format!("Failed to check privileges - Status: {}, Response: {}", status, error_text));
+    }
+    
+    let check_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let granted = |privileges: &serde_json::Value| -> BTreeMap<String, bool> {
+        privileges.as_object()
+            .map(|privileges| {
+                privileges.iter()
+                    .map(|(name, allowed)| (name.clone(), allowed.as_bool().unwrap_or(false)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    
+    let index = check_data["index"].as_object()
+        .map(|indices| indices.iter().map(|(name, privileges)| (name.clone(), granted(privileges))).collect())
+        .unwrap_or_default();
+    
+    Ok(PrivilegeCheck {
+        username: check_data["username"].as_str().unwrap_or("").to_string(),
+        has_all_requested: check_data["has_all_requested"].as_bool().unwrap_or(false),
+        cluster: granted(&check_data["cluster"]),
+        index,
+    })
+}
+
+// Pre-flight check for destructive operations, turning a would-be 403 into a readable error.
+// A failing check (e.g. security disabled) lets the operation proceed and report for itself.
+async fn ensure_privileges(
+    transport: &Transport,
+    cluster: &[&str],
+    index_privileges: &[&str],
+    indices: &[&str],
+) -> Result<(), String> {
+    let to_strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+    
+    let check = match fetch_privileges(transport, &to_strings(cluster), &to_strings(index_privileges), &to_strings(indices)).await {
+        Ok(check) => check,
+        Err(_) => return Ok(()),
+    };
+    
+    if check.has_all_requested {
+        return Ok(());
+    }
+    
+    let mut missing = Vec::new();
+    for (privilege, granted) in &check.cluster {
+        if !granted {
+            missing.push(format!("missing privilege '{}' on cluster", privilege));
+        }
+    }
+    for (index, privileges) in &check.index {
+        for (privilege, granted) in privileges {
+            if !granted {
+                missing.push(format!("missing privilege '{}' on index '{}'", privilege, index));
+            }
+        }
+    }
+    
+    Err(format!("User '{}' is not authorized: {}", check.username, missing.join(", ")))
+}
+
+#[command]
+pub async fn check_privileges(
+    window: Window,
+    actions: Vec<String>,
+    indices: Vec<String>,
+    cluster: Option<Vec<String>>,
+    timeout_ms: Option<u64>,
+) -> Result<PrivilegeCheck, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    fetch_privileges(&transport, &cluster.unwrap_or_default(), &actions, &indices).await
+}
+
+struct TokenSession {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: std::time::Instant,
+    // Auth type to fall back to once the token is invalidated
+    previous_auth_type: String,
+    // The password grant's GrantCredentials sealed with the install's key, so refreshing
+    // authenticates as the same user whatever the connection's own auth is
+    grant_credentials: String,
+    // Bumped on every new token so stale renewal tasks can tell they've been replaced
+    generation: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GrantCredentials {
+    username: String,
+    password: String,
+}
+
+// Sent as "security-token-renewal-failed" when the background renewal gives up, after which
+// the token runs out unless the user gets a new one
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenRenewalFailure {
+    pub connection_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityTokenInfo {
+    pub connection_id: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub has_refresh_token: bool,
+}
+
+// Renew this far ahead of expiry so requests in flight never see a dead token
+const TOKEN_RENEWAL_MARGIN_SECS: u64 = 60;
+
+async fn request_security_token(
+    transport: &Transport,
+    token_json: serde_json::Value,
+) -> Result<(String, Option<String>, u64, String), String> {
+    // Send the request
+    let response = transport.send(Method::POST, "/_security/oauth2/token", Some(&token_json)).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        return Err(format!("Failed to get security token - Status: {}, Response: {}", status, error_text));
+    }
+    
+    let token_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let access_token = token_data["access_token"].as_str()
+        .ok_or("Security token response did not contain an access token")?
+        .to_string();
+    
+    Ok((
+        access_token,
+        token_data["refresh_token"].as_str().map(|s| s.to_string()),
+        token_data["expires_in"].as_u64().unwrap_or(1200),
+        token_data["type"].as_str().unwrap_or("Bearer").to_string(),
+    ))
+}
+
+// Swaps in a fresh token pair and returns the generation the renewal task should watch
+fn store_token_session(
+    conn: &ElasticsearchConnection,
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: u64,
+    previous_auth_type: String,
+    grant_credentials: String,
+) -> u64 {
+    let mut sessions = TOKEN_SESSIONS.lock();
+    let generation = sessions.get(&conn.id).map(|session| session.generation + 1).unwrap_or(0);
+    
+    sessions.insert(conn.id.clone(), TokenSession {
+        access_token,
+        refresh_token,
+        expires_at: std::time::Instant::now() + std::time::Duration::from_secs(expires_in),
+        previous_auth_type,
+        grant_credentials,
+        generation,
+    });
+    
+    generation
+}
+
+async fn refresh_token_session(app: &AppHandle, transport: &Transport) -> Result<SecurityTokenInfo, String> {
+    let conn = transport.connection();
+    let (refresh_token, previous_auth_type, grant_credentials) = {
+        let sessions = TOKEN_SESSIONS.lock();
+        let session = sessions.get(&conn.id).ok_or("No security token for this connection")?;
+        
+        (
+            session.refresh_token.clone().ok_or("The security token can't be refreshed")?,
+            session.previous_auth_type.clone(),
+            session.grant_credentials.clone(),
+        )
+    };
+    
+    // The bearer token may already have expired, so the grant authenticates as the user the token was issued to
+    let credentials: GrantCredentials = serde_json::from_str(&secrets::open(&data_dir(app)?, &grant_credentials)?)
+        .map_err(|e| e.to_string())?;
+    let mut refresh_conn = conn.clone();
+    refresh_conn.auth_type = "basic".to_string();
+    refresh_conn.username = Some(credentials.username);
+    refresh_conn.password = Some(credentials.password);
+    
+    let token_json = serde_json::json!({
+        "grant_type": "refresh_token",
+        "refresh_token": refresh_token
+    });
+    let (access_token, refresh_token, expires_in, token_type) = request_security_token(&transport.for_connection(refresh_conn), token_json).await?;
+    
+    let has_refresh_token = refresh_token.is_some();
+    let generation = store_token_session(conn, access_token, refresh_token, expires_in, previous_auth_type, grant_credentials);
+    spawn_token_renewal(app.clone(), transport.clone(), generation);
+    
+    Ok(SecurityTokenInfo {
+        connection_id: conn.id.clone(),
+        token_type,
+        expires_in,
+        has_refresh_token,
+    })
+}
+
+// Sleeps until shortly before the token expires, then refreshes it, which schedules the next renewal
+fn spawn_token_renewal(app: AppHandle, transport: Transport, generation: u64) {
+    tauri::async_runtime::spawn(async move {
+        let renew_at = {
+            let sessions = TOKEN_SESSIONS.lock();
+            match sessions.get(transport.connection_id()) {
+                Some(session) if session.generation == generation && session.refresh_token.is_some() => {
+                    session.expires_at - std::time::Duration::from_secs(TOKEN_RENEWAL_MARGIN_SECS)
+                        .min(session.expires_at.saturating_duration_since(std::time::Instant::now()) / 2)
+                }
+                _ => return,
+            }
+        };
+        
+        tokio::time::sleep_until(renew_at.into()).await;
+        
+        // The token may have been replaced or invalidated while we slept
+        let current = TOKEN_SESSIONS.lock().get(transport.connection_id()).map(|session| session.generation);
+        if current != Some(generation) {
+            return;
+        }
+        
+        if let Err(e) = refresh_token_session(&app, &transport).await {
+            println!("Failed to renew security token for connection {}: {}", transport.connection_id(), e);
+            let _ = app.emit("security-token-renewal-failed", TokenRenewalFailure {
+                connection_id: transport.connection_id().to_string(),
+                error: e,
+            });
+        }
+    });
+}
+
+#[command]
+pub async fn get_security_token(window: Window, app: AppHandle, username: Option<String>, password: Option<String>, timeout_ms: Option<u64>) -> Result<SecurityTokenInfo, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let conn = transport.connection().clone();
+    
+    // The password grant authenticates as the given user, defaulting to the connection's own credentials
+    let username = username.or_else(|| conn.username.clone()).ok_or("A username is required to get a security token")?;
+    let password = password.or_else(|| conn.password.clone()).ok_or("A password is required to get a security token")?;
+    
+    let previous_auth_type = match TOKEN_SESSIONS.lock().get(&conn.id) {
+        Some(session) => session.previous_auth_type.clone(),
+        None => conn.auth_type.clone(),
+    };
+    
+    let mut request_conn = conn.clone();
+    request_conn.auth_type = "basic".to_string();
+    request_conn.username = Some(username.clone());
+    request_conn.password = Some(password.clone());
+    
+    let token_json = serde_json::json!({
+        "grant_type": "password",
+        "username": username,
+        "password": password
+    });
+    let (access_token, refresh_token, expires_in, token_type) = request_security_token(&transport.for_connection(request_conn), token_json).await?;
+    
+    let grant_credentials = serde_json::to_string(&GrantCredentials { username: username.clone(), password })
+        .map_err(|e| e.to_string())?;
+    let grant_credentials = secrets::seal(&data_dir(&app)?, &grant_credentials)?;
+    
+    let has_refresh_token = refresh_token.is_some();
+    let generation = store_token_session(&conn, access_token, refresh_token, expires_in, previous_auth_type, grant_credentials);
+    
+    // Every request from here on carries the bearer token
+    let mut token_conn = conn.clone();
+    token_conn.auth_type = "token".to_string();
+    {
+        // Other windows on the same saved connection share the token session
+        let mut conn_guard = CONNECTIONS.lock();
+        for current in conn_guard.values_mut().filter(|c| c.id == conn.id) {
+            *current = token_conn.clone();
+        }
+    }
+    spawn_token_renewal(app, transport.for_connection(token_conn), generation);
+    
+    println!("Successfully obtained security token for user: {}", username);
+    
+    Ok(SecurityTokenInfo {
+        connection_id: conn.id,
+        token_type,
+        expires_in,
+        has_refresh_token,
+    })
+}
+
+#[command]
+pub async fn refresh_security_token(window: Window, app: AppHandle, timeout_ms: Option<u64>) -> Result<SecurityTokenInfo, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    refresh_token_session(&app, &transport).await
+}
+
+#[command]
+pub async fn invalidate_security_token(window: Window, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let conn = transport.connection();
+    
+    let (access_token, refresh_token, previous_auth_type) = {
+        let sessions = TOKEN_SESSIONS.lock();
+        let session = sessions.get(&conn.id).ok_or("No security token for this connection")?;
+        
+        (session.access_token.clone(), session.refresh_token.clone(), session.previous_auth_type.clone())
+    };
+    
+    // Access and refresh tokens are invalidated separately; the refresh token goes first so it can't mint a replacement
+    let mut bodies = Vec::new();
+    if let Some(refresh_token) = refresh_token {
+        bodies.push(serde_json::json!({ "refresh_token": refresh_token }));
+    }
+    bodies.push(serde_json::json!({ "token": access_token }));
+    
+    for body in bodies {
+        // Send the DELETE request
+        let response = transport.send(Method::DELETE, "/_security/oauth2/token", Some(&body)).await?;
+        
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = match response.text().await {
+                Ok(text) => describe_error_body(&text),
+                Err(_) => "Could not read error response".to_string()
+            };
+            
+            return Err(format!("Failed to invalidate security token: {} - {}", status, body));
+        }
+    }
+    
+    TOKEN_SESSIONS.lock().remove(&conn.id);
+    
+    {
+        let mut conn_guard = CONNECTIONS.lock();
+        for current in conn_guard.values_mut().filter(|c| c.id == conn.id) {
+            current.auth_type = previous_auth_type.clone();
+        }
+    }
+    
+    println!("Successfully invalidated security token for connection: {}", conn.id);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchSummary {
+    pub id: String,
+    pub active: bool,
+    pub last_checked: Option<String>,
+    pub last_met_condition: Option<String>,
+    pub execution_state: Option<String>,
+    // Action id -> ack state ("awaits_successful_execution", "ackable" or "acked")
+    pub action_states: BTreeMap<String, String>,
+    pub trigger: serde_json::Value,
+    pub metadata: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchDetails {
+    pub id: String,
+    pub found: bool,
+    pub watch: serde_json::Value,
+    pub status: serde_json::Value,
+}
+
+fn parse_watch_summary(id: &str, watch: &serde_json::Value, status: &serde_json::Value) -> WatchSummary {
+    let action_states = status["actions"].as_object()
+        .map(|actions| {
+            actions.iter()
+                .map(|(action_id, action)| (action_id.clone(), action["ack"]["state"].as_str().unwrap_or("").to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    
+    WatchSummary {
+        id: id.to_string(),
+        active: status["state"]["active"].as_bool().unwrap_or(false),
+        last_checked: status["last_checked"].as_str().map(|s| s.to_string()),
+        last_met_condition: status["last_met_condition"].as_str().map(|s| s.to_string()),
+        execution_state: status["execution_state"].as_str().map(|s| s.to_string()),
+        action_states,
+        trigger: watch["trigger"].clone(),
+        metadata: watch["metadata"].clone(),
+    }
+}
+
+#[command]
+pub async fn list_watches(window: Window, timeout_ms: Option<u64>) -> Result<Vec<WatchSummary>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_watcher/_query/watches";
+    
+    let query_json = serde_json::json!({
+        "size": 1000,
+        "sort": [{ "_id": "asc" }]
+    });
+    
+    // Send the request
+    let response = transport.send(Method::POST, path, Some(&query_json)).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list watches: {}", describe_error_response(response).await));
+    }
+    
+    let watches_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let watches = watches_data["watches"].as_array()
+        .map(|watches| {
+            watches.iter()
+                .map(|watch| parse_watch_summary(watch["_id"].as_str().unwrap_or(""), &watch["watch"], &watch["status"]))
+                .collect()
+        })
+        .unwrap_or_default();
+    
+    Ok(watches)
+}
+
+#[command]
+pub async fn get_watch(window: Window, id: String, timeout_ms: Option<u64>) -> Result<WatchDetails, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_watcher/watch/{}", encode_segment(&id));
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get watch '{}': {}", id, describe_error_response(response).await));
+    }
+    
+    let watch_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(WatchDetails {
+        id: watch_data["_id"].as_str().unwrap_or(&id).to_string(),
+        found: watch_data["found"].as_bool().unwrap_or(false),
+        watch: watch_data["watch"].clone(),
+        status: watch_data["status"].clone(),
+    })
+}
+
+#[command]
+pub async fn put_watch(window: Window, id: String, watch: serde_json::Value, active: Option<bool>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_watcher/watch/{}", encode_segment(&id));
+    
+    // Send the request
+    let response = transport.request(Method::PUT, &path)?
+        .query(&[("active", active.unwrap_or(true).to_string())])
+        .json(&watch)
+        .send_with_retry()
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    if response.status().is_success() {
+        println!("Successfully saved watch: {}", id);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to save watch - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn set_watch_active(window: Window, id: String, active: bool, timeout_ms: Option<u64>) -> Result<WatchSummary, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let action = if active { "_activate" } else { "_deactivate" };
+    let path = format!("/_watcher/watch/{}/{}", encode_segment(&id), action);
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        return Err(format!("Failed to {} watch - Status: {}, Response: {}", if active { "activate" } else { "deactivate" }, status, error_text));
+    }
+    
+    let status_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    println!("Successfully {} watch: {}", if active { "activated" } else { "deactivated" }, id);
+    
+    Ok(parse_watch_summary(&id, &serde_json::Value::Null, &status_data["status"]))
+}
+
+#[command]
+pub async fn execute_watch(
+    window: Window,
+    id: String,
+    record_execution: Option<bool>,
+    ignore_condition: Option<bool>,
+    alternative_input: Option<serde_json::Value>,
+    action_modes: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_watcher/watch/{}/_execute", encode_segment(&id));
+    
+    // Dry runs by default: nothing is written to the watch history unless asked
+    let mut execute_json = serde_json::json!({
+        "record_execution": record_execution.unwrap_or(false),
+        "ignore_condition": ignore_condition.unwrap_or(false)
+    });
+    if let Some(alternative_input) = alternative_input {
+        execute_json["alternative_input"] = alternative_input;
+    }
+    // e.g. {"_all": "simulate"} to run the watch without sending any emails or webhooks
+    if let Some(action_modes) = action_modes {
+        execute_json["action_modes"] = action_modes;
+    }
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, Some(&execute_json)).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        return Err(format!("Failed to execute watch - Status: {}, Response: {}", status, error_text));
+    }
+    
+    let execution_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(execution_data["watch_record"].clone())
+}
+
+#[command]
+pub async fn ack_watch(window: Window, id: String, action_ids: Option<Vec<String>>, timeout_ms: Option<u64>) -> Result<WatchSummary, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Without action ids every action of the watch is acknowledged
+    let path = match action_ids.filter(|ids| !ids.is_empty()) {
+        Some(ids) => format!("/_watcher/watch/{}/_ack/{}", encode_segment(&id), encode_segments(&ids)),
+        None => format!("/_watcher/watch/{}/_ack", encode_segment(&id)),
+    };
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        return Err(format!("Failed to acknowledge watch - Status: {}, Response: {}", status, error_text));
+    }
+    
+    let status_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    println!("Successfully acknowledged watch: {}", id);
+    
+    Ok(parse_watch_summary(&id, &serde_json::Value::Null, &status_data["status"]))
+}
+
+#[command]
+pub async fn delete_watch(window: Window, id: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_watcher/watch/{}", encode_segment(&id));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete watch '{}': {} - {}", id, status, body));
+    }
+    
+    println!("Successfully deleted watch: {}", id);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardCell {
+    pub index: String,
+    pub shard: u32,
+    pub primary: bool,
+    pub state: String,
+    pub docs: u64,
+    pub store_bytes: u64,
+    pub unassigned_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardMapNode {
+    pub name: String,
+    pub ip: String,
+    pub roles: String,
+    pub master: bool,
+    pub heap_percent: u32,
+    pub disk_used_percent: f64,
+    pub shard_count: u32,
+    pub primary_count: u32,
+    pub store_bytes: u64,
+    pub shards: Vec<ShardCell>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardMap {
+    pub nodes: Vec<ShardMapNode>,
+    pub indices: Vec<String>,
+    pub unassigned: Vec<ShardCell>,
+}
+
+#[command]
+pub async fn get_shard_map(window: Window, timeout_ms: Option<u64>) -> Result<ShardMap, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let nodes_path = "/_cat/nodes?format=json&h=name,ip,node.role,master,heap.percent,disk.used_percent";
+    let response = transport.send(Method::GET, nodes_path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get nodes: {}", describe_error_response(response).await));
+    }
+    
+    let nodes_data: Vec<HashMap<String, Option<String>>> = response.json().await.map_err(|e| e.to_string())?;
+    
+    let shards_path = "/_cat/shards?format=json&bytes=b&h=index,shard,prirep,state,docs,store,node,unassigned.reason";
+    let response = transport.send(Method::GET, shards_path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get shards: {}", describe_error_response(response).await));
+    }
+    
+    let shards_data: Vec<HashMap<String, Option<String>>> = response.json().await.map_err(|e| e.to_string())?;
+    
+    let mut nodes: BTreeMap<String, ShardMapNode> = nodes_data.into_iter().map(|row| {
+        let field = |key: &str| row.get(key).cloned().flatten();
+        let name = field("name").unwrap_or_default();
+        
+        (name.clone(), ShardMapNode {
+            name,
+            ip: field("ip").unwrap_or_default(),
+            roles: field("node.role").unwrap_or_default(),
+            master: field("master").as_deref() == Some("*"),
+            heap_percent: field("heap.percent").and_then(|v| v.parse().ok()).unwrap_or(0),
+            disk_used_percent: field("disk.used_percent").and_then(|v| v.parse().ok()).unwrap_or(0.0),
+            shard_count: 0,
+            primary_count: 0,
+            store_bytes: 0,
+            shards: Vec::new(),
+        })
+    }).collect();
+    
+    let mut indices = BTreeSet::new();
+    let mut unassigned = Vec::new();
+    
+    for row in shards_data {
+        let field = |key: &str| row.get(key).cloned().flatten();
+        
+        let cell = ShardCell {
+            index: field("index").unwrap_or_default(),
+            shard: field("shard").and_then(|v| v.parse().ok()).unwrap_or(0),
+            primary: field("prirep").as_deref() == Some("p"),
+            state: field("state").unwrap_or_default(),
+            docs: field("docs").and_then(|v| v.parse().ok()).unwrap_or(0),
+            store_bytes: field("store").and_then(|v| v.parse().ok()).unwrap_or(0),
+            unassigned_reason: field("unassigned.reason"),
+        };
+        indices.insert(cell.index.clone());
+        
+        // Relocating shards read "source -> ip id target"; the shard still lives on the source
+        let node_name = field("node").and_then(|node| node.split(" -> ").next().map(|n| n.trim().to_string()));
+        
+        match node_name.and_then(|name| nodes.get_mut(&name)) {
+            Some(node) => {
+                node.shard_count += 1;
+                if cell.primary {
+                    node.primary_count += 1;
+                }
+                node.store_bytes += cell.store_bytes;
+                node.shards.push(cell);
+            }
+            None => unassigned.push(cell),
+        }
+    }
+    
+    let mut nodes: Vec<ShardMapNode> = nodes.into_values().collect();
+    for node in &mut nodes {
+        node.shards.sort_by(|a, b| a.index.cmp(&b.index).then(a.shard.cmp(&b.shard)));
+    }
+    unassigned.sort_by(|a, b| a.index.cmp(&b.index).then(a.shard.cmp(&b.shard)));
+    
+    Ok(ShardMap {
+        nodes,
+        indices: indices.into_iter().collect(),
+        unassigned,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformSummary {
+    pub id: String,
+    pub description: Option<String>,
+    pub source_indices: Vec<String>,
+    pub dest_index: String,
+    // "pivot" or "latest"
+    pub kind: String,
+    // Continuous transforms have a sync section, batch transforms run once
+    pub continuous: bool,
+    pub frequency: Option<String>,
+    pub create_time: Option<i64>,
+    pub config: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformStats {
+    pub id: String,
+    // "stopped", "started", "indexing", "aborting", "stopping" or "failed"
+    pub state: String,
+    pub reason: Option<String>,
+    pub health: Option<String>,
+    pub node: Option<String>,
+    pub documents_processed: u64,
+    pub documents_indexed: u64,
+    pub pages_processed: u64,
+    pub trigger_count: u64,
+    pub index_failures: u64,
+    pub search_failures: u64,
+    pub checkpoint: u64,
+    pub last_checkpoint_at: Option<i64>,
+    pub changes_last_detected_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformPreview {
+    pub documents: Vec<serde_json::Value>,
+    // Mappings and settings the destination index would be created with
+    pub generated_dest_index: serde_json::Value,
+}
+
+fn parse_transform_summary(transform: &serde_json::Value) -> TransformSummary {
+    // source.index can be a single string or a list
+    let source_indices = match &transform["source"]["index"] {
+        serde_json::Value::Array(indices) => indices.iter().filter_map(|i| i.as_str().map(|s| s.to_string())).collect(),
+        serde_json::Value::String(index) => vec![index.clone()],
+        _ => Vec::new(),
+    };
+    
+    TransformSummary {
+        id: transform["id"].as_str().unwrap_or("").to_string(),
+        description: transform["description"].as_str().map(|s| s.to_string()),
+        source_indices,
+        dest_index: transform["dest"]["index"].as_str().unwrap_or("").to_string(),
+        kind: if transform.get("latest").is_some() { "latest" } else { "pivot" }.to_string(),
+        continuous: transform.get("sync").is_some(),
+        frequency: transform["frequency"].as_str().map(|s| s.to_string()),
+        create_time: transform["create_time"].as_i64(),
+        config: transform.clone(),
+    }
+}
+
+fn parse_transform_stats(stats: &serde_json::Value) -> TransformStats {
+    let counter = |key: &str| stats["stats"][key].as_u64().unwrap_or(0);
+    
+    TransformStats {
+        id: stats["id"].as_str().unwrap_or("").to_string(),
+        state: stats["state"].as_str().unwrap_or("unknown").to_string(),
+        reason: stats["reason"].as_str().map(|s| s.to_string()),
+        health: stats["health"]["status"].as_str().map(|s| s.to_string()),
+        node: stats["node"]["name"].as_str().map(|s| s.to_string()),
+        documents_processed: counter("documents_processed"),
+        documents_indexed: counter("documents_indexed"),
+        pages_processed: counter("pages_processed"),
+        trigger_count: counter("trigger_count"),
+        index_failures: counter("index_failures"),
+        search_failures: counter("search_failures"),
+        checkpoint: stats["checkpointing"]["last"]["checkpoint"].as_u64().unwrap_or(0),
+        last_checkpoint_at: stats["checkpointing"]["last"]["timestamp_millis"].as_i64(),
+        changes_last_detected_at: stats["checkpointing"]["changes_last_detected_at"].as_i64(),
+    }
+}
+
+#[command]
+pub async fn list_transforms(window: Window, timeout_ms: Option<u64>) -> Result<Vec<TransformSummary>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_transform?size=1000";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list transforms: {}", describe_error_response(response).await));
+    }
+    
+    let transforms_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let transforms = transforms_data["transforms"].as_array()
+        .map(|transforms| transforms.iter().map(parse_transform_summary).collect())
+        .unwrap_or_default();
+    
+    Ok(transforms)
+}
+
+#[command]
+pub async fn get_transform_stats(window: Window, id: Option<String>, timeout_ms: Option<u64>) -> Result<Vec<TransformStats>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Without an id the stats of every transform are returned
+    let target = id.as_deref().map(encode_segment).unwrap_or_else(|| "_all".to_string());
+    let path = format!("/_transform/{}/_stats?size=1000", target);
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get transform stats: {}", describe_error_response(response).await));
+    }
+    
+    let stats_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let stats = stats_data["transforms"].as_array()
+        .map(|transforms| transforms.iter().map(parse_transform_stats).collect())
+        .unwrap_or_default();
+    
+    Ok(stats)
+}
+
+#[command]
+pub async fn put_transform(window: Window, id: String, transform: serde_json::Value, defer_validation: Option<bool>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_transform/{}", encode_segment(&id));
+    
+    // Deferred validation lets a transform be created before its source index exists
+    let response = transport.request(Method::PUT, &path)?
+        .query(&[("defer_validation", defer_validation.unwrap_or(false).to_string())])
+        .json(&transform)
+        .send_with_retry()
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    if response.status().is_success() {
+        println!("Successfully created transform: {}", id);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to create transform - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn preview_transform(window: Window, transform: serde_json::Value, timeout_ms: Option<u64>) -> Result<TransformPreview, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_transform/_preview";
+    
+    // Send the request
+    let response = transport.send(Method::POST, path, Some(&transform)).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to preview transform: {}", describe_error_response(response).await));
+    }
+    
+    let preview_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(TransformPreview {
+        documents: preview_data["preview"].as_array().cloned().unwrap_or_default(),
+        generated_dest_index: preview_data["generated_dest_index"].clone(),
+    })
+}
+
+#[command]
+pub async fn start_transform(window: Window, id: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_transform/{}/_start", encode_segment(&id));
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to start transform '{}': {}", id, describe_error_response(response).await));
+    }
+    
+    println!("Successfully started transform: {}", id);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn stop_transform(window: Window, id: String, force: Option<bool>, wait_for_checkpoint: Option<bool>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_transform/{}/_stop", encode_segment(&id));
+    
+    // Waiting for the checkpoint lets a continuous transform finish its current run cleanly
+    let response = transport.request(Method::POST, &path)?
+        .query(&[
+            ("force", force.unwrap_or(false).to_string()),
+            ("wait_for_checkpoint", wait_for_checkpoint.unwrap_or(false).to_string()),
+        ])
+        .send_with_retry()
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to stop transform '{}': {}", id, describe_error_response(response).await));
+    }
+    
+    println!("Successfully stopped transform: {}", id);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn delete_transform(window: Window, id: String, force: Option<bool>, delete_dest_index: Option<bool>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_transform/{}", encode_segment(&id));
+    
+    // Send the DELETE request
+    let response = transport.request(Method::DELETE, &path)?
+        .query(&[
+            ("force", force.unwrap_or(false).to_string()),
+            ("delete_dest_index", delete_dest_index.unwrap_or(false).to_string()),
+        ])
+        .send_with_retry()
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete transform '{}': {} - {}", id, status, body));
+    }
+    
+    println!("Successfully deleted transform: {}", id);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupSupport {
+    pub version: String,
+    // Whether the _rollup endpoints respond at all on this cluster
+    pub available: bool,
+    // Rollups are deprecated from 8.11 in favour of downsampling
+    pub deprecated: bool,
+    // From 9.0 new jobs can only be created on clusters that already use rollups
+    pub can_create_jobs: bool,
+    pub job_count: usize,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollupJob {
+    pub id: String,
+    pub index_pattern: String,
+    pub rollup_index: String,
+    pub cron: String,
+    pub page_size: u64,
+    // "started", "indexing", "stopping", "stopped" or "aborting"
+    pub state: String,
+    pub documents_processed: u64,
+    pub rollups_indexed: u64,
+    pub trigger_count: u64,
+    pub index_failures: u64,
+    pub search_failures: u64,
+    pub config: serde_json::Value,
+}
+
+fn parse_major_minor(version: &str) -> (u32, u32) {
+    let mut parts = version.split(['.', '-']).map(|part| part.parse().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+fn parse_rollup_job(job: &serde_json::Value) -> RollupJob {
+    let config = &job["config"];
+    let counter = |key: &str| job["stats"][key].as_u64().unwrap_or(0);
+    
+    RollupJob {
+        id: config["id"].as_str().unwrap_or("").to_string(),
+        index_pattern: config["index_pattern"].as_str().unwrap_or("").to_string(),
+        rollup_index: config["rollup_index"].as_str().unwrap_or("").to_string(),
+        cron: config["cron"].as_str().unwrap_or("").to_string(),
+        page_size: config["page_size"].as_u64().unwrap_or(0),
+        state: job["status"]["job_state"].as_str().unwrap_or("unknown").to_string(),
+        documents_processed: counter("documents_processed"),
+        rollups_indexed: counter("rollups_indexed"),
+        trigger_count: counter("trigger_count"),
+        index_failures: counter("index_failures"),
+        search_failures: counter("search_failures"),
+        config: config.clone(),
+    }
+}
+
+#[command]
+pub async fn get_rollup_support(window: Window, timeout_ms: Option<u64>) -> Result<RollupSupport, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let root = transport.get_json("/").await?;
+    let version = root["version"]["number"].as_str().unwrap_or("").to_string();
+    let (major, minor) = parse_major_minor(&version);
+    let deprecated = major > 8 || (major == 8 && minor >= 11);
+    
+    // A failing probe means rollups were removed or the license/role doesn't allow them
+    let (available, job_count, probe_error) = match transport.get_json("/_rollup/job/_all").await {
+        Ok(jobs) => (true, jobs["jobs"].as_array().map(|jobs| jobs.len()).unwrap_or(0), None),
+        Err(e) => (false, 0, Some(e)),
+    };
+    
+    let can_create_jobs = available && (major < 9 || job_count > 0);
+    
+    let message = if let Some(e) = probe_error {
+        Some(format!("Rollup API is not available on this cluster: {}", e))
+    } else if !can_create_jobs {
+        Some("New rollup jobs can only be created on clusters that already use rollups, use downsampling instead".to_string())
+    } else if deprecated {
+        Some("Rollups are deprecated on this version, consider downsampling instead".to_string())
+    } else {
+        None
+    };
+    
+    Ok(RollupSupport {
+        version,
+        available,
+        deprecated,
+        can_create_jobs,
+        job_count,
+        message,
+    })
+}
+
+#[command]
+pub async fn list_rollup_jobs(window: Window, timeout_ms: Option<u64>) -> Result<Vec<RollupJob>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_rollup/job/_all";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list rollup jobs: {}", describe_error_response(response).await));
+    }
+    
+    let jobs_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let jobs = jobs_data["jobs"].as_array()
+        .map(|jobs| jobs.iter().map(parse_rollup_job).collect())
+        .unwrap_or_default();
+    
+    Ok(jobs)
+}
+
+#[command]
+pub async fn get_rollup_job(window: Window, id: String, timeout_ms: Option<u64>) -> Result<RollupJob, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_rollup/job/{}", encode_segment(&id));
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get rollup job '{}': {}", id, describe_error_response(response).await));
+    }
+    
+    let jobs_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    jobs_data["jobs"].as_array()
+        .and_then(|jobs| jobs.first())
+        .map(parse_rollup_job)
+        .ok_or_else(|| format!("Rollup job '{}' not found", id))
+}
+
+#[command]
+pub async fn put_rollup_job(window: Window, id: String, job: serde_json::Value, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_rollup/job/{}", encode_segment(&id));
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&job)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully created rollup job: {}", id);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to create rollup job - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn start_rollup_job(window: Window, id: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_rollup/job/{}/_start", encode_segment(&id));
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to start rollup job '{}': {}", id, describe_error_response(response).await));
+    }
+    
+    println!("Successfully started rollup job: {}", id);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn stop_rollup_job(window: Window, id: String, wait_for_completion: Option<bool>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_rollup/job/{}/_stop", encode_segment(&id));
+    
+    // Send the request
+    let response = transport.request(Method::POST, &path)?
+        .query(&[("wait_for_completion", wait_for_completion.unwrap_or(false).to_string())])
+        .send_with_retry()
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to stop rollup job '{}': {}", id, describe_error_response(response).await));
+    }
+    
+    println!("Successfully stopped rollup job: {}", id);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn delete_rollup_job(window: Window, id: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // ES refuses to delete a job that hasn't been stopped first
+    let path = format!("/_rollup/job/{}", encode_segment(&id));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete rollup job '{}': {} - {}", id, status, body));
+    }
+    
+    println!("Successfully deleted rollup job: {}", id);
+    
+    Ok(true)
+}
+
+// Passing a request_id lets cancel_request abort the search
+#[command]
+pub async fn rollup_search(window: Window, index: String, query: serde_json::Value, request_id: Option<String>, timeout_ms: Option<u64>) -> Result<serde_json::Value, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // The index can mix live and rollup indices, ES merges the results
+    let path = format!("/{}/_rollup_search", encode_segment(&index));
+    
+    // Rollup search only supports aggregations, so never ask for hits
+    let mut query_json = query;
+    if let Some(body) = query_json.as_object_mut() {
+        body.insert("size".to_string(), serde_json::json!(0));
+    }
+    
+    cancellable(request_id, async {
+        // Send the request
+        let response = transport.send(Method::POST, &path, Some(&query_json)).await?;
+        
+        if !response.status().is_success() {
+            return Err(format!("Rollup search failed: {}", describe_error_response(response).await));
+        }
+        
+        response.json().await.map_err(|e| e.to_string())
+    }).await
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphVertexField {
+    pub field: String,
+    pub size: Option<u32>,
+    pub min_doc_count: Option<u32>,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphVertex {
+    pub field: String,
+    pub term: String,
+    pub weight: f64,
+    // 0 for vertices from the first hop, 1 for those found through connections
+    pub depth: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    // Positions in the vertices list
+    pub source: usize,
+    pub target: usize,
+    pub weight: f64,
+    pub doc_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphExploreResult {
+    pub vertices: Vec<GraphVertex>,
+    pub edges: Vec<GraphEdge>,
+    pub took: u64,
+    pub timed_out: bool,
+}
+
+fn graph_vertex_json(vertex: &GraphVertexField) -> serde_json::Value {
+    let mut vertex_json = serde_json::json!({ "field": vertex.field });
+    if let Some(size) = vertex.size {
+        vertex_json["size"] = serde_json::json!(size);
+    }
+    if let Some(min_doc_count) = vertex.min_doc_count {
+        vertex_json["min_doc_count"] = serde_json::json!(min_doc_count);
+    }
+    if let Some(include) = &vertex.include {
+        vertex_json["include"] = serde_json::json!(include);
+    }
+    if let Some(exclude) = &vertex.exclude {
+        vertex_json["exclude"] = serde_json::json!(exclude);
+    }
+    vertex_json
+}
+
+#[command]
+pub async fn graph_explore(
+    window: Window,
+    index: String,
+    query: serde_json::Value,
+    vertices: Vec<GraphVertexField>,
+    connections: Option<Vec<GraphVertexField>>,
+    timeout_ms: Option<u64>,
+) -> Result<GraphExploreResult, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    if vertices.is_empty() {
+        return Err("At least one vertex field is required".to_string());
+    }
+    
+    let path = format!("/{}/_graph/explore", encode_segment(&index));
+    
+    let mut explore_json = serde_json::json!({
+        "query": query,
+        "vertices": vertices.iter().map(graph_vertex_json).collect::<Vec<_>>()
+    });
+    // Connections take the first hop's terms as the query for a second hop
+    if let Some(connections) = connections.filter(|c| !c.is_empty()) {
+        explore_json["connections"] = serde_json::json!({
+            "vertices": connections.iter().map(graph_vertex_json).collect::<Vec<_>>()
+        });
+    }
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, Some(&explore_json)).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Graph explore failed: {}", describe_error_response(response).await));
+    }
+    
+    let graph_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let vertices = graph_data["vertices"].as_array().unwrap_or(&vec![]).iter()
+        .map(|vertex| GraphVertex {
+            field: vertex["field"].as_str().unwrap_or("").to_string(),
+            term: match &vertex["term"] {
+                serde_json::Value::String(term) => term.clone(),
+                other => other.to_string(),
+            },
+            weight: vertex["weight"].as_f64().unwrap_or(0.0),
+            depth: vertex["depth"].as_u64().unwrap_or(0) as u32,
+        })
+        .collect();
+    
+    let edges = graph_data["connections"].as_array().unwrap_or(&vec![]).iter()
+        .map(|edge| GraphEdge {
+            source: edge["source"].as_u64().unwrap_or(0) as usize,
+            target: edge["target"].as_u64().unwrap_or(0) as usize,
+            weight: edge["weight"].as_f64().unwrap_or(0.0),
+            doc_count: edge["doc_count"].as_u64().unwrap_or(0),
+        })
+        .collect();
+    
+    Ok(GraphExploreResult {
+        vertices,
+        edges,
+        took: graph_data["took"].as_u64().unwrap_or(0),
+        timed_out: graph_data["timed_out"].as_bool().unwrap_or(false),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowerIndexInfo {
+    pub follower_index: String,
+    pub remote_cluster: String,
+    pub leader_index: String,
+    // "active" or "paused"
+    pub status: String,
+    pub parameters: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowerIndexStats {
+    pub index: String,
+    pub remote_cluster: String,
+    pub leader_index: String,
+    pub shard_count: usize,
+    pub operations_read: u64,
+    pub operations_written: u64,
+    pub failed_read_requests: u64,
+    pub failed_write_requests: u64,
+    // Largest gap between leader and follower global checkpoints across shards
+    pub max_operations_behind: u64,
+    pub time_since_last_read_millis: u64,
+    pub fatal_exception: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoFollowPattern {
+    pub name: String,
+    pub remote_cluster: String,
+    pub leader_index_patterns: Vec<String>,
+    pub leader_index_exclusion_patterns: Vec<String>,
+    pub follow_index_pattern: Option<String>,
+    pub active: bool,
+}
+
+fn string_list(value: &serde_json::Value) -> Vec<String> {
+    value.as_array()
+        .map(|items| items.iter().filter_map(|item| item.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_default()
+}
+
+fn parse_follower_stats(index: &serde_json::Value) -> FollowerIndexStats {
+    let shards = index["shards"].as_array().cloned().unwrap_or_default();
+    let sum = |key: &str| shards.iter().map(|shard| shard[key].as_u64().unwrap_or(0)).sum::<u64>();
+    let first = shards.first().cloned().unwrap_or(serde_json::Value::Null);
+    
+    let max_operations_behind = shards.iter()
+        .map(|shard| {
+            let leader = shard["leader_global_checkpoint"].as_i64().unwrap_or(0);
+            let follower = shard["follower_global_checkpoint"].as_i64().unwrap_or(0);
+            (leader - follower).max(0) as u64
+        })
+        .max()
+        .unwrap_or(0);
+    
+    let fatal_exception = shards.iter()
+        .find_map(|shard| shard["fatal_exception"]["reason"].as_str().map(|s| s.to_string()));
+    
+    FollowerIndexStats {
+        index: index["index"].as_str().unwrap_or("").to_string(),
+        remote_cluster: first["remote_cluster"].as_str().unwrap_or("").to_string(),
+        leader_index: first["leader_index"].as_str().unwrap_or("").to_string(),
+        shard_count: shards.len(),
+        operations_read: sum("operations_read"),
+        operations_written: sum("operations_written"),
+        failed_read_requests: sum("failed_read_requests"),
+        failed_write_requests: sum("failed_write_requests"),
+        max_operations_behind,
+        time_since_last_read_millis: shards.iter().map(|shard| shard["time_since_last_read_millis"].as_u64().unwrap_or(0)).max().unwrap_or(0),
+        fatal_exception,
+    }
+}
+
+#[command]
+pub async fn follow_index(
+    window: Window,
+    follower_index: String,
+    remote_cluster: String,
+    leader_index: String,
+    parameters: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/{}/_ccr/follow", encode_segment(&follower_index));
+    
+    // Optional tuning (max_read_request_operation_count, settings, ...) goes alongside the leader
+    let mut follow_json = parameters.filter(|p| p.is_object()).unwrap_or_else(|| serde_json::json!({}));
+    follow_json["remote_cluster"] = serde_json::json!(remote_cluster);
+    follow_json["leader_index"] = serde_json::json!(leader_index);
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&follow_json)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully started following {}:{} as {}", remote_cluster, leader_index, follower_index);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to create follower index - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn list_follower_indices(window: Window, timeout_ms: Option<u64>) -> Result<Vec<FollowerIndexInfo>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_all/_ccr/info";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list follower indices: {}", describe_error_response(response).await));
+    }
+    
+    let info_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let followers = info_data["follower_indices"].as_array().unwrap_or(&vec![]).iter()
+        .map(|follower| FollowerIndexInfo {
+            follower_index: follower["follower_index"].as_str().unwrap_or("").to_string(),
+            remote_cluster: follower["remote_cluster"].as_str().unwrap_or("").to_string(),
+            leader_index: follower["leader_index"].as_str().unwrap_or("").to_string(),
+            status: follower["status"].as_str().unwrap_or("unknown").to_string(),
+            parameters: follower["parameters"].clone(),
+        })
+        .collect();
+    
+    Ok(followers)
+}
+
+#[command]
+pub async fn get_follower_stats(window: Window, index: Option<String>, timeout_ms: Option<u64>) -> Result<Vec<FollowerIndexStats>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Without an index the stats of every follower index are returned
+    let target = index.as_deref().map(encode_segment).unwrap_or_else(|| "_all".to_string());
+    let path = format!("/{}/_ccr/stats", target);
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get follower stats: {}", describe_error_response(response).await));
+    }
+    
+    let stats_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let stats = stats_data["indices"].as_array()
+        .map(|indices| indices.iter().map(parse_follower_stats).collect())
+        .unwrap_or_default();
+    
+    Ok(stats)
+}
+
+#[command]
+pub async fn pause_follow(window: Window, follower_index: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/{}/_ccr/pause_follow", encode_segment(&follower_index));
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to pause following for '{}': {}", follower_index, describe_error_response(response).await));
+    }
+    
+    println!("Successfully paused follower index: {}", follower_index);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn resume_follow(window: Window, follower_index: String, parameters: Option<serde_json::Value>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/{}/_ccr/resume_follow", encode_segment(&follower_index));
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, Some(&parameters.unwrap_or_else(|| serde_json::json!({})))).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to resume following for '{}': {}", follower_index, describe_error_response(response).await));
+    }
+    
+    println!("Successfully resumed follower index: {}", follower_index);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn unfollow_index(window: Window, follower_index: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // ES only converts a follower that is paused and closed, the index stays closed afterwards
+    let path = format!("/{}/_ccr/unfollow", encode_segment(&follower_index));
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to unfollow '{}': {}", follower_index, describe_error_response(response).await));
+    }
+    
+    println!("Successfully converted follower index to a regular index: {}", follower_index);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn list_auto_follow_patterns(window: Window, timeout_ms: Option<u64>) -> Result<Vec<AutoFollowPattern>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_ccr/auto_follow";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list auto-follow patterns: {}", describe_error_response(response).await));
+    }
+    
+    let patterns_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let patterns = patterns_data["patterns"].as_array().unwrap_or(&vec![]).iter()
+        .map(|entry| {
+            let pattern = &entry["pattern"];
+            AutoFollowPattern {
+                name: entry["name"].as_str().unwrap_or("").to_string(),
+                remote_cluster: pattern["remote_cluster"].as_str().unwrap_or("").to_string(),
+                leader_index_patterns: string_list(&pattern["leader_index_patterns"]),
+                leader_index_exclusion_patterns: string_list(&pattern["leader_index_exclusion_patterns"]),
+                follow_index_pattern: pattern["follow_index_pattern"].as_str().map(|s| s.to_string()),
+                active: pattern["active"].as_bool().unwrap_or(true),
+            }
+        })
+        .collect();
+    
+    Ok(patterns)
+}
+
+#[command]
+pub async fn put_auto_follow_pattern(
+    window: Window,
+    name: String,
+    remote_cluster: String,
+    leader_index_patterns: Vec<String>,
+    leader_index_exclusion_patterns: Option<Vec<String>>,
+    follow_index_pattern: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_ccr/auto_follow/{}", encode_segment(&name));
+    
+    let mut pattern_json = serde_json::json!({
+        "remote_cluster": remote_cluster,
+        "leader_index_patterns": leader_index_patterns
+    });
+    if let Some(exclusions) = leader_index_exclusion_patterns {
+        pattern_json["leader_index_exclusion_patterns"] = serde_json::json!(exclusions);
+    }
+    // e.g. "{{leader_index}}-copy"; without it followers get the leader's name
+    if let Some(follow_index_pattern) = follow_index_pattern {
+        pattern_json["follow_index_pattern"] = serde_json::json!(follow_index_pattern);
+    }
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&pattern_json)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully saved auto-follow pattern: {}", name);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to save auto-follow pattern - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn set_auto_follow_pattern_active(window: Window, name: String, active: bool, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let action = if active { "resume" } else { "pause" };
+    let path = format!("/_ccr/auto_follow/{}/{}", encode_segment(&name), action);
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to {} auto-follow pattern '{}': {}", action, name, describe_error_response(response).await));
+    }
+    
+    println!("Successfully {}d auto-follow pattern: {}", action, name);
+    
+    Ok(active)
+}
+
+#[command]
+pub async fn delete_auto_follow_pattern(window: Window, name: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_ccr/auto_follow/{}", encode_segment(&name));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete auto-follow pattern '{}': {} - {}", name, status, body));
+    }
+    
+    println!("Successfully deleted auto-follow pattern: {}", name);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoscalingPolicy {
+    pub name: String,
+    pub roles: Vec<String>,
+    pub deciders: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AutoscalingResources {
+    pub storage: u64,
+    pub memory: u64,
+    pub processors: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoscalingCapacity {
+    pub policy: String,
+    // Tier-wide totals and the per-node minimum, all in bytes
+    pub required_total: AutoscalingResources,
+    pub required_node: AutoscalingResources,
+    pub current_total: Option<AutoscalingResources>,
+    pub current_node: Option<AutoscalingResources>,
+    pub current_nodes: Vec<String>,
+    // Decider name -> its required capacity and reason
+    pub deciders: serde_json::Value,
+}
+
+fn parse_autoscaling_resources(resources: &serde_json::Value) -> Option<AutoscalingResources> {
+    if !resources.is_object() {
+        return None;
+    }
+    
+    Some(AutoscalingResources {
+        storage: resources["storage"].as_u64().unwrap_or(0),
+        memory: resources["memory"].as_u64().unwrap_or(0),
+        processors: resources["processors"].as_f64(),
+    })
+}
+
+#[command]
+pub async fn get_autoscaling_policy(window: Window, name: String, timeout_ms: Option<u64>) -> Result<AutoscalingPolicy, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_autoscaling/policy/{}", encode_segment(&name));
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get autoscaling policy '{}': {}", name, describe_error_response(response).await));
+    }
+    
+    let policy_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    Ok(AutoscalingPolicy {
+        name,
+        roles: string_list(&policy_data["roles"]),
+        deciders: policy_data["deciders"].clone(),
+    })
+}
+
+#[command]
+pub async fn put_autoscaling_policy(window: Window, name: String, roles: Vec<String>, deciders: Option<serde_json::Value>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_autoscaling/policy/{}", encode_segment(&name));
+    
+    // Without deciders ES enables the defaults for the given roles
+    let mut policy_json = serde_json::json!({ "roles": roles });
+    if let Some(deciders) = deciders {
+        policy_json["deciders"] = deciders;
+    }
+    
+    // Send the request
+    let response = transport.send(Method::PUT, &path, Some(&policy_json)).await?;
+    
+    if response.status().is_success() {
+        println!("Successfully saved autoscaling policy: {}", name);
+        Ok(true)
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string()
+        };
+        
+        Err(format!("Failed to save autoscaling policy - Status: {}, Response: {}", status, error_text))
+    }
+}
+
+#[command]
+pub async fn delete_autoscaling_policy(window: Window, name: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_autoscaling/policy/{}", encode_segment(&name));
+    
+    // Send the DELETE request
+    let response = transport.send(Method::DELETE, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete autoscaling policy '{}': {} - {}", name, status, body));
+    }
+    
+    println!("Successfully deleted autoscaling policy: {}", name);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn get_autoscaling_capacity(window: Window, timeout_ms: Option<u64>) -> Result<Vec<AutoscalingCapacity>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_autoscaling/capacity";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get autoscaling capacity: {}", describe_error_response(response).await));
+    }
+    
+    let capacity_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    // The capacity response is the only way to list every policy, keyed by policy name
+    let capacities = capacity_data["policies"].as_object()
+        .map(|policies| {
+            policies.iter()
+                .map(|(policy, capacity)| {
+                    let required = &capacity["required_capacity"];
+                    let current = &capacity["current_capacity"];
+                    AutoscalingCapacity {
+                        policy: policy.clone(),
+                        required_total: parse_autoscaling_resources(&required["total"]).unwrap_or_default(),
+                        required_node: parse_autoscaling_resources(&required["node"]).unwrap_or_default(),
+                        current_total: parse_autoscaling_resources(&current["total"]),
+                        current_node: parse_autoscaling_resources(&current["node"]),
+                        current_nodes: capacity["current_nodes"].as_array().unwrap_or(&vec![]).iter()
+                            .filter_map(|node| node["name"].as_str().map(|s| s.to_string()))
+                            .collect(),
+                        deciders: capacity["deciders"].clone(),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    
+    Ok(capacities)
+}
+
+#[command]
+pub async fn swap_alias(window: Window, alias: String, from_index: String, to_index: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    if from_index == to_index {
+        return Err("Source and target index must be different".to_string());
+    }
+    
+    // Check where the alias points right now; the response is keyed by concrete index name
+    let path = format!("/_alias/{}", encode_segment(&alias));
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("Alias '{}' does not exist", alias));
+    }
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get alias '{}': {}", alias, describe_error_response(response).await));
+    }
+    
+    let alias_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let current_indices: Vec<String> = alias_data.as_object()
+        .map(|indices| indices.keys().cloned().collect())
+        .unwrap_or_default();
+    
+    let alias_config = alias_data[&from_index]["aliases"][&alias].clone();
+    if alias_config.is_null() {
+        return Err(format!("Alias '{}' does not point at '{}' (currently: {})", alias, from_index, current_indices.join(", ")));
+    }
+    
+    // Carry filter, routing and write index flag over to the new index
+    let mut add_action = serde_json::json!({ "index": to_index, "alias": alias });
+    if let (Some(add), Some(config)) = (add_action.as_object_mut(), alias_config.as_object()) {
+        for (key, value) in config {
+            add.insert(key.clone(), value.clone());
+        }
+    }
+    
+    // Both actions go in one request so searches never see the alias missing or doubled. If the
+    // alias was moved off from_index since the check above, must_exist fails the whole swap
+    // instead of silently adding a second target.
+    let body = serde_json::json!({
+        "actions": [
+            { "remove": { "index": from_index, "alias": alias, "must_exist": true } },
+            { "add": add_action }
+        ]
+    });
+    
+    let response = transport.send(Method::POST, "/_aliases", Some(&body)).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to swap alias '{}' from '{}' to '{}': {} - {}", alias, from_index, to_index, status, body));
+    }
+    
+    println!("Successfully swapped alias {} from {} to {}", alias, from_index, to_index);
+    
+    Ok(true)
+}
+
+// Types a runtime field can have, see the runtime fields docs
+const RUNTIME_FIELD_TYPES: &[&str] = &["boolean", "composite", "date", "double", "geo_point", "ip", "keyword", "long", "lookup"];
+
+#[command]
+pub async fn put_runtime_field(window: Window, index: String, name: String, field_type: String, script: Option<String>, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    if name.trim().is_empty() {
+        return Err("A runtime field name is required".to_string());
+    }
+    if !RUNTIME_FIELD_TYPES.contains(&field_type.as_str()) {
+        return Err(format!("Unsupported runtime field type '{}', expected one of: {}", field_type, RUNTIME_FIELD_TYPES.join(", ")));
+    }
+    
+    // Without a script the field reads the value of the same name from _source
+    let mut definition = serde_json::json!({ "type": field_type });
+    if let Some(script) = script.filter(|s| !s.trim().is_empty()) {
+        definition["script"] = serde_json::json!({ "source": script });
+    }
+    
+    let path = format!("/{}/_mapping", encode_segment(&index));
+    
+    let response = transport.send(Method::PUT, &path, Some(&serde_json::json!({ "runtime": { name.clone(): definition } }))).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to put runtime field '{}': {} - {}", name, status, body));
+    }
+    
+    println!("Successfully put runtime field {} on index: {}", name, index);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn delete_runtime_field(window: Window, index: String, name: String, timeout_ms: Option<u64>) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/{}/_mapping", encode_segment(&index));
+    
+    // Setting a runtime field to null is how the mapping API removes it
+    let response = transport.send(Method::PUT, &path, Some(&serde_json::json!({ "runtime": { name.clone(): null } }))).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to delete runtime field '{}': {} - {}", name, status, body));
+    }
+    
+    println!("Successfully deleted runtime field {} from index: {}", name, index);
+    
+    Ok(true)
+}
+
+// Runs a search with runtime fields defined just for this request, so computed fields can be
+// tried out without touching the mapping. A request_id makes it cancellable like execute_elasticsearch_query.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn search_with_runtime_fields(window: Window, index: String, query: String, runtime_mappings: serde_json::Value, options: Option<SearchOptions>, request_id: Option<String>, timeout_ms: Option<u64>) -> Result<QueryResult, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let runtime_fields = runtime_mappings.as_object().ok_or("runtime_mappings must be an object of field definitions")?;
+    
+    let (path, mut query_json) = build_search_request(transport.connection(), &index, &query, &options.unwrap_or_default())?;
+    let body = query_json.as_object_mut().ok_or("The query must be a JSON object")?;
+    
+    // Runtime fields aren't part of _source, so ask for them explicitly to see their values in the hits
+    let mut fields = body.get("fields").and_then(|f| f.as_array()).cloned().unwrap_or_default();
+    for name in runtime_fields.keys() {
+        let requested = serde_json::json!(name);
+        if !fields.contains(&requested) {
+            fields.push(requested);
+        }
+    }
+    body.insert("fields".to_string(), serde_json::json!(fields));
+    
+    // Definitions passed in replace any of the same name already in the query
+    let mut merged = body.get("runtime_mappings").and_then(|m| m.as_object()).cloned().unwrap_or_default();
+    merged.extend(runtime_fields.clone());
+    body.insert("runtime_mappings".to_string(), serde_json::Value::Object(merged));
+    
+    let response_body: serde_json::Value = cancellable(request_id, async {
+        // Send the request
+        let response = transport.send(Method::POST, &path, Some(&query_json)).await?;
+        
+        if !response.status().is_success() {
+            return Err(format!("Failed to execute query with runtime fields: {}", describe_error_response(response).await));
+        }
+        
+        response.json().await.map_err(|e| e.to_string())
+    }).await?;
+    
+    parse_query_result(&response_body)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PainlessExecution {
+    // Whatever the script returned, only set when it ran
+    pub result: Option<serde_json::Value>,
+    pub error: Option<PainlessError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PainlessError {
+    pub message: String,
+    // The lines around the failure with a ^ under the offending token, as Painless reports them
+    pub script_stack: Vec<String>,
+    // Character offsets into the script
+    pub position_start: Option<u64>,
+    pub position_end: Option<u64>,
+}
+
+// Contexts other than painless_test run against a document of an index, like a filter,
+// score or runtime field script would
+const PAINLESS_DOCUMENT_CONTEXTS: &[&str] = &[
+    "filter", "score", "boolean_field", "date_field", "double_field", "geo_point_field",
+    "ip_field", "keyword_field", "long_field", "composite_field",
+];
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn execute_painless(
+    window: Window,
+    script: String,
+    context: Option<String>,
+    sample_doc: Option<serde_json::Value>,
+    index: Option<String>,
+    params: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<PainlessExecution, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let context = context.unwrap_or_else(|| "painless_test".to_string());
+    let mut params = match params {
+        Some(serde_json::Value::Object(params)) => params,
+        Some(serde_json::Value::Null) | None => serde_json::Map::new(),
+        Some(_) => return Err("Script params must be an object".to_string()),
+    };
+    
+    let mut execute_json = serde_json::json!({ "context": context });
+    
+    if PAINLESS_DOCUMENT_CONTEXTS.contains(&context.as_str()) {
+        let index = index.filter(|i| !i.trim().is_empty()).ok_or(format!("The {} context needs an index to resolve field mappings", context))?;
+        let document = sample_doc.ok_or(format!("The {} context needs a sample document", context))?;
+        execute_json["context_setup"] = serde_json::json!({ "index": index, "document": document });
+    } else if context == "painless_test" {
+        // painless_test has no document access, so the sample is handed over as params.doc
+        if let Some(document) = sample_doc {
+            params.entry("doc").or_insert(document);
+        }
+    } else {
+        return Err(format!("Unsupported Painless context '{}'", context));
+    }
+    
+    execute_json["script"] = serde_json::json!({ "source": script, "params": params });
+    
+    let path = "/_scripts/painless/_execute";
+    
+    // Send the request
+    let response = transport.send(Method::POST, path, Some(&execute_json)).await?;
+    
+    let status = response.status();
+    let body: serde_json::Value = match response.text().await {
+        Ok(text) => serde_json::from_str(&text).map_err(|_| format!("Failed to execute script: {} - {}", status, text))?,
+        Err(e) => return Err(e.to_string()),
+    };
+    
+    if status.is_success() {
+        return Ok(PainlessExecution { result: Some(body["result"].clone()), error: None });
+    }
+    
+    // Compile and runtime errors are results the editor should show, not command failures
+    let error = &body["error"];
+    let script_error = match error["type"].as_str() {
+        Some("script_exception") => error,
+        _ => error["caused_by"].as_object().map(|_| &error["caused_by"]).filter(|cause| cause["type"] == "script_exception").unwrap_or(&serde_json::Value::Null),
+    };
+    if script_error.is_null() {
+        return Err(format!("Failed to execute script: {} - {}", status, describe_error_body(&body.to_string())));
+    }
+    
+    let message = match script_error["caused_by"]["reason"].as_str() {
+        Some(cause) => format!("{}: {}", script_error["reason"].as_str().unwrap_or("script error"), cause),
+        None => script_error["reason"].as_str().unwrap_or("script error").to_string(),
+    };
+    
+    Ok(PainlessExecution {
+        result: None,
+        error: Some(PainlessError {
+            message,
+            script_stack: script_error["script_stack"].as_array().unwrap_or(&vec![]).iter()
+                .filter_map(|line| line.as_str().map(|s| s.to_string()))
+                .collect(),
+            position_start: script_error["position"]["start"].as_u64(),
+            position_end: script_error["position"]["end"].as_u64(),
+        }),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexSimulation {
+    // The highest priority template whose patterns match, None when no template applies
+    pub template: Option<String>,
+    pub composed_of: Vec<String>,
+    pub settings: serde_json::Value,
+    pub mappings: serde_json::Value,
+    pub aliases: serde_json::Value,
+    // Lower priority templates that match the name too but lose to the one above
+    pub overlapping: Vec<OverlappingTemplate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlappingTemplate {
+    pub name: String,
+    pub index_patterns: Vec<String>,
+}
+
+// Index patterns only support `*`
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == name;
+    }
+    
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !name.starts_with(first) || !name[first.len()..].ends_with(last) {
+        return false;
+    }
+    
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[command]
+pub async fn simulate_index_name(window: Window, index_name: String, timeout_ms: Option<u64>) -> Result<IndexSimulation, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_index_template/_simulate_index/{}", encode_segment(&index_name));
+    
+    // Send the request
+    let response = transport.send(Method::POST, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to simulate index '{}': {}", index_name, describe_error_response(response).await));
+    }
+    
+    let simulation: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    // The simulation doesn't name the winning template, so work it out from the template list
+    let templates = transport.get_json_cached("/_index_template").await?;
+    
+    let winner = templates["index_templates"].as_array().unwrap_or(&vec![]).iter()
+        .filter(|template| {
+            template["index_template"]["index_patterns"].as_array().unwrap_or(&vec![]).iter()
+                .filter_map(|pattern| pattern.as_str())
+                .any(|pattern| wildcard_match(pattern, &index_name))
+        })
+        .max_by_key(|template| template["index_template"]["priority"].as_i64().unwrap_or(0))
+        .cloned();
+    
+    let template = &simulation["template"];
+    
+    Ok(IndexSimulation {
+        template: winner.as_ref().and_then(|w| w["name"].as_str().map(|s| s.to_string())),
+        composed_of: winner.as_ref().map(|w| string_list(&w["index_template"]["composed_of"])).unwrap_or_default(),
+        settings: template["settings"].clone(),
+        mappings: template["mappings"].clone(),
+        aliases: template["aliases"].clone(),
+        overlapping: simulation["overlapping"].as_array().unwrap_or(&vec![]).iter()
+            .map(|overlap| OverlappingTemplate {
+                name: overlap["name"].as_str().unwrap_or("").to_string(),
+                index_patterns: string_list(&overlap["index_patterns"]),
+            })
+            .collect(),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardRecovery {
+    pub index: String,
+    pub shard: u32,
+    // "peer", "snapshot", "existing_store", "empty_store" or "local_shards"
+    pub recovery_type: String,
+    pub stage: String,
+    pub source_node: Option<String>,
+    pub target_node: String,
+    pub time_ms: u64,
+    pub bytes_recovered: u64,
+    pub bytes_total: u64,
+    pub bytes_percent: f64,
+    pub translog_ops_percent: f64,
+    // Average since the recovery started
+    pub bytes_per_second: f64,
+}
+
+#[command]
+pub async fn get_cluster_recovery(window: Window, active_only: Option<bool>, timeout_ms: Option<u64>) -> Result<Vec<ShardRecovery>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    // Raw bytes and milliseconds so throughput can be worked out here
+    let path = format!(
+        "/_cat/recovery?format=json&bytes=b&time=ms&active_only={}&h=index,shard,time,type,stage,source_node,target_node,bytes_recovered,bytes_total,bytes_percent,translog_ops_percent",
+        active_only.unwrap_or(true)
+    );
+    
+    // Send the request
+    let response = transport.send(Method::GET, &path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get shard recoveries: {}", describe_error_response(response).await));
+    }
+    
+    // Source node is "n/a" or null for recoveries that don't copy from another node
+    let recovery_data: Vec<HashMap<String, Option<String>>> = response.json().await.map_err(|e| e.to_string())?;
+    
+    let recoveries = recovery_data.into_iter().map(|row| {
+        let field = |key: &str| row.get(key).cloned().flatten();
+        let percent = |key: &str| field(key).and_then(|v| v.trim_end_matches('%').parse().ok()).unwrap_or(0.0);
+        
+        let time_ms: u64 = field("time").and_then(|v| v.parse().ok()).unwrap_or(0);
+        let bytes_recovered: u64 = field("bytes_recovered").and_then(|v| v.parse().ok()).unwrap_or(0);
+        
+        ShardRecovery {
+            index: field("index").unwrap_or_default(),
+            shard: field("shard").and_then(|v| v.parse().ok()).unwrap_or(0),
+            recovery_type: field("type").unwrap_or_default(),
+            stage: field("stage").unwrap_or_default(),
+            source_node: field("source_node").filter(|node| node != "n/a"),
+            target_node: field("target_node").unwrap_or_default(),
+            time_ms,
+            bytes_recovered,
+            bytes_total: field("bytes_total").and_then(|v| v.parse().ok()).unwrap_or(0),
+            bytes_percent: percent("bytes_percent"),
+            translog_ops_percent: percent("translog_ops_percent"),
+            bytes_per_second: if time_ms > 0 { bytes_recovered as f64 * 1000.0 / time_ms as f64 } else { 0.0 },
+        }
+    }).collect();
+    
+    Ok(recoveries)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DanglingIndex {
+    pub index_name: String,
+    pub index_uuid: String,
+    pub creation_date_millis: Option<i64>,
+    // Nodes that still hold data for the index
+    pub node_ids: Vec<String>,
+}
+
+#[command]
+pub async fn list_dangling_indices(window: Window, timeout_ms: Option<u64>) -> Result<Vec<DanglingIndex>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_dangling";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to list dangling indices: {}", describe_error_response(response).await));
+    }
+    
+    let dangling_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let indices = dangling_data["dangling_indices"].as_array().unwrap_or(&vec![]).iter()
+        .map(|index| DanglingIndex {
+            index_name: index["index_name"].as_str().unwrap_or("").to_string(),
+            index_uuid: index["index_uuid"].as_str().unwrap_or("").to_string(),
+            creation_date_millis: index["creation_date_millis"].as_i64(),
+            node_ids: string_list(&index["node_ids"]),
+        })
+        .collect();
+    
+    Ok(indices)
+}
+
+// Importing and deleting both take the index UUID, names aren't unique among dangling indices.
+// Elasticsearch can't tell whether the data is complete, so the caller has to accept data loss.
+async fn dangling_index_action(window: &Window, method: Method, index_uuid: &str, accept_data_loss: bool, timeout_ms: Option<u64>) -> Result<(), String> {
+    if !accept_data_loss {
+        return Err("Dangling index operations require accepting possible data loss".to_string());
+    }
+    
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = format!("/_dangling/{}?accept_data_loss=true", encode_segment(index_uuid));
+    let response = transport.send(method, &path, None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("{} - {}", status, body));
+    }
+    
+    Ok(())
+}
+
+#[command]
+pub async fn import_dangling_index(window: Window, index_uuid: String, accept_data_loss: bool, timeout_ms: Option<u64>) -> Result<bool, String> {
+    dangling_index_action(&window, Method::POST, &index_uuid, accept_data_loss, timeout_ms).await
+        .map_err(|e| format!("Failed to import dangling index '{}': {}", index_uuid, e))?;
+    
+    println!("Successfully imported dangling index: {}", index_uuid);
+    
+    Ok(true)
+}
+
+#[command]
+pub async fn delete_dangling_index(window: Window, index_uuid: String, accept_data_loss: bool, timeout_ms: Option<u64>) -> Result<bool, String> {
+    dangling_index_action(&window, Method::DELETE, &index_uuid, accept_data_loss, timeout_ms).await
+        .map_err(|e| format!("Failed to delete dangling index '{}': {}", index_uuid, e))?;
+    
+    println!("Successfully deleted dangling index: {}", index_uuid);
+    
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterFeature {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureResetResult {
+    pub feature_name: String,
+    // "SUCCESS" or "FAILURE"
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[command]
+pub async fn get_cluster_features(window: Window, timeout_ms: Option<u64>) -> Result<Vec<ClusterFeature>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_features";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get cluster features: {}", describe_error_response(response).await));
+    }
+    
+    let features_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    let features = features_data["features"].as_array().unwrap_or(&vec![]).iter()
+        .map(|feature| ClusterFeature {
+            name: feature["name"].as_str().unwrap_or("").to_string(),
+            description: feature["description"].as_str().unwrap_or("").to_string(),
+        })
+        .collect();
+    
+    Ok(features)
+}
+
+// Wipes the system indices and state of every feature, meant for resetting test clusters only.
+// Needs a token from request_confirmation_token("reset_features", "cluster").
+#[command]
+pub async fn reset_features(window: Window, confirmation_token: String, timeout_ms: Option<u64>) -> Result<Vec<FeatureResetResult>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    consume_confirmation_token(transport.connection_id(), "reset_features", "cluster", &confirmation_token)?;
+    
+    // Send the POST request
+    let response = transport.send(Method::POST, "/_features/_reset", None).await?;
+    
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Could not read error response".to_string()
+        };
+        
+        return Err(format!("Failed to reset features: {} - {}", status, body));
+    }
+    
+    let reset_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    println!("Successfully reset features on cluster: {}", transport.connection().name);
+    
+    let results = reset_data["features"].as_array().unwrap_or(&vec![]).iter()
+        .map(|feature| FeatureResetResult {
+            feature_name: feature["feature_name"].as_str().unwrap_or("").to_string(),
+            status: feature["status"].as_str().unwrap_or("").to_string(),
+            error: feature["exception"]["reason"].as_str().map(|s| s.to_string()),
+        })
+        .collect();
+    
+    Ok(results)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestActionUsage {
+    pub action: String,
+    pub total: u64,
+    // Calls per node id
+    pub per_node: BTreeMap<String, u64>,
+}
+
+#[command]
+pub async fn get_rest_action_usage(window: Window, timeout_ms: Option<u64>) -> Result<Vec<RestActionUsage>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let path = "/_nodes/usage";
+    
+    // Send the request
+    let response = transport.send(Method::GET, path, None).await?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to get nodes usage: {}", describe_error_response(response).await));
+    }
+    
+    let usage_data: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    
+    // Counters are per node and reset when a node restarts
+    let mut usage: BTreeMap<String, RestActionUsage> = BTreeMap::new();
+    for (node_id, node) in usage_data["nodes"].as_object().into_iter().flatten() {
+        for (action, count) in node["rest_actions"].as_object().into_iter().flatten() {
+            let count = count.as_u64().unwrap_or(0);
+            let entry = usage.entry(action.clone()).or_insert_with(|| RestActionUsage {
+                action: action.clone(),
+                total: 0,
+                per_node: BTreeMap::new(),
+            });
+            entry.total += count;
+            entry.per_node.insert(node_id.clone(), count);
+        }
+    }
+    
+    // Most used first
+    let mut actions: Vec<RestActionUsage> = usage.into_values().collect();
+    actions.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.action.cmp(&b.action)));
+    
+    Ok(actions)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateUsage {
+    pub name: String,
+    pub index_patterns: Vec<String>,
+    pub priority: i64,
+    // Regular indices this template wins for
+    pub indices: Vec<String>,
+    // Data streams that report this template as theirs
+    pub data_streams: Vec<String>,
+    pub unused: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateUsageReport {
+    pub templates: Vec<TemplateUsage>,
+    // Non-system indices no template matches
+    pub indices_without_template: Vec<String>,
+}
+
+// Which template an index was created from isn't recorded, so regular indices are matched
+// against the current patterns the way Elasticsearch picks one: highest priority wins
+#[command]
+pub async fn get_template_usage(window: Window, timeout_ms: Option<u64>) -> Result<TemplateUsageReport, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    
+    let (templates_data, indices_data, data_streams_data) = tokio::try_join!(
+        transport.get_json_cached("/_index_template"),
+        transport.get_json("/_cat/indices?format=json&h=index&expand_wildcards=open,closed"),
+        transport.get_json("/_data_stream?expand_wildcards=all"),
+    )?;
+    
+    let mut templates: Vec<TemplateUsage> = templates_data["index_templates"].as_array().unwrap_or(&vec![]).iter()
+        .map(|template| TemplateUsage {
+            name: template["name"].as_str().unwrap_or("").to_string(),
+            index_patterns: string_list(&template["index_template"]["index_patterns"]),
+            priority: template["index_template"]["priority"].as_i64().unwrap_or(0),
+            indices: Vec::new(),
+            data_streams: Vec::new(),
+            unused: false,
+        })
+        .collect();
+    
+    // Backing indices belong to their data stream, which names its template directly
+    let mut backing_indices = BTreeSet::new();
+    for data_stream in data_streams_data["data_streams"].as_array().unwrap_or(&vec![]) {
+        for index in data_stream["indices"].as_array().unwrap_or(&vec![]) {
+            if let Some(name) = index["index_name"].as_str() {
+                backing_indices.insert(name.to_string());
+            }
+        }
+        
+        let template_name = data_stream["template"].as_str().unwrap_or("");
+        if let Some(template) = templates.iter_mut().find(|t| t.name == template_name) {
+            template.data_streams.push(data_stream["name"].as_str().unwrap_or("").to_string());
+        }
+    }
+    
+    let mut indices_without_template = Vec::new();
+    let index_names = indices_data.as_array().unwrap_or(&vec![]).iter()
+        .filter_map(|row| row["index"].as_str().map(|s| s.to_string()))
+        .filter(|name| !backing_indices.contains(name))
+        .collect::<BTreeSet<_>>();
+    
+    for index in index_names {
+        let winner = templates.iter_mut()
+            .filter(|template| template.index_patterns.iter().any(|pattern| wildcard_match(pattern, &index)))
+            .max_by_key(|template| template.priority);
+        
+        match winner {
+            Some(template) => template.indices.push(index),
+            None if !index.starts_with('.') => indices_without_template.push(index),
+            None => {}
+        }
+    }
+    
+    for template in &mut templates {
+        template.unused = template.indices.is_empty() && template.data_streams.is_empty();
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    Ok(TemplateUsageReport {
+        templates,
+        indices_without_template,
+    })
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_patterns() {
+        assert!(wildcard_match("logs", "logs"));
+        assert!(!wildcard_match("logs", "logs-1"));
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("logs-*", "logs-2024.01"));
+        assert!(wildcard_match("logs-*", "logs-"));
+        assert!(!wildcard_match("logs-*", "metrics-1"));
+        assert!(wildcard_match("*-prod", "logs-prod"));
+        assert!(wildcard_match("logs-*-prod-*", "logs-app-prod-1"));
+        assert!(!wildcard_match("logs-*-prod-*", "logs-app-dev-1"));
+        // The prefix and suffix can't share characters
+        assert!(!wildcard_match("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn error_bodies() {
+        assert_eq!(describe_error_body("Bad Gateway"), "Bad Gateway");
+        assert_eq!(describe_error_body(r#"{"error":"Incorrect HTTP method"}"#), "Incorrect HTTP method");
+        assert_eq!(
+            describe_error_body(r#"{"error":{"type":"index_not_found_exception","reason":"no such index [x]",
+                "root_cause":[{"type":"index_not_found_exception","reason":"no such index [x]"}]}}"#),
+            "index_not_found_exception: no such index [x]"
+        );
+        assert_eq!(
+            describe_error_body(r#"{"error":{"type":"search_phase_execution_exception","reason":"all shards failed",
+                "root_cause":[{"type":"query_shard_exception","reason":"failed to create query"}]}}"#),
+            "search_phase_execution_exception: all shards failed (root cause: query_shard_exception: failed to create query)"
+        );
+        assert_eq!(
+            describe_error_body(r#"{"error":{"type":"mapper_parsing_exception","reason":"failed to parse",
+                "caused_by":{"type":"illegal_argument_exception","reason":"bad value"}}}"#),
+            "mapper_parsing_exception: failed to parse (caused by: illegal_argument_exception: bad value)"
+        );
+        assert_eq!(describe_error_body(r#"{"acknowledged":false}"#), r#"{"acknowledged":false}"#);
+    }
+}
@@ -0,0 +1,1673 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/export.rs
+
+use crate::elasticsearch::{
+    connection_index, current_connection, describe_error_body, describe_error_response,
+    ElasticsearchConnection,
+};
+use crate::search_contexts::{
+    replace_search_context, track_search_context, untrack_search_context,
+};
+use crate::storage::{
+    app_settings, load_export_watermark, save_export_watermark, ExportWatermark, TaskCheckpoint,
+};
+use crate::tasks::TaskHandle;
+use crate::transport::{encode_query, encode_segment, Transport};
+use reqwest::{Client as ReqwestClient, Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Window};
+
+const SCROLL_KEEP_ALIVE: &str = "2m";
+// Long enough for a checkpointed export to be resumed after the app restarts
+const EXPORT_PIT_KEEP_ALIVE: &str = "1h";
+const SCROLL_PAGE_SIZE: u64 = 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CsvExportOptions {
+    pub delimiter: Option<String>,
+    // Joins nested object keys into column names, "." by default
+    pub path_separator: Option<String>,
+    pub include_id: Option<bool>,
+    pub compress: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportProgress {
+    pub file_path: String,
+    pub index: String,
+    pub docs_written: u64,
+    pub total_docs: u64,
+    pub finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub file_path: String,
+    pub index: String,
+    pub docs_written: u64,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalExportSummary {
+    pub file_path: String,
+    pub index: String,
+    pub docs_written: u64,
+    pub since_field: String,
+    // None on the first export of an index, which exports everything
+    pub previous_watermark: Option<serde_json::Value>,
+    // Unchanged when nothing newer was found
+    pub watermark: Option<serde_json::Value>,
+}
+
+// Where a checkpointed NDJSON export got to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportPosition {
+    // The point in time and the sort values of the last hit written; the next page starts after it
+    pit_id: String,
+    search_after: Vec<serde_json::Value>,
+    docs_written: u64,
+    total_docs: u64,
+    // Length of the file after the last complete page
+    bytes_written: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NdjsonExportParams {
+    index: String,
+    query: String,
+    file_path: String,
+    include_metadata: Option<bool>,
+}
+
+// An export destination, gzip-compressed on request
+pub(crate) enum OutputFile {
+    Plain(std::io::BufWriter<std::fs::File>),
+    Gzip(flate2::write::GzEncoder<std::io::BufWriter<std::fs::File>>),
+}
+
+impl OutputFile {
+    // Appends .gz to compressed outputs so the file name says what it holds
+    pub(crate) fn create(file_path: &str, compress: bool) -> Result<(Self, String), String> {
+        let file_path = if compress && !file_path.ends_with(".gz") {
+            format!("{}.gz", file_path)
+        } else {
+            file_path.to_string()
+        };
+
+        let file = std::fs::File::create(&file_path)
+            .map_err(|e| format!("Unable to create '{}': {}", file_path, e))?;
+        let writer = std::io::BufWriter::new(file);
+
+        let output = if compress {
+            OutputFile::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            ))
+        } else {
+            OutputFile::Plain(writer)
+        };
+
+        Ok((output, file_path))
+    }
+
+    // Reopens an uncompressed export to continue it, dropping anything written after `len`
+    pub(crate) fn reopen(file_path: &str, len: u64) -> Result<Self, String> {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(file_path)
+            .map_err(|e| format!("Unable to open '{}': {}", file_path, e))?;
+        let current = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if current < len {
+            return Err(format!(
+                "'{}' is shorter than its checkpoint, start the export again",
+                file_path
+            ));
+        }
+
+        file.set_len(len).map_err(|e| e.to_string())?;
+        std::io::Seek::seek(&mut file, std::io::SeekFrom::End(0)).map_err(|e| e.to_string())?;
+
+        Ok(OutputFile::Plain(std::io::BufWriter::new(file)))
+    }
+
+    // Writes the gzip trailer; without it the file is truncated as far as readers are concerned
+    pub(crate) fn finish(self) -> Result<(), String> {
+        let mut writer = match self {
+            OutputFile::Plain(writer) => writer,
+            OutputFile::Gzip(encoder) => encoder.finish().map_err(|e| e.to_string())?,
+        };
+        std::io::Write::flush(&mut writer).map_err(|e| e.to_string())
+    }
+}
+
+impl std::io::Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            OutputFile::Plain(writer) => writer.write(buf),
+            OutputFile::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            OutputFile::Plain(writer) => writer.flush(),
+            OutputFile::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+// Relative paths land in the configured export directory, when there is one
+pub(crate) fn resolve_export_path(path: String) -> String {
+    match app_settings().export_directory {
+        Some(dir) if std::path::Path::new(&path).is_relative() => std::path::Path::new(&dir)
+            .join(&path)
+            .to_string_lossy()
+            .into_owned(),
+        _ => path,
+    }
+}
+
+// Walks every matching document page by page with the scroll API
+pub(crate) struct ScrollCursor {
+    transport: Transport,
+    scroll_id: Option<String>,
+    first_page: Option<Vec<serde_json::Value>>,
+    pub total: u64,
+}
+
+impl ScrollCursor {
+    pub(crate) async fn open(
+        client: ReqwestClient,
+        conn: ElasticsearchConnection,
+        index: &str,
+        query: &str,
+        timeout_ms: Option<u64>,
+    ) -> Result<Self, String> {
+        let mut body: serde_json::Value = if query.trim().is_empty() {
+            serde_json::json!({ "query": { "match_all": {} } })
+        } else {
+            serde_json::from_str(query).map_err(|e| format!("Invalid query JSON: {}", e))?
+        };
+
+        // Paging is driven by the scroll, so any from/size in the query would only get in the way
+        if let Some(body) = body.as_object_mut() {
+            body.remove("from");
+            body.insert("size".to_string(), serde_json::json!(SCROLL_PAGE_SIZE));
+            body.insert("track_total_hits".to_string(), serde_json::json!(true));
+        }
+
+        let transport = Transport::new(client, conn).with_timeout(timeout_ms);
+        let path = format!(
+            "/{}/_search?scroll={}",
+            encode_segment(index),
+            SCROLL_KEEP_ALIVE
+        );
+        let response = transport.send(Method::POST, &path, Some(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = match response.text().await {
+                Ok(text) => describe_error_body(&text),
+                Err(_) => "Unable to read error response".to_string(),
+            };
+
+            return Err(format!(
+                "Failed to start export query - Status: {}, Response: {}",
+                status, error_text
+            ));
+        }
+
+        let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+
+        let total = if result["hits"]["total"].is_object() {
+            result["hits"]["total"]["value"].as_u64().unwrap_or(0)
+        } else {
+            result["hits"]["total"].as_u64().unwrap_or(0)
+        };
+
+        let scroll_id = result["_scroll_id"].as_str().map(|s| s.to_string());
+        if let Some(scroll_id) = &scroll_id {
+            track_search_context(transport.connection_id(), "scroll", scroll_id, index);
+        }
+
+        Ok(ScrollCursor {
+            transport,
+            scroll_id,
+            first_page: Some(
+                result["hits"]["hits"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            total,
+        })
+    }
+
+    // Returns the next page of hits, or an empty page once the scroll is exhausted
+    pub(crate) async fn next_page(&mut self) -> Result<Vec<serde_json::Value>, String> {
+        if let Some(page) = self.first_page.take() {
+            return Ok(page);
+        }
+
+        let scroll_id = match &self.scroll_id {
+            Some(scroll_id) => scroll_id.clone(),
+            None => return Ok(vec![]),
+        };
+
+        let body = serde_json::json!({ "scroll": SCROLL_KEEP_ALIVE, "scroll_id": scroll_id });
+        let response = self
+            .transport
+            .send(Method::POST, "/_search/scroll", Some(&body))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Failed to continue export scroll: {}",
+                describe_error_response(response).await
+            ));
+        }
+
+        let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        match result["_scroll_id"].as_str() {
+            Some(new_id) if new_id != scroll_id => {
+                replace_search_context(self.transport.connection_id(), &scroll_id, new_id)
+            }
+            Some(_) => {}
+            None => untrack_search_context(self.transport.connection_id(), &scroll_id),
+        }
+        self.scroll_id = result["_scroll_id"].as_str().map(|s| s.to_string());
+
+        Ok(result["hits"]["hits"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    // Frees the search context on the cluster instead of waiting for it to expire
+    pub(crate) async fn close(self) {
+        if let Some(scroll_id) = self.scroll_id {
+            untrack_search_context(self.transport.connection_id(), &scroll_id);
+            let body = serde_json::json!({ "scroll_id": scroll_id });
+            let _ = self
+                .transport
+                .send(Method::DELETE, "/_search/scroll", Some(&body))
+                .await;
+        }
+    }
+}
+
+// Walks every matching document with search_after over a point in time. Its position is just
+// the PIT id and the last hit's sort values, so unlike a scroll it can be checkpointed. The PIT
+// isn't tracked as a search context, a disconnect leaves it open for a later resume.
+pub(crate) struct PitCursor {
+    transport: Transport,
+    pit_id: String,
+    body: serde_json::Value,
+    search_after: Option<Vec<serde_json::Value>>,
+    first_page: Option<Vec<serde_json::Value>>,
+    pub total: u64,
+}
+
+impl PitCursor {
+    pub(crate) async fn open(
+        transport: Transport,
+        index: &str,
+        query: &str,
+    ) -> Result<Self, String> {
+        let opened = transport
+            .json(
+                Method::POST,
+                &format!(
+                    "/{}/_pit?keep_alive={}",
+                    encode_segment(index),
+                    EXPORT_PIT_KEEP_ALIVE
+                ),
+                None,
+            )
+            .await
+            .map_err(|e| format!("Failed to open point in time for export - {}", e))?;
+        let pit_id = opened["id"]
+            .as_str()
+            .ok_or("Elasticsearch did not return a point in time id")?
+            .to_string();
+
+        let mut cursor = PitCursor {
+            transport,
+            pit_id,
+            body: Self::page_body(query)?,
+            search_after: None,
+            first_page: None,
+            total: 0,
+        };
+
+        let result = match cursor.search(true).await {
+            Ok(result) => result,
+            Err(e) => {
+                cursor.close().await;
+                return Err(format!("Failed to start export query - {}", e));
+            }
+        };
+        cursor.total = if result["hits"]["total"].is_object() {
+            result["hits"]["total"]["value"].as_u64().unwrap_or(0)
+        } else {
+            result["hits"]["total"].as_u64().unwrap_or(0)
+        };
+        cursor.first_page = Some(cursor.advance(&result));
+
+        Ok(cursor)
+    }
+
+    // Continues after the last hit an interrupted export wrote
+    pub(crate) fn resume(
+        transport: Transport,
+        query: &str,
+        pit_id: &str,
+        search_after: Vec<serde_json::Value>,
+        total: u64,
+    ) -> Result<Self, String> {
+        Ok(PitCursor {
+            transport,
+            pit_id: pit_id.to_string(),
+            body: Self::page_body(query)?,
+            search_after: Some(search_after),
+            first_page: None,
+            total,
+        })
+    }
+
+    // The query with paging replaced; without a sort of its own, index order is cheapest. ES adds
+    // the PIT's `_shard_doc` as a tiebreaker to any sort, so sort values never repeat.
+    fn page_body(query: &str) -> Result<serde_json::Value, String> {
+        let mut body: serde_json::Value = if query.trim().is_empty() {
+            serde_json::json!({ "query": { "match_all": {} } })
+        } else {
+            serde_json::from_str(query).map_err(|e| format!("Invalid query JSON: {}", e))?
+        };
+
+        let body_map = body
+            .as_object_mut()
+            .ok_or("The query must be a JSON object")?;
+        body_map.remove("from");
+        body_map.insert("size".to_string(), serde_json::json!(SCROLL_PAGE_SIZE));
+        body_map
+            .entry("sort")
+            .or_insert_with(|| serde_json::json!([{ "_shard_doc": "asc" }]));
+
+        Ok(body)
+    }
+
+    async fn search(&self, count_total: bool) -> Result<serde_json::Value, String> {
+        let mut body = self.body.clone();
+        body["pit"] = serde_json::json!({ "id": self.pit_id, "keep_alive": EXPORT_PIT_KEEP_ALIVE });
+        body["track_total_hits"] = serde_json::json!(count_total);
+        if let Some(search_after) = &self.search_after {
+            body["search_after"] = serde_json::json!(search_after);
+        }
+
+        let response = self
+            .transport
+            .send(Method::POST, "/_search", Some(&body))
+            .await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(format!(
+                "The export's point in time has expired ({} after its last page), start the export again",
+                EXPORT_PIT_KEEP_ALIVE
+            ));
+        }
+        if !response.status().is_success() {
+            return Err(describe_error_response(response).await);
+        }
+
+        response.json().await.map_err(|e| e.to_string())
+    }
+
+    // Moves past the hits of a response and returns them
+    fn advance(&mut self, result: &serde_json::Value) -> Vec<serde_json::Value> {
+        // The PIT id may change between requests, the latest one must be used
+        if let Some(pit_id) = result["pit_id"].as_str() {
+            self.pit_id = pit_id.to_string();
+        }
+
+        let hits = result["hits"]["hits"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        if let Some(sort) = hits.last().and_then(|hit| hit["sort"].as_array()) {
+            self.search_after = Some(sort.clone());
+        }
+
+        hits
+    }
+
+    // The PIT id and the sort values to continue after, once a page has been read
+    pub(crate) fn position(&self) -> Option<(&str, &[serde_json::Value])> {
+        self.search_after
+            .as_deref()
+            .map(|search_after| (self.pit_id.as_str(), search_after))
+    }
+
+    // Returns the next page of hits, or an empty page once every document has been read
+    pub(crate) async fn next_page(&mut self) -> Result<Vec<serde_json::Value>, String> {
+        if let Some(page) = self.first_page.take() {
+            return Ok(page);
+        }
+
+        let result = self
+            .search(false)
+            .await
+            .map_err(|e| format!("Failed to continue export: {}", e))?;
+
+        Ok(self.advance(&result))
+    }
+
+    // Frees the point in time on the cluster instead of waiting for it to expire
+    pub(crate) async fn close(self) {
+        let body = serde_json::json!({ "id": self.pit_id });
+        let _ = self
+            .transport
+            .send(Method::DELETE, "/_pit", Some(&body))
+            .await;
+    }
+}
+
+// Publishes page progress and stops the export once its task has been cancelled
+fn report_progress(
+    app: &AppHandle,
+    task: &TaskHandle,
+    progress: ExportProgress,
+) -> Result<(), String> {
+    let percent = if progress.total_docs == 0 {
+        0.0
+    } else {
+        progress.docs_written as f64 / progress.total_docs as f64 * 100.0
+    };
+    let _ = app.emit("export-progress", progress);
+    task.progress(percent)
+}
+
+// Flattens nested objects into `parent<sep>child` keys; arrays are kept as JSON text
+fn flatten_source(
+    prefix: &str,
+    value: &serde_json::Value,
+    separator: &str,
+    out: &mut std::collections::BTreeMap<String, String>,
+) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, child) in object {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}{}{}", prefix, separator, key)
+                };
+                flatten_source(&path, child, separator, out);
+            }
+        }
+        serde_json::Value::Null => {
+            out.insert(prefix.to_string(), String::new());
+        }
+        serde_json::Value::String(s) => {
+            out.insert(prefix.to_string(), s.clone());
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+// The `_id` column comes first when the export includes it
+fn csv_header(include_id: bool, columns: &[String]) -> Vec<&str> {
+    let mut header: Vec<&str> = Vec::new();
+    if include_id {
+        header.push("_id");
+    }
+    header.extend(columns.iter().map(|c| c.as_str()));
+    header
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_query_to_csv(
+    window: Window,
+    app: AppHandle,
+    index: String,
+    query: String,
+    fields: Option<Vec<String>>,
+    file_path: String,
+    options: Option<CsvExportOptions>,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    let file_path = resolve_export_path(file_path);
+    let task = TaskHandle::start(&app, "export", format!("Export {} to {}", index, file_path));
+    let result = run_export_query_to_csv(
+        &window, app, &task, index, query, fields, file_path, options, timeout_ms,
+    )
+    .await;
+    task.finish(result)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_export_query_to_csv(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    index: String,
+    query: String,
+    fields: Option<Vec<String>>,
+    file_path: String,
+    options: Option<CsvExportOptions>,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+    let index = connection_index(&conn, &index)?;
+    let options = options.unwrap_or_default();
+
+    let delimiter = match options.delimiter.as_deref() {
+        None | Some("") => b',',
+        Some("\\t") | Some("\t") => b'\t',
+        Some(d) if d.len() == 1 => d.as_bytes()[0],
+        Some(d) => {
+            return Err(format!(
+                "Invalid CSV delimiter '{}': expected a single character",
+                d
+            ))
+        }
+    };
+    let separator = options.path_separator.unwrap_or_else(|| ".".to_string());
+    let include_id = options.include_id.unwrap_or(true);
+
+    let (output, file_path) = OutputFile::create(&file_path, options.compress.unwrap_or(false))?;
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(output);
+
+    let mut cursor = ScrollCursor::open(client, conn, &index, &query, timeout_ms).await?;
+    let total_docs = cursor.total;
+    let mut columns: Option<Vec<String>> = fields.filter(|f| !f.is_empty());
+    let mut docs_written = 0;
+
+    let result: Result<(), String> = async {
+        loop {
+            let page = cursor.next_page().await?;
+            if page.is_empty() {
+                break;
+            }
+
+            let rows: Vec<std::collections::BTreeMap<String, String>> = page
+                .iter()
+                .map(|hit| {
+                    let mut row = std::collections::BTreeMap::new();
+                    flatten_source("", &hit["_source"], &separator, &mut row);
+                    row
+                })
+                .collect();
+
+            // Without an explicit field list the columns come from the first page of results
+            let columns = columns.get_or_insert_with(|| {
+                let mut names: std::collections::BTreeSet<String> =
+                    std::collections::BTreeSet::new();
+                for row in &rows {
+                    names.extend(row.keys().cloned());
+                }
+                names.into_iter().collect()
+            });
+
+            if docs_written == 0 {
+                writer
+                    .write_record(csv_header(include_id, columns))
+                    .map_err(|e| e.to_string())?;
+            }
+
+            for (hit, row) in page.iter().zip(rows.iter()) {
+                let mut record: Vec<&str> = Vec::new();
+                if include_id {
+                    record.push(hit["_id"].as_str().unwrap_or(""));
+                }
+                record.extend(
+                    columns
+                        .iter()
+                        .map(|c| row.get(c).map(|v| v.as_str()).unwrap_or("")),
+                );
+                writer.write_record(&record).map_err(|e| e.to_string())?;
+                docs_written += 1;
+            }
+
+            writer.flush().map_err(|e| e.to_string())?;
+            report_progress(
+                &app,
+                task,
+                ExportProgress {
+                    file_path: file_path.clone(),
+                    index: index.clone(),
+                    docs_written,
+                    total_docs,
+                    finished: false,
+                },
+            )?;
+        }
+
+        // An empty result still gets a header when the columns were given up front
+        if docs_written == 0 {
+            if let Some(columns) = &columns {
+                writer
+                    .write_record(csv_header(include_id, columns))
+                    .map_err(|e| e.to_string())?;
+                writer.flush().map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    cursor.close().await;
+    result?;
+    writer.into_inner().map_err(|e| e.to_string())?.finish()?;
+
+    let _ = app.emit(
+        "export-progress",
+        ExportProgress {
+            file_path: file_path.clone(),
+            index: index.clone(),
+            docs_written,
+            total_docs,
+            finished: true,
+        },
+    );
+
+    println!(
+        "Exported {} documents from index {} to: {}",
+        docs_written, index, file_path
+    );
+
+    Ok(ExportSummary {
+        file_path,
+        index,
+        docs_written,
+        columns: columns.unwrap_or_default(),
+    })
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_query_to_ndjson(
+    window: Window,
+    app: AppHandle,
+    index: String,
+    query: String,
+    file_path: String,
+    include_metadata: Option<bool>,
+    compress: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    let file_path = resolve_export_path(file_path);
+    let task = TaskHandle::start(&app, "export", format!("Export {} to {}", index, file_path));
+    let params = NdjsonExportParams {
+        index,
+        query,
+        file_path,
+        include_metadata,
+    };
+    let compress = compress.unwrap_or(false);
+    let result =
+        run_export_query_to_ndjson(&window, app, &task, params, compress, None, timeout_ms).await;
+    task.finish(result)
+}
+
+// Only uncompressed exports are checkpointed; a gzip stream can't be cut off and continued.
+// The point in time survives disconnects and restarts but expires EXPORT_PIT_KEEP_ALIVE after
+// the last page was read, so that is how long an interrupted export can be resumed.
+pub(crate) async fn resume_export_ndjson(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    checkpoint: &TaskCheckpoint,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    let params = serde_json::from_value(checkpoint.params.clone())
+        .map_err(|e| format!("Invalid checkpoint for task {}: {}", checkpoint.task_id, e))?;
+    let position = serde_json::from_value(checkpoint.position.clone())
+        .map_err(|e| format!("Invalid checkpoint for task {}: {}", checkpoint.task_id, e))?;
+    run_export_query_to_ndjson(window, app, task, params, false, Some(position), timeout_ms).await
+}
+
+async fn run_export_query_to_ndjson(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    params: NdjsonExportParams,
+    compress: bool,
+    resume: Option<ExportPosition>,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+    let connection_id = conn.id.clone();
+    let include_metadata = params.include_metadata.unwrap_or(false);
+    let index = connection_index(&conn, &params.index)?;
+
+    let (mut writer, file_path) = match &resume {
+        Some(position) => (
+            OutputFile::reopen(&params.file_path, position.bytes_written)?,
+            params.file_path.clone(),
+        ),
+        None => OutputFile::create(&params.file_path, compress)?,
+    };
+    // The checkpoint records the final path, with any .gz already appended
+    let checkpoint_params = serde_json::to_value(NdjsonExportParams {
+        index: index.clone(),
+        file_path: file_path.clone(),
+        ..params.clone()
+    })
+    .map_err(|e| e.to_string())?;
+
+    let transport = Transport::new(client, conn).with_timeout(timeout_ms);
+    let mut cursor = match &resume {
+        Some(position) => PitCursor::resume(
+            transport,
+            &params.query,
+            &position.pit_id,
+            position.search_after.clone(),
+            position.total_docs,
+        )?,
+        None => PitCursor::open(transport, &index, &params.query).await?,
+    };
+    let total_docs = cursor.total;
+    let mut docs_written = resume.as_ref().map_or(0, |position| position.docs_written);
+    let mut bytes_written = resume.as_ref().map_or(0, |position| position.bytes_written);
+
+    let result: Result<(), String> = async {
+        loop {
+            let page = cursor.next_page().await?;
+            if page.is_empty() {
+                break;
+            }
+
+            for hit in &page {
+                // With metadata each line keeps _index/_id so the dump can be re-imported as-is
+                let line = if include_metadata {
+                    serde_json::json!({
+                        "_index": hit["_index"],
+                        "_id": hit["_id"],
+                        "_source": hit["_source"]
+                    })
+                } else {
+                    hit["_source"].clone()
+                };
+
+                let line = line.to_string();
+                std::io::Write::write_all(&mut writer, line.as_bytes())
+                    .map_err(|e| e.to_string())?;
+                std::io::Write::write_all(&mut writer, b"\n").map_err(|e| e.to_string())?;
+                docs_written += 1;
+                bytes_written += line.len() as u64 + 1;
+            }
+
+            std::io::Write::flush(&mut writer).map_err(|e| e.to_string())?;
+            if let (false, Some((pit_id, search_after))) = (compress, cursor.position()) {
+                let position = ExportPosition {
+                    pit_id: pit_id.to_string(),
+                    search_after: search_after.to_vec(),
+                    docs_written,
+                    total_docs,
+                    bytes_written,
+                };
+                task.checkpoint(
+                    "export_ndjson",
+                    &connection_id,
+                    &checkpoint_params,
+                    serde_json::to_value(position).map_err(|e| e.to_string())?,
+                );
+            }
+            report_progress(
+                &app,
+                task,
+                ExportProgress {
+                    file_path: file_path.clone(),
+                    index: index.clone(),
+                    docs_written,
+                    total_docs,
+                    finished: false,
+                },
+            )?;
+        }
+        Ok(())
+    }
+    .await;
+
+    // An interrupted export that saved a checkpoint keeps its point in time, which expires on its
+    // own unless the export is resumed first
+    if result.is_ok() || compress || docs_written == 0 {
+        cursor.close().await;
+    }
+    result?;
+    writer.finish()?;
+
+    let _ = app.emit(
+        "export-progress",
+        ExportProgress {
+            file_path: file_path.clone(),
+            index: index.clone(),
+            docs_written,
+            total_docs,
+            finished: true,
+        },
+    );
+
+    println!(
+        "Exported {} documents from index {} to: {}",
+        docs_written, index, file_path
+    );
+
+    Ok(ExportSummary {
+        file_path,
+        index,
+        docs_written,
+        columns: vec![],
+    })
+}
+
+// Exports documents whose `since_field` is newer than a watermark to NDJSON and remembers the
+// newest value written, so the next run only picks up what came in since. Without
+// `since_value` the watermark stored by the previous run is used. Documents sharing the
+// watermark value exactly but indexed after the export are not picked up by the next run.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_incremental(
+    window: Window,
+    app: AppHandle,
+    index: String,
+    since_field: String,
+    since_value: Option<serde_json::Value>,
+    file_path: String,
+    compress: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<IncrementalExportSummary, String> {
+    let file_path = resolve_export_path(file_path);
+    let task = TaskHandle::start(
+        &app,
+        "export",
+        format!("Export new documents from {} to {}", index, file_path),
+    );
+    let result = run_export_incremental(
+        &window,
+        &app,
+        &task,
+        index,
+        since_field,
+        since_value,
+        file_path,
+        compress.unwrap_or(false),
+        timeout_ms,
+    )
+    .await;
+    task.finish(result)
+}
+
+// Keeps the full precision of date_nanos fields and parses back for plain dates too
+const WATERMARK_DATE_FORMAT: &str = "strict_date_optional_time_nanos";
+
+// Whether the field is a date (or date_nanos) in every index it is mapped in
+async fn is_date_field(transport: &Transport, index: &str, field: &str) -> Result<bool, String> {
+    let caps = transport
+        .get_json(&format!(
+            "/{}/_field_caps?fields={}",
+            encode_segment(index),
+            encode_query(field)
+        ))
+        .await?;
+
+    Ok(caps["fields"][field].as_object().is_some_and(|types| {
+        !types.is_empty()
+            && types
+                .keys()
+                .all(|field_type| field_type == "date" || field_type == "date_nanos")
+    }))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_export_incremental(
+    window: &Window,
+    app: &AppHandle,
+    task: &TaskHandle,
+    index: String,
+    since_field: String,
+    since_value: Option<serde_json::Value>,
+    file_path: String,
+    compress: bool,
+    timeout_ms: Option<u64>,
+) -> Result<IncrementalExportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+    let connection_id = conn.id.clone();
+    let index = connection_index(&conn, &index)?;
+
+    let previous_watermark = match since_value {
+        Some(value) => Some(value),
+        None => load_export_watermark(app, &connection_id, &index, &since_field)?,
+    };
+
+    // Sorting on the watermark field makes the last sort value of the export the new watermark.
+    // Raw sort values of date_nanos fields are nanoseconds while range queries read numbers as
+    // millis, so dates are sorted and compared as formatted strings instead.
+    let transport = Transport::new(client.clone(), conn.clone()).with_timeout(timeout_ms);
+    let is_date = is_date_field(&transport, &index, &since_field).await?;
+    let query = match &previous_watermark {
+        Some(watermark) => {
+            let mut range = serde_json::json!({ "gt": watermark });
+            // Watermarks saved as numbers, or given as numbers, are epoch millis
+            if is_date && watermark.is_number() {
+                range["format"] = serde_json::json!("epoch_millis");
+            } else if is_date {
+                range["format"] = serde_json::json!(WATERMARK_DATE_FORMAT);
+            }
+            serde_json::json!({ "range": { since_field.as_str(): range } })
+        }
+        None => serde_json::json!({ "exists": { "field": since_field } }),
+    };
+    let sort = if is_date {
+        serde_json::json!({ since_field.as_str(): { "order": "asc", "format": WATERMARK_DATE_FORMAT } })
+    } else {
+        serde_json::json!({ since_field.as_str(): "asc" })
+    };
+    let body = serde_json::json!({
+        "query": query,
+        "sort": [sort]
+    });
+
+    let (mut writer, file_path) = OutputFile::create(&file_path, compress)?;
+    let mut cursor =
+        ScrollCursor::open(client, conn, &index, &body.to_string(), timeout_ms).await?;
+    let total_docs = cursor.total;
+    let mut docs_written = 0;
+    let mut watermark = previous_watermark.clone();
+
+    let result: Result<(), String> = async {
+        loop {
+            let page = cursor.next_page().await?;
+            if page.is_empty() {
+                break;
+            }
+
+            for hit in &page {
+                std::io::Write::write_all(&mut writer, hit["_source"].to_string().as_bytes())
+                    .map_err(|e| e.to_string())?;
+                std::io::Write::write_all(&mut writer, b"\n").map_err(|e| e.to_string())?;
+                docs_written += 1;
+            }
+
+            if let Some(last) = page.last().and_then(|hit| hit["sort"].get(0)) {
+                watermark = Some(last.clone());
+            }
+
+            std::io::Write::flush(&mut writer).map_err(|e| e.to_string())?;
+            report_progress(
+                app,
+                task,
+                ExportProgress {
+                    file_path: file_path.clone(),
+                    index: index.clone(),
+                    docs_written,
+                    total_docs,
+                    finished: false,
+                },
+            )?;
+        }
+        Ok(())
+    }
+    .await;
+
+    cursor.close().await;
+    result?;
+    writer.finish()?;
+
+    // Only moved once the file is complete, so a failed run is simply repeated next time
+    if let Some(value) = &watermark {
+        save_export_watermark(
+            app,
+            &ExportWatermark {
+                connection_id,
+                index: index.clone(),
+                field: since_field.clone(),
+                value: value.clone(),
+                updated_at: chrono::Utc::now().timestamp_millis(),
+            },
+        )?;
+    }
+
+    let _ = app.emit(
+        "export-progress",
+        ExportProgress {
+            file_path: file_path.clone(),
+            index: index.clone(),
+            docs_written,
+            total_docs,
+            finished: true,
+        },
+    );
+
+    println!(
+        "Exported {} new documents from index {} to: {}",
+        docs_written, index, file_path
+    );
+
+    Ok(IncrementalExportSummary {
+        file_path,
+        index,
+        docs_written,
+        since_field,
+        previous_watermark,
+        watermark,
+    })
+}
+
+// Parquet column kinds an ES field type is narrowed down to
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColumnKind {
+    Long,
+    // Goes past i64::MAX, so it gets an unsigned column of its own
+    UnsignedLong,
+    Double,
+    Boolean,
+    Timestamp,
+    TimestampNanos,
+    Text,
+}
+
+fn column_kind(es_type: &str) -> ColumnKind {
+    match es_type {
+        "long" | "integer" | "short" | "byte" => ColumnKind::Long,
+        "unsigned_long" => ColumnKind::UnsignedLong,
+        "double" | "float" | "half_float" | "scaled_float" => ColumnKind::Double,
+        "boolean" => ColumnKind::Boolean,
+        "date" => ColumnKind::Timestamp,
+        "date_nanos" => ColumnKind::TimestampNanos,
+        _ => ColumnKind::Text,
+    }
+}
+
+// Collects leaf field paths and their types from a `_mapping` response
+fn collect_mapping_fields(
+    prefix: &str,
+    properties: &serde_json::Value,
+    out: &mut std::collections::BTreeMap<String, String>,
+) {
+    if let Some(properties) = properties.as_object() {
+        for (name, definition) in properties {
+            let path = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+
+            match definition["type"].as_str() {
+                // Nested documents don't fit a flat row, so they are written as JSON text
+                Some("nested") => {
+                    out.entry(path).or_insert_with(|| "nested".to_string());
+                }
+                Some(es_type) if es_type != "object" => {
+                    out.entry(path).or_insert_with(|| es_type.to_string());
+                }
+                _ => collect_mapping_fields(&path, &definition["properties"], out),
+            }
+        }
+    }
+}
+
+fn lookup_path<'a>(source: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    // Documents may hold either nested objects or literal dotted keys
+    if let Some(value) = source.get(path) {
+        return Some(value);
+    }
+    path.split('.')
+        .try_fold(source, |value, key| value.get(key))
+}
+
+// Numbers and numeric strings are epoch milliseconds, as ES reads them for both date types
+fn parse_timestamp(value: &serde_json::Value) -> Option<chrono::DateTime<chrono::Utc>> {
+    match value {
+        serde_json::Value::Number(n) => {
+            n.as_i64().and_then(chrono::DateTime::from_timestamp_millis)
+        }
+        serde_json::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .map(|d| d.to_utc())
+            .ok()
+            .or_else(|| {
+                chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+                    .or_else(|_| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S"))
+                    .map(|d| d.and_utc())
+                    .ok()
+            })
+            .or_else(|| {
+                chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|d| d.and_utc())
+            })
+            .or_else(|| {
+                s.parse()
+                    .ok()
+                    .and_then(chrono::DateTime::from_timestamp_millis)
+            }),
+        _ => None,
+    }
+}
+
+fn build_record_batch(
+    schema: &std::sync::Arc<arrow_schema::Schema>,
+    columns: &[(String, ColumnKind)],
+    hits: &[serde_json::Value],
+) -> Result<arrow_array::RecordBatch, String> {
+    use arrow_array::builder::{
+        BooleanBuilder, Float64Builder, Int64Builder, StringBuilder, TimestampMillisecondBuilder,
+        TimestampNanosecondBuilder, UInt64Builder,
+    };
+    use arrow_array::ArrayRef;
+
+    let mut arrays: Vec<ArrayRef> = Vec::with_capacity(columns.len() + 1);
+
+    let mut ids = StringBuilder::new();
+    for hit in hits {
+        ids.append_option(hit["_id"].as_str());
+    }
+    arrays.push(std::sync::Arc::new(ids.finish()));
+
+    for (path, kind) in columns {
+        // Multi-valued fields keep their first value in typed columns
+        let values = hits.iter().map(|hit| {
+            lookup_path(&hit["_source"], path).and_then(|value| match value {
+                serde_json::Value::Null => None,
+                serde_json::Value::Array(items) if *kind != ColumnKind::Text => items.first(),
+                other => Some(other),
+            })
+        });
+
+        let array: ArrayRef = match kind {
+            ColumnKind::Long => {
+                let mut builder = Int64Builder::new();
+                for value in values {
+                    builder.append_option(value.and_then(|v| {
+                        v.as_i64()
+                            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+                    }));
+                }
+                std::sync::Arc::new(builder.finish())
+            }
+            ColumnKind::UnsignedLong => {
+                let mut builder = UInt64Builder::new();
+                for value in values {
+                    builder.append_option(value.and_then(|v| {
+                        v.as_u64()
+                            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+                    }));
+                }
+                std::sync::Arc::new(builder.finish())
+            }
+            ColumnKind::Double => {
+                let mut builder = Float64Builder::new();
+                for value in values {
+                    builder.append_option(value.and_then(|v| {
+                        v.as_f64()
+                            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+                    }));
+                }
+                std::sync::Arc::new(builder.finish())
+            }
+            ColumnKind::Boolean => {
+                let mut builder = BooleanBuilder::new();
+                for value in values {
+                    builder.append_option(value.and_then(|v| {
+                        v.as_bool()
+                            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+                    }));
+                }
+                std::sync::Arc::new(builder.finish())
+            }
+            ColumnKind::Timestamp => {
+                let mut builder = TimestampMillisecondBuilder::new().with_timezone("UTC");
+                for value in values {
+                    builder.append_option(
+                        value
+                            .and_then(parse_timestamp)
+                            .map(|d| d.timestamp_millis()),
+                    );
+                }
+                std::sync::Arc::new(builder.finish())
+            }
+            ColumnKind::TimestampNanos => {
+                let mut builder = TimestampNanosecondBuilder::new().with_timezone("UTC");
+                for value in values {
+                    builder.append_option(
+                        value
+                            .and_then(parse_timestamp)
+                            .and_then(|d| d.timestamp_nanos_opt()),
+                    );
+                }
+                std::sync::Arc::new(builder.finish())
+            }
+            ColumnKind::Text => {
+                let mut builder = StringBuilder::new();
+                for value in values {
+                    builder.append_option(value.map(|v| match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    }));
+                }
+                std::sync::Arc::new(builder.finish())
+            }
+        };
+        arrays.push(array);
+    }
+
+    arrow_array::RecordBatch::try_new(schema.clone(), arrays).map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn export_query_to_parquet(
+    window: Window,
+    app: AppHandle,
+    index: String,
+    query: String,
+    file_path: String,
+    fields: Option<Vec<String>>,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    let file_path = resolve_export_path(file_path);
+    let task = TaskHandle::start(&app, "export", format!("Export {} to {}", index, file_path));
+    let result = run_export_query_to_parquet(
+        &window, app, &task, index, query, file_path, fields, timeout_ms,
+    )
+    .await;
+    task.finish(result)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_export_query_to_parquet(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    index: String,
+    query: String,
+    file_path: String,
+    fields: Option<Vec<String>>,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+    let index = connection_index(&conn, &index)?;
+
+    // Derive the schema from the mapping so column types don't depend on which documents come first
+    let path = format!("/{}/_mapping", encode_segment(&index));
+    let response = Transport::new(client.clone(), conn.clone())
+        .with_timeout(timeout_ms)
+        .send(Method::GET, &path, None)
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to get index mappings: {}",
+            describe_error_response(response).await
+        ));
+    }
+
+    let mappings: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let mut mapped_fields = std::collections::BTreeMap::new();
+    for index_mapping in mappings.as_object().into_iter().flat_map(|m| m.values()) {
+        collect_mapping_fields(
+            "",
+            &index_mapping["mappings"]["properties"],
+            &mut mapped_fields,
+        );
+    }
+
+    let columns: Vec<(String, ColumnKind)> = match fields.filter(|f| !f.is_empty()) {
+        Some(fields) => fields
+            .into_iter()
+            .map(|field| {
+                let kind = mapped_fields
+                    .get(&field)
+                    .map(|t| column_kind(t))
+                    .unwrap_or(ColumnKind::Text);
+                (field, kind)
+            })
+            .collect(),
+        None => mapped_fields
+            .iter()
+            .map(|(field, es_type)| (field.clone(), column_kind(es_type)))
+            .collect(),
+    };
+
+    let mut schema_fields = vec![arrow_schema::Field::new(
+        "_id",
+        arrow_schema::DataType::Utf8,
+        true,
+    )];
+    for (path, kind) in &columns {
+        let data_type = match kind {
+            ColumnKind::Long => arrow_schema::DataType::Int64,
+            ColumnKind::UnsignedLong => arrow_schema::DataType::UInt64,
+            ColumnKind::Double => arrow_schema::DataType::Float64,
+            ColumnKind::Boolean => arrow_schema::DataType::Boolean,
+            ColumnKind::Timestamp => arrow_schema::DataType::Timestamp(
+                arrow_schema::TimeUnit::Millisecond,
+                Some("UTC".into()),
+            ),
+            ColumnKind::TimestampNanos => arrow_schema::DataType::Timestamp(
+                arrow_schema::TimeUnit::Nanosecond,
+                Some("UTC".into()),
+            ),
+            ColumnKind::Text => arrow_schema::DataType::Utf8,
+        };
+        schema_fields.push(arrow_schema::Field::new(path, data_type, true));
+    }
+    let schema = std::sync::Arc::new(arrow_schema::Schema::new(schema_fields));
+
+    let file = std::fs::File::create(&file_path)
+        .map_err(|e| format!("Unable to create '{}': {}", file_path, e))?;
+    let properties = parquet::file::properties::WriterProperties::builder()
+        .set_compression(parquet::basic::Compression::SNAPPY)
+        .build();
+    let mut writer = parquet::arrow::ArrowWriter::try_new(file, schema.clone(), Some(properties))
+        .map_err(|e| e.to_string())?;
+
+    let mut cursor = ScrollCursor::open(client, conn, &index, &query, timeout_ms).await?;
+    let total_docs = cursor.total;
+    let mut docs_written = 0;
+
+    let result: Result<(), String> = async {
+        loop {
+            let page = cursor.next_page().await?;
+            if page.is_empty() {
+                break;
+            }
+
+            let batch = build_record_batch(&schema, &columns, &page)?;
+            writer.write(&batch).map_err(|e| e.to_string())?;
+            docs_written += page.len() as u64;
+
+            report_progress(
+                &app,
+                task,
+                ExportProgress {
+                    file_path: file_path.clone(),
+                    index: index.clone(),
+                    docs_written,
+                    total_docs,
+                    finished: false,
+                },
+            )?;
+        }
+        Ok(())
+    }
+    .await;
+
+    cursor.close().await;
+    result?;
+
+    // The footer holds the schema and row group index, so the file is unreadable without it
+    writer.close().map_err(|e| e.to_string())?;
+
+    let _ = app.emit(
+        "export-progress",
+        ExportProgress {
+            file_path: file_path.clone(),
+            index: index.clone(),
+            docs_written,
+            total_docs,
+            finished: true,
+        },
+    );
+
+    println!(
+        "Exported {} documents from index {} to: {}",
+        docs_written, index, file_path
+    );
+
+    Ok(ExportSummary {
+        file_path,
+        index,
+        docs_written,
+        columns: columns.into_iter().map(|(path, _)| path).collect(),
+    })
+}
+
+// Settings ES generates per index, which can't (or shouldn't) be replayed onto a new one
+const GENERATED_INDEX_SETTINGS: &[&str] = &[
+    "creation_date",
+    "uuid",
+    "version",
+    "provided_name",
+    "resize",
+    "shrink",
+    "history_uuid",
+    "verified_before_close",
+];
+
+async fn fetch_index_section(
+    transport: &Transport,
+    index: &str,
+    endpoint: &str,
+) -> Result<serde_json::Value, String> {
+    let path = format!("/{}/{}", encode_segment(index), endpoint);
+    let response = transport.send(Method::GET, &path, None).await?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to get {} for index '{}': {}",
+            endpoint,
+            index,
+            describe_error_response(response).await
+        ));
+    }
+
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+
+    // Responses are keyed by the concrete index name, which differs from the request for aliases
+    body.as_object()
+        .and_then(|indices| indices.values().next().cloned())
+        .ok_or_else(|| format!("Index '{}' was not found", index))
+}
+
+// Backs up a single concrete index, so the connection's default index pattern isn't used
+#[command]
+pub async fn export_index(
+    window: Window,
+    app: AppHandle,
+    index: String,
+    dir: String,
+    compress: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    let dir = resolve_export_path(dir);
+    let task = TaskHandle::start(&app, "export", format!("Back up {} to {}", index, dir));
+    let result = run_export_index(&window, app, &task, index, dir, compress, timeout_ms).await;
+    task.finish(result)
+}
+
+async fn run_export_index(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    index: String,
+    dir: String,
+    compress: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<ExportSummary, String> {
+    if index.trim().is_empty() {
+        return Err("An index is required for a backup".to_string());
+    }
+    let (conn, client) = current_connection(window.label())?;
+
+    let dir_path = std::path::Path::new(&dir);
+    std::fs::create_dir_all(dir_path).map_err(|e| format!("Unable to create '{}': {}", dir, e))?;
+
+    let transport = Transport::new(client.clone(), conn.clone()).with_timeout(timeout_ms);
+    let mappings = fetch_index_section(&transport, &index, "_mapping").await?["mappings"].clone();
+    let mut settings =
+        fetch_index_section(&transport, &index, "_settings").await?["settings"].clone();
+
+    if let Some(index_settings) = settings["index"].as_object_mut() {
+        for key in GENERATED_INDEX_SETTINGS {
+            index_settings.remove(*key);
+        }
+    }
+
+    let write_json = |name: &str, value: &serde_json::Value| -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+        std::fs::write(dir_path.join(name), contents)
+            .map_err(|e| format!("Unable to write {}: {}", name, e))
+    };
+    write_json("mapping.json", &mappings)?;
+    write_json("settings.json", &settings)?;
+
+    let docs_path = dir_path.join("docs.ndjson");
+    let (mut writer, file_path) =
+        OutputFile::create(&docs_path.to_string_lossy(), compress.unwrap_or(false))?;
+
+    let mut cursor = ScrollCursor::open(client, conn, &index, "", timeout_ms).await?;
+    let total_docs = cursor.total;
+    let mut docs_written = 0;
+
+    let result: Result<(), String> = async {
+        loop {
+            let page = cursor.next_page().await?;
+            if page.is_empty() {
+                break;
+            }
+
+            // Keep ids so a restore produces the same documents rather than copies
+            for hit in &page {
+                let line = serde_json::json!({ "_id": hit["_id"], "_source": hit["_source"] });
+                std::io::Write::write_all(&mut writer, line.to_string().as_bytes())
+                    .map_err(|e| e.to_string())?;
+                std::io::Write::write_all(&mut writer, b"\n").map_err(|e| e.to_string())?;
+                docs_written += 1;
+            }
+
+            std::io::Write::flush(&mut writer).map_err(|e| e.to_string())?;
+            report_progress(
+                &app,
+                task,
+                ExportProgress {
+                    file_path: file_path.clone(),
+                    index: index.clone(),
+                    docs_written,
+                    total_docs,
+                    finished: false,
+                },
+            )?;
+        }
+        Ok(())
+    }
+    .await;
+
+    cursor.close().await;
+    result?;
+    writer.finish()?;
+
+    let _ = app.emit(
+        "export-progress",
+        ExportProgress {
+            file_path: file_path.clone(),
+            index: index.clone(),
+            docs_written,
+            total_docs,
+            finished: true,
+        },
+    );
+
+    println!("Exported index {} to: {}", index, dir);
+
+    Ok(ExportSummary {
+        file_path: dir,
+        index,
+        docs_written,
+        columns: vec![],
+    })
+}
+
+// Escapes a value for a Markdown table cell, which can't hold pipes or line breaks
+fn markdown_cell(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+#[command]
+pub fn format_hits(hits: Vec<serde_json::Value>, format: String) -> Result<String, String> {
+    if format.eq_ignore_ascii_case("json") {
+        return serde_json::to_string_pretty(&hits).map_err(|e| e.to_string());
+    }
+
+    // Search hits are flattened from _source with their _id; bare documents are used as-is
+    let include_id = hits.iter().any(|hit| hit.get("_id").is_some());
+    let rows: Vec<std::collections::BTreeMap<String, String>> = hits
+        .iter()
+        .map(|hit| {
+            let mut row = std::collections::BTreeMap::new();
+            flatten_source("", hit.get("_source").unwrap_or(hit), ".", &mut row);
+            row
+        })
+        .collect();
+
+    let mut columns: Vec<String> = Vec::new();
+    if include_id {
+        columns.push("_id".to_string());
+    }
+    let mut names: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
+    for row in &rows {
+        names.extend(row.keys().cloned());
+    }
+    columns.extend(names.into_iter().filter(|name| name != "_id"));
+
+    let records: Vec<Vec<&str>> = hits
+        .iter()
+        .zip(rows.iter())
+        .map(|(hit, row)| {
+            columns
+                .iter()
+                .map(|column| {
+                    if include_id && column == "_id" {
+                        hit["_id"].as_str().unwrap_or("")
+                    } else {
+                        row.get(column).map(|v| v.as_str()).unwrap_or("")
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    match format.to_lowercase().as_str() {
+        "csv" | "tsv" => {
+            let delimiter = if format.eq_ignore_ascii_case("tsv") {
+                b'\t'
+            } else {
+                b','
+            };
+            let mut writer = csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .from_writer(Vec::new());
+            writer.write_record(&columns).map_err(|e| e.to_string())?;
+            for record in &records {
+                writer.write_record(record).map_err(|e| e.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+        "markdown" | "md" => {
+            let mut out = String::new();
+            let header: Vec<String> = columns.iter().map(|c| markdown_cell(c)).collect();
+            out.push_str(&format!("| {} |\n", header.join(" | ")));
+            out.push_str(&format!("|{}\n", " --- |".repeat(columns.len())));
+            for record in &records {
+                let cells: Vec<String> = record.iter().map(|v| markdown_cell(v)).collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+            Ok(out)
+        }
+        other => Err(format!(
+            "Unsupported format '{}': expected csv, tsv, json or markdown",
+            other
+        )),
+    }
+}
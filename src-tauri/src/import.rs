@@ -0,0 +1,2130 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/import.rs
+
+use crate::bulk::{BulkAction, BulkBuilder};
+use crate::elasticsearch::{current_connection, describe_error_body, ElasticsearchConnection};
+use crate::storage::{app_settings, TaskCheckpoint};
+use crate::tasks::TaskHandle;
+use crate::transport::{encode_segment, Transport};
+use reqwest::{Client as ReqwestClient, Method};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::BufRead;
+use tauri::{command, AppHandle, Emitter, Window};
+
+// Keep the failure report bounded even when every row of a huge file is rejected
+const MAX_REPORTED_ERRORS: usize = 1000;
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CsvImportOptions {
+    pub delimiter: Option<String>,
+    pub has_headers: Option<bool>,
+    pub batch_size: Option<usize>,
+    // column -> field; columns mapped to an empty string are skipped
+    pub field_mapping: Option<HashMap<String, String>>,
+    pub infer_types: Option<bool>,
+    pub id_column: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportProgress {
+    pub file_path: String,
+    pub index: String,
+    pub rows_processed: u64,
+    pub rows_indexed: u64,
+    pub rows_failed: u64,
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+    pub finished: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRowError {
+    pub row: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub index: String,
+    pub rows_processed: u64,
+    pub rows_indexed: u64,
+    pub rows_failed: u64,
+    pub errors: Vec<ImportRowError>,
+    pub errors_truncated: bool,
+}
+
+impl ImportSummary {
+    fn new(index: &str) -> Self {
+        ImportSummary {
+            index: index.to_string(),
+            rows_processed: 0,
+            rows_indexed: 0,
+            rows_failed: 0,
+            errors: Vec::new(),
+            errors_truncated: false,
+        }
+    }
+
+    fn record_error(&mut self, row: u64, reason: String) {
+        self.rows_failed += 1;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(ImportRowError { row, reason });
+        } else {
+            self.errors_truncated = true;
+        }
+    }
+
+    fn progress(
+        &self,
+        file_path: &str,
+        bytes_read: u64,
+        total_bytes: u64,
+        finished: bool,
+    ) -> ImportProgress {
+        ImportProgress {
+            file_path: file_path.to_string(),
+            index: self.index.clone(),
+            rows_processed: self.rows_processed,
+            rows_indexed: self.rows_indexed,
+            rows_failed: self.rows_failed,
+            bytes_read,
+            total_bytes,
+            finished,
+        }
+    }
+}
+
+// Where a checkpointed import got to. `offset` counts bytes of the decompressed input
+// consumed up to the last batch that reached the cluster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ImportPosition {
+    offset: u64,
+    row: u64,
+    // Size of the file when the import started, to notice it changing before a resume
+    file_bytes: u64,
+    summary: ImportSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CsvImportParams {
+    file_path: String,
+    index: String,
+    options: Option<CsvImportOptions>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NdjsonImportParams {
+    file_path: String,
+    index: String,
+    batch_size: Option<usize>,
+}
+
+// Splits a checkpoint back into the original arguments and the position to resume from
+fn read_checkpoint<P: serde::de::DeserializeOwned>(
+    checkpoint: &TaskCheckpoint,
+) -> Result<(P, ImportPosition), String> {
+    let params = serde_json::from_value(checkpoint.params.clone())
+        .map_err(|e| format!("Invalid checkpoint for task {}: {}", checkpoint.task_id, e))?;
+    let position = serde_json::from_value(checkpoint.position.clone())
+        .map_err(|e| format!("Invalid checkpoint for task {}: {}", checkpoint.task_id, e))?;
+    Ok((params, position))
+}
+
+fn check_unchanged(
+    file_path: &str,
+    total_bytes: u64,
+    position: &ImportPosition,
+) -> Result<(), String> {
+    if total_bytes != position.file_bytes {
+        return Err(format!(
+            "'{}' has changed since the import was interrupted, start it again",
+            file_path
+        ));
+    }
+    Ok(())
+}
+
+// A document waiting to be sent, tagged with the source row it came from
+pub(crate) struct PendingDocument {
+    pub row: u64,
+    pub id: Option<String>,
+    pub source: serde_json::Value,
+}
+
+// Counts bytes as they come off disk so progress can be reported while streaming
+struct CountingReader<R> {
+    inner: R,
+    count: std::sync::Arc<std::sync::atomic::AtomicU64>,
+}
+
+impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count
+            .fetch_add(read as u64, std::sync::atomic::Ordering::Relaxed);
+        Ok(read)
+    }
+}
+
+// An import source file, transparently gunzipped when it ends in `.gz`
+pub(crate) struct InputFile {
+    pub reader: Box<dyn std::io::BufRead + Send>,
+    // Counts compressed bytes for .gz files, so it always lines up with total_bytes
+    pub bytes_read: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    pub total_bytes: u64,
+}
+
+impl InputFile {
+    pub(crate) fn bytes_read(&self) -> u64 {
+        self.bytes_read.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+pub(crate) fn open_input(path: &std::path::Path) -> Result<InputFile, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Unable to open '{}': {}", path.display(), e))?;
+    let total_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let bytes_read = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let counted = CountingReader {
+        inner: file,
+        count: bytes_read.clone(),
+    };
+
+    let is_gzip = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("gz"))
+        .unwrap_or(false);
+    let reader: Box<dyn std::io::BufRead + Send> = if is_gzip {
+        Box::new(std::io::BufReader::new(flate2::read::MultiGzDecoder::new(
+            counted,
+        )))
+    } else {
+        Box::new(std::io::BufReader::new(counted))
+    };
+
+    Ok(InputFile {
+        reader,
+        bytes_read,
+        total_bytes,
+    })
+}
+
+// Sends a batch and folds the outcome into the running summary. Fails when a `_bulk` request
+// failed as a whole, so the caller stops before checkpointing past rows that weren't written.
+pub(crate) async fn flush_batch(
+    client: &ReqwestClient,
+    conn: &ElasticsearchConnection,
+    index: &str,
+    batch: &mut Vec<PendingDocument>,
+    summary: &mut ImportSummary,
+    timeout_ms: Option<u64>,
+) -> Result<(), String> {
+    let transport = Transport::new(client.clone(), conn.clone()).with_timeout(timeout_ms);
+    let mut bulk = BulkBuilder::new(transport);
+    for doc in batch.drain(..) {
+        bulk.push(BulkAction::index(
+            doc.row,
+            index,
+            doc.id.as_deref(),
+            doc.source,
+        ))
+        .await?;
+    }
+
+    let outcome = bulk.finish().await?;
+    summary.rows_indexed += outcome.succeeded;
+    for error in outcome.errors {
+        summary.record_error(error.tag, error.reason);
+    }
+    Ok(())
+}
+
+// Publishes batch progress and stops the import once its task has been cancelled
+fn report_progress(
+    app: &AppHandle,
+    task: &TaskHandle,
+    progress: ImportProgress,
+) -> Result<(), String> {
+    let percent = if progress.total_bytes == 0 {
+        0.0
+    } else {
+        progress.bytes_read as f64 / progress.total_bytes as f64 * 100.0
+    };
+    let _ = app.emit("import-progress", progress);
+    task.progress(percent)
+}
+
+// Zip codes, phone numbers and ids like "007" lose their meaning as numbers; "0" and "0.5" don't
+fn has_leading_zero(number: &str) -> bool {
+    let integer_part = number
+        .trim_start_matches(['+', '-'])
+        .split(['.', 'e', 'E'])
+        .next()
+        .unwrap_or("");
+    integer_part.len() > 1 && integer_part.starts_with('0')
+}
+
+// Turns a raw CSV cell into the most specific JSON value it parses as
+fn infer_value(raw: &str) -> serde_json::Value {
+    let trimmed = raw.trim();
+
+    if trimmed.is_empty() {
+        return serde_json::Value::Null;
+    }
+
+    let is_integer = trimmed
+        .trim_start_matches(['+', '-'])
+        .bytes()
+        .all(|b| b.is_ascii_digit());
+    if has_leading_zero(trimmed) {
+        return serde_json::Value::String(raw.to_string());
+    }
+    if is_integer {
+        // Beyond u64 a float would silently round the value, so it stays text
+        return match (trimmed.parse::<i64>(), trimmed.parse::<u64>()) {
+            (Ok(value), _) => serde_json::Value::from(value),
+            (_, Ok(value)) => serde_json::Value::from(value),
+            _ => serde_json::Value::String(raw.to_string()),
+        };
+    }
+
+    if let Ok(value) = trimmed.parse::<f64>() {
+        if value.is_finite() {
+            return serde_json::Value::from(value);
+        }
+    }
+
+    match trimmed.to_ascii_lowercase().as_str() {
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        _ => serde_json::Value::String(raw.to_string()),
+    }
+}
+
+// Whether every non-empty cell of a column infers to the same kind of value. Only such columns
+// keep inferred numbers and booleans, so a column of ids holding "007" next to "123" stays text.
+#[derive(Default)]
+struct ColumnKinds {
+    kinds: Vec<Option<&'static str>>,
+    mixed: Vec<bool>,
+}
+
+impl ColumnKinds {
+    fn observe(&mut self, record: &csv::StringRecord) {
+        for (position, raw) in record.iter().enumerate() {
+            if self.kinds.len() <= position {
+                self.kinds.resize(position + 1, None);
+                self.mixed.resize(position + 1, false);
+            }
+
+            let kind = match infer_value(raw) {
+                serde_json::Value::Null => continue,
+                serde_json::Value::Number(_) => "number",
+                serde_json::Value::Bool(_) => "boolean",
+                _ => "string",
+            };
+            match self.kinds[position] {
+                None => self.kinds[position] = Some(kind),
+                Some(seen) if seen != kind => self.mixed[position] = true,
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn value(&self, position: usize, raw: &str) -> serde_json::Value {
+        let value = infer_value(raw);
+        if self.mixed.get(position).copied().unwrap_or(false) && !value.is_null() {
+            serde_json::Value::String(raw.to_string())
+        } else {
+            value
+        }
+    }
+}
+
+// Reads the whole file once up front, since a column's type can only be settled after its last row
+fn scan_column_kinds(
+    file_path: &str,
+    delimiter: u8,
+    has_headers: bool,
+) -> Result<ColumnKinds, String> {
+    let input = open_input(std::path::Path::new(file_path))?;
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_headers)
+        .from_reader(input.reader);
+
+    let mut kinds = ColumnKinds::default();
+    let mut record = csv::StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => kinds.observe(&record),
+            Err(e) if matches!(e.kind(), csv::ErrorKind::Io(_)) => {
+                return Err(format!("Unable to read '{}': {}", file_path, e))
+            }
+            // The import itself reports unparseable rows
+            Err(_) => {}
+        }
+    }
+    Ok(kinds)
+}
+
+#[command]
+pub async fn import_csv(
+    window: Window,
+    app: AppHandle,
+    file_path: String,
+    index: String,
+    options: Option<CsvImportOptions>,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let task = TaskHandle::start(
+        &app,
+        "import",
+        format!("Import {} into {}", file_path, index),
+    );
+    let params = CsvImportParams {
+        file_path,
+        index,
+        options,
+    };
+    let result = run_import_csv(&window, app, &task, params, None, timeout_ms).await;
+    task.finish(result)
+}
+
+pub(crate) async fn resume_import_csv(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    checkpoint: &TaskCheckpoint,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let (params, position) = read_checkpoint(checkpoint)?;
+    run_import_csv(window, app, task, params, Some(position), timeout_ms).await
+}
+
+async fn run_import_csv(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    params: CsvImportParams,
+    resume: Option<ImportPosition>,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+    let checkpoint_params = serde_json::to_value(&params).map_err(|e| e.to_string())?;
+    let CsvImportParams {
+        file_path,
+        index,
+        options,
+    } = params;
+    let options = options.unwrap_or_default();
+
+    let delimiter = match options.delimiter.as_deref() {
+        None | Some("") => b',',
+        Some("\\t") | Some("\t") => b'\t',
+        Some(d) if d.len() == 1 => d.as_bytes()[0],
+        Some(d) => {
+            return Err(format!(
+                "Invalid CSV delimiter '{}': expected a single character",
+                d
+            ))
+        }
+    };
+    let has_headers = options.has_headers.unwrap_or(true);
+    let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+    let infer_types = options.infer_types.unwrap_or(true);
+    let field_mapping = options.field_mapping.unwrap_or_default();
+    let column_kinds = if infer_types {
+        scan_column_kinds(&file_path, delimiter, has_headers)?
+    } else {
+        ColumnKinds::default()
+    };
+
+    let input = open_input(std::path::Path::new(&file_path))?;
+    let total_bytes = input.total_bytes;
+    let bytes_read = input.bytes_read.clone();
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_headers)
+        .from_reader(input.reader);
+
+    let columns: Vec<String> = if has_headers {
+        reader
+            .headers()
+            .map_err(|e| format!("Unable to read CSV header: {}", e))?
+            .iter()
+            .map(|h| h.trim().to_string())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut summary = ImportSummary::new(&index);
+    let mut batch: Vec<PendingDocument> = Vec::with_capacity(batch_size);
+    let mut record = csv::StringRecord::new();
+
+    // Every record read so far was counted as processed, so skipping that many lands
+    // right after the last batch that was sent
+    if let Some(position) = resume {
+        check_unchanged(&file_path, total_bytes, &position)?;
+        for _ in 0..position.summary.rows_processed {
+            match reader.read_record(&mut record) {
+                Ok(false) => break,
+                Ok(true) => {}
+                Err(e) if matches!(e.kind(), csv::ErrorKind::Io(_)) => {
+                    return Err(format!("Unable to read '{}': {}", file_path, e))
+                }
+                Err(_) => {}
+            }
+        }
+        if reader.position().byte() != position.offset {
+            return Err(format!(
+                "'{}' doesn't match its checkpoint anymore, start the import again",
+                file_path
+            ));
+        }
+        summary = position.summary;
+    }
+
+    loop {
+        let row = reader.position().line();
+
+        match reader.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {}
+            Err(e) => {
+                summary.rows_processed += 1;
+                summary.record_error(row, format!("Unable to parse row: {}", e));
+
+                // An I/O error won't go away on the next read, so stop here
+                if matches!(e.kind(), csv::ErrorKind::Io(_)) {
+                    break;
+                }
+                continue;
+            }
+        }
+
+        summary.rows_processed += 1;
+
+        let mut source = serde_json::Map::new();
+        let mut id = None;
+
+        for (position, raw) in record.iter().enumerate() {
+            let column = columns
+                .get(position)
+                .cloned()
+                .unwrap_or_else(|| format!("column_{}", position + 1));
+
+            if options.id_column.as_deref() == Some(column.as_str()) && !raw.trim().is_empty() {
+                id = Some(raw.trim().to_string());
+            }
+
+            let field = match field_mapping.get(&column) {
+                Some(field) if field.is_empty() => continue,
+                Some(field) => field.clone(),
+                None => column,
+            };
+
+            let value = if infer_types {
+                column_kinds.value(position, raw)
+            } else {
+                serde_json::Value::String(raw.to_string())
+            };
+            if !value.is_null() {
+                source.insert(field, value);
+            }
+        }
+
+        batch.push(PendingDocument {
+            row,
+            id,
+            source: serde_json::Value::Object(source),
+        });
+
+        if batch.len() >= batch_size {
+            flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+            let position = ImportPosition {
+                offset: reader.position().byte(),
+                row: reader.position().line(),
+                file_bytes: total_bytes,
+                summary: summary.clone(),
+            };
+            task.checkpoint(
+                "import_csv",
+                &conn.id,
+                &checkpoint_params,
+                serde_json::to_value(position).map_err(|e| e.to_string())?,
+            );
+            report_progress(
+                &app,
+                task,
+                summary.progress(
+                    &file_path,
+                    bytes_read.load(std::sync::atomic::Ordering::Relaxed),
+                    total_bytes,
+                    false,
+                ),
+            )?;
+        }
+    }
+
+    flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+    let _ = app.emit(
+        "import-progress",
+        summary.progress(&file_path, total_bytes, total_bytes, true),
+    );
+
+    println!(
+        "Imported {} of {} CSV rows into index: {}",
+        summary.rows_indexed, summary.rows_processed, index
+    );
+
+    Ok(summary)
+}
+
+#[command]
+pub async fn import_ndjson(
+    window: Window,
+    app: AppHandle,
+    file_path: String,
+    index: String,
+    batch_size: Option<usize>,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let task = TaskHandle::start(
+        &app,
+        "import",
+        format!("Import {} into {}", file_path, index),
+    );
+    let params = NdjsonImportParams {
+        file_path,
+        index,
+        batch_size,
+    };
+    let result = run_import_ndjson(&window, app, &task, params, None, timeout_ms).await;
+    task.finish(result)
+}
+
+pub(crate) async fn resume_import_ndjson(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    checkpoint: &TaskCheckpoint,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let (params, position) = read_checkpoint(checkpoint)?;
+    run_import_ndjson(window, app, task, params, Some(position), timeout_ms).await
+}
+
+async fn run_import_ndjson(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    params: NdjsonImportParams,
+    resume: Option<ImportPosition>,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+    let checkpoint_params = serde_json::to_value(&params).map_err(|e| e.to_string())?;
+    let NdjsonImportParams {
+        file_path,
+        index,
+        batch_size,
+    } = params;
+    let batch_size = batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+
+    let mut input = open_input(std::path::Path::new(&file_path))?;
+    let total_bytes = input.total_bytes;
+
+    let mut summary = ImportSummary::new(&index);
+    let mut batch: Vec<PendingDocument> = Vec::with_capacity(batch_size);
+    let mut batch_bytes = 0;
+    let max_bulk_bytes = app_settings().bulk_max_bytes;
+    let mut line = String::new();
+    let mut row = 0;
+    let mut offset = 0;
+
+    // Compressed input can't be seeked into, so the part already imported is read past instead
+    if let Some(position) = resume {
+        check_unchanged(&file_path, total_bytes, &position)?;
+        let skipped = std::io::copy(
+            &mut std::io::Read::take(&mut input.reader, position.offset),
+            &mut std::io::sink(),
+        )
+        .map_err(|e| format!("Unable to read '{}': {}", file_path, e))?;
+        if skipped != position.offset {
+            return Err(format!(
+                "'{}' is shorter than its checkpoint, start the import again",
+                file_path
+            ));
+        }
+        offset = position.offset;
+        row = position.row;
+        summary = position.summary;
+    }
+
+    // Each batch is sent and awaited before more of the file is read, so a slow
+    // cluster throttles the reader instead of the whole file piling up in memory
+    loop {
+        line.clear();
+        let read = input
+            .reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Unable to read '{}': {}", file_path, e))?;
+        if read == 0 {
+            break;
+        }
+
+        row += 1;
+        offset += read as u64;
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        summary.rows_processed += 1;
+
+        match serde_json::from_str::<serde_json::Value>(trimmed) {
+            Ok(source) if source.is_object() => {
+                batch_bytes += trimmed.len();
+                batch.push(PendingDocument {
+                    row,
+                    id: None,
+                    source,
+                });
+            }
+            Ok(_) => summary.record_error(row, "Line is not a JSON object".to_string()),
+            Err(e) => summary.record_error(row, format!("Invalid JSON: {}", e)),
+        }
+
+        if batch.len() >= batch_size || batch_bytes >= max_bulk_bytes {
+            flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+            batch_bytes = 0;
+            let position = ImportPosition {
+                offset,
+                row,
+                file_bytes: total_bytes,
+                summary: summary.clone(),
+            };
+            task.checkpoint(
+                "import_ndjson",
+                &conn.id,
+                &checkpoint_params,
+                serde_json::to_value(position).map_err(|e| e.to_string())?,
+            );
+            report_progress(
+                &app,
+                task,
+                summary.progress(&file_path, input.bytes_read(), total_bytes, false),
+            )?;
+        }
+    }
+
+    flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+    let _ = app.emit(
+        "import-progress",
+        summary.progress(&file_path, total_bytes, total_bytes, true),
+    );
+
+    println!(
+        "Imported {} of {} NDJSON documents into index: {}",
+        summary.rows_indexed, summary.rows_processed, index
+    );
+
+    Ok(summary)
+}
+
+// Hands array elements to the importer one at a time instead of building the whole array
+struct ArrayElementVisitor {
+    sender: tokio::sync::mpsc::Sender<Result<serde_json::Value, String>>,
+}
+
+impl<'de> serde::de::Visitor<'de> for ArrayElementVisitor {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a JSON array of documents")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(element) = seq.next_element::<serde_json::Value>()? {
+            // The importer stopped listening, there is no point parsing the rest
+            if self.sender.blocking_send(Ok(element)).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Resolves a (possibly dotted) field path to a string usable as a document id
+fn extract_id(source: &serde_json::Value, id_field: &str) -> Option<String> {
+    let value = id_field
+        .split('.')
+        .try_fold(source, |value, key| value.get(key))?;
+
+    match value {
+        serde_json::Value::String(s) if !s.is_empty() => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+#[command]
+pub async fn import_json_array(
+    window: Window,
+    app: AppHandle,
+    file_path: String,
+    index: String,
+    id_field: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let task = TaskHandle::start(
+        &app,
+        "import",
+        format!("Import {} into {}", file_path, index),
+    );
+    let result =
+        run_import_json_array(&window, app, &task, file_path, index, id_field, timeout_ms).await;
+    task.finish(result)
+}
+
+async fn run_import_json_array(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    file_path: String,
+    index: String,
+    id_field: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+
+    let input = open_input(std::path::Path::new(&file_path))?;
+    let total_bytes = input.total_bytes;
+    let bytes_read = input.bytes_read.clone();
+    let reader = input.reader;
+
+    // Parse on a blocking thread; the bounded channel keeps it from racing ahead of the cluster
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(DEFAULT_BATCH_SIZE);
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        let visitor = ArrayElementVisitor {
+            sender: sender.clone(),
+        };
+        if let Err(e) = serde::Deserializer::deserialize_seq(&mut deserializer, visitor) {
+            let _ = sender.blocking_send(Err(format!("Invalid JSON array: {}", e)));
+        }
+    });
+
+    let mut summary = ImportSummary::new(&index);
+    let mut batch: Vec<PendingDocument> = Vec::with_capacity(DEFAULT_BATCH_SIZE);
+    let mut batch_bytes = 0;
+    let max_bulk_bytes = app_settings().bulk_max_bytes;
+    let mut row = 0;
+
+    while let Some(element) = receiver.recv().await {
+        row += 1;
+
+        let source = match element {
+            Ok(source) => source,
+            Err(e) => {
+                // The parser can't recover from malformed JSON, so this ends the import
+                summary.record_error(row, e);
+                break;
+            }
+        };
+
+        summary.rows_processed += 1;
+
+        if !source.is_object() {
+            summary.record_error(row, "Array element is not a JSON object".to_string());
+            continue;
+        }
+
+        let id = id_field
+            .as_deref()
+            .and_then(|field| extract_id(&source, field));
+        if id_field.is_some() && id.is_none() {
+            summary.record_error(
+                row,
+                "Document has no usable value for the id field".to_string(),
+            );
+            continue;
+        }
+
+        batch_bytes += source.to_string().len();
+        batch.push(PendingDocument { row, id, source });
+
+        if batch.len() >= DEFAULT_BATCH_SIZE || batch_bytes >= max_bulk_bytes {
+            flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+            batch_bytes = 0;
+            report_progress(
+                &app,
+                task,
+                summary.progress(
+                    &file_path,
+                    bytes_read.load(std::sync::atomic::Ordering::Relaxed),
+                    total_bytes,
+                    false,
+                ),
+            )?;
+        }
+    }
+
+    flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+    let _ = app.emit(
+        "import-progress",
+        summary.progress(&file_path, total_bytes, total_bytes, true),
+    );
+
+    println!(
+        "Imported {} of {} JSON array documents into index: {}",
+        summary.rows_indexed, summary.rows_processed, index
+    );
+
+    Ok(summary)
+}
+
+// Creates the index with the given mapping, or adds the mapping to it if it already exists
+async fn ensure_index_mapping(
+    client: &ReqwestClient,
+    conn: &ElasticsearchConnection,
+    index: &str,
+    properties: serde_json::Value,
+    timeout_ms: Option<u64>,
+) -> Result<(), String> {
+    let transport = Transport::new(client.clone(), conn.clone()).with_timeout(timeout_ms);
+    let path = format!("/{}", encode_segment(index));
+
+    let exists = transport
+        .send(Method::HEAD, &path, None)
+        .await?
+        .status()
+        .is_success();
+
+    let response = if exists {
+        let body = serde_json::json!({ "properties": properties });
+        transport
+            .send(Method::PUT, &format!("{}/_mapping", path), Some(&body))
+            .await?
+    } else {
+        let body = serde_json::json!({ "mappings": { "properties": properties } });
+        transport.send(Method::PUT, &path, Some(&body)).await?
+    };
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string(),
+        };
+
+        Err(format!(
+            "Failed to prepare mapping for index '{}' - Status: {}, Response: {}",
+            index, status, error_text
+        ))
+    }
+}
+
+#[command]
+pub async fn import_geojson(
+    window: Window,
+    app: AppHandle,
+    file_path: String,
+    index: String,
+    geo_field: String,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let task = TaskHandle::start(
+        &app,
+        "import",
+        format!("Import {} into {}", file_path, index),
+    );
+    let result =
+        run_import_geojson(&window, app, &task, file_path, index, geo_field, timeout_ms).await;
+    task.finish(result)
+}
+
+// Calls `on_feature` for every feature of a FeatureCollection, streaming its `features` array,
+// or once for a single Feature or a bare geometry wrapped as one
+fn for_each_feature<R: std::io::Read>(
+    reader: R,
+    mut on_feature: impl FnMut(serde_json::Value) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    serde::Deserializer::deserialize_map(
+        &mut deserializer,
+        FeatureStream {
+            on_feature: &mut on_feature,
+        },
+    )
+    .and_then(|_| deserializer.end())
+    .map_err(|e| format!("Invalid GeoJSON: {}", e))
+}
+
+struct FeatureStream<'a, F> {
+    on_feature: &'a mut F,
+}
+
+impl<'de, F: FnMut(serde_json::Value) -> Result<(), String>> serde::de::Visitor<'de>
+    for FeatureStream<'_, F>
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a GeoJSON Feature, FeatureCollection, or geometry")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        use serde::de::Error;
+
+        // Everything but the features array is small, so it is kept to tell the kinds apart
+        let mut rest = serde_json::Map::new();
+        let mut streamed = false;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "features" {
+                map.next_value_seed(FeatureStream {
+                    on_feature: &mut *self.on_feature,
+                })?;
+                streamed = true;
+            } else {
+                rest.insert(key, map.next_value()?);
+            }
+        }
+        if streamed {
+            return Ok(());
+        }
+
+        let object = serde_json::Value::Object(rest);
+        let feature = match object["type"].as_str() {
+            Some("Feature") => object,
+            Some("FeatureCollection") => {
+                return Err(A::Error::custom("FeatureCollection has no features array"))
+            }
+            Some(_)
+                if object.get("coordinates").is_some() || object.get("geometries").is_some() =>
+            {
+                serde_json::json!({ "type": "Feature", "geometry": object, "properties": {} })
+            }
+            _ => {
+                return Err(A::Error::custom(
+                    "File is not a GeoJSON Feature, FeatureCollection, or geometry",
+                ))
+            }
+        };
+        (self.on_feature)(feature).map_err(A::Error::custom)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(feature) = seq.next_element::<serde_json::Value>()? {
+            (self.on_feature)(feature).map_err(serde::de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
+
+// The `features` array itself
+impl<'de, F: FnMut(serde_json::Value) -> Result<(), String>> serde::de::DeserializeSeed<'de>
+    for FeatureStream<'_, F>
+{
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+async fn run_import_geojson(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    file_path: String,
+    index: String,
+    geo_field: String,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+
+    // A first pass settles the geo type before anything is indexed, the second one sends the
+    // features; neither holds more than one feature in memory
+    let scan_path = file_path.clone();
+    let all_points = tokio::task::spawn_blocking(move || {
+        let input = open_input(std::path::Path::new(&scan_path))?;
+        let mut all_points = true;
+        for_each_feature(input.reader, |feature| {
+            all_points &= feature["geometry"]["type"].as_str() == Some("Point");
+            Ok(())
+        })?;
+        Ok::<bool, String>(all_points)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    // Points alone fit geo_point; anything else (lines, polygons, mixes) needs geo_shape
+    let geo_type = if all_points { "geo_point" } else { "geo_shape" };
+
+    ensure_index_mapping(
+        &client,
+        &conn,
+        &index,
+        serde_json::json!({ geo_field.clone(): { "type": geo_type } }),
+        timeout_ms,
+    )
+    .await?;
+
+    let input = open_input(std::path::Path::new(&file_path))?;
+    let total_bytes = input.total_bytes;
+    let bytes_read = input.bytes_read.clone();
+
+    // Parsing blocks, so it runs on its own thread and hands features over as they are read
+    let (sender, mut features) = tokio::sync::mpsc::channel(DEFAULT_BATCH_SIZE);
+    let parser = tokio::task::spawn_blocking(move || {
+        for_each_feature(input.reader, |feature| {
+            sender
+                .blocking_send(feature)
+                .map_err(|_| "Import stopped".to_string())
+        })
+    });
+
+    let mut summary = ImportSummary::new(&index);
+    let mut batch: Vec<PendingDocument> = Vec::with_capacity(DEFAULT_BATCH_SIZE);
+    let mut batch_bytes = 0;
+    let max_bulk_bytes = app_settings().bulk_max_bytes;
+    let mut row = 0;
+
+    while let Some(feature) = features.recv().await {
+        row += 1;
+        summary.rows_processed += 1;
+
+        let geometry = &feature["geometry"];
+        if geometry.is_null() {
+            summary.record_error(row, "Feature has no geometry".to_string());
+            continue;
+        }
+
+        // geo_point takes [lon, lat] directly; geo_shape understands GeoJSON geometries as-is
+        let geo_value = if all_points {
+            geometry["coordinates"].clone()
+        } else {
+            geometry.clone()
+        };
+
+        let mut source = match &feature["properties"] {
+            serde_json::Value::Object(properties) => properties.clone(),
+            _ => serde_json::Map::new(),
+        };
+        source.insert(geo_field.clone(), geo_value);
+
+        let id = match &feature["id"] {
+            serde_json::Value::String(id) => Some(id.clone()),
+            serde_json::Value::Number(id) => Some(id.to_string()),
+            _ => None,
+        };
+
+        let source = serde_json::Value::Object(source);
+        batch_bytes += source.to_string().len();
+        batch.push(PendingDocument { row, id, source });
+
+        if batch.len() >= DEFAULT_BATCH_SIZE || batch_bytes >= max_bulk_bytes {
+            flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+            batch_bytes = 0;
+            report_progress(
+                &app,
+                task,
+                summary.progress(
+                    &file_path,
+                    bytes_read.load(std::sync::atomic::Ordering::Relaxed),
+                    total_bytes,
+                    false,
+                ),
+            )?;
+        }
+    }
+    parser.await.map_err(|e| e.to_string())??;
+
+    flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+    let _ = app.emit(
+        "import-progress",
+        summary.progress(&file_path, total_bytes, total_bytes, true),
+    );
+
+    println!(
+        "Imported {} of {} GeoJSON features into index: {} ({})",
+        summary.rows_indexed, summary.rows_processed, index, geo_type
+    );
+
+    Ok(summary)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferredField {
+    pub path: String,
+    pub field_type: String,
+    pub date_format: Option<String>,
+    pub occurrences: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPreview {
+    pub file_format: String,
+    pub rows_sampled: u64,
+    pub fields: Vec<InferredField>,
+    pub mapping: serde_json::Value,
+    pub sample_rows: Vec<serde_json::Value>,
+}
+
+#[derive(Default)]
+struct FieldObservation {
+    kinds: std::collections::BTreeSet<&'static str>,
+    date_formats: std::collections::BTreeSet<&'static str>,
+    max_length: usize,
+    has_whitespace: bool,
+    occurrences: u64,
+}
+
+// Collects at most `limit` elements of a JSON array, ignoring the rest of the file
+struct SampleVisitor<'a> {
+    samples: &'a mut Vec<serde_json::Value>,
+    limit: usize,
+}
+
+impl<'de> serde::de::Visitor<'de> for SampleVisitor<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a JSON array of documents")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while self.samples.len() < self.limit {
+            match seq.next_element::<serde_json::Value>()? {
+                Some(element) => self.samples.push(element),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Recognises the common date layouts by their digit/separator shape
+fn detect_date_format(value: &str) -> Option<&'static str> {
+    let shape: String = value
+        .chars()
+        .map(|c| if c.is_ascii_digit() { 'd' } else { c })
+        .collect();
+
+    if shape.starts_with("dddd-dd-ddTdd:dd") {
+        return Some("strict_date_optional_time");
+    }
+
+    match shape.as_str() {
+        "dddd-dd-dd" => Some("strict_date"),
+        "dddd-dd-dd dd:dd:dd" => Some("yyyy-MM-dd HH:mm:ss"),
+        "dddd/dd/dd" => Some("yyyy/MM/dd"),
+        "dddd/dd/dd dd:dd:dd" => Some("yyyy/MM/dd HH:mm:ss"),
+        // Day-first only when the first part can't be a month
+        "dd/dd/dddd" => match value[..2].parse::<u32>() {
+            Ok(first) if first > 12 => Some("dd/MM/yyyy"),
+            _ => Some("MM/dd/yyyy"),
+        },
+        _ => None,
+    }
+}
+
+fn observe_value(
+    observations: &mut std::collections::BTreeMap<String, FieldObservation>,
+    path: &str,
+    value: &serde_json::Value,
+) {
+    match value {
+        serde_json::Value::Null => {}
+        serde_json::Value::Object(object) => {
+            for (key, child) in object {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                observe_value(observations, &child_path, child);
+            }
+        }
+        // ES has no array type, a field simply holds one or more values of the element type
+        serde_json::Value::Array(values) => {
+            for child in values {
+                observe_value(observations, path, child);
+            }
+        }
+        _ => {
+            let observation = observations.entry(path.to_string()).or_default();
+            observation.occurrences += 1;
+
+            match value {
+                serde_json::Value::Bool(_) => {
+                    observation.kinds.insert("boolean");
+                }
+                serde_json::Value::Number(n) if n.is_f64() => {
+                    observation.kinds.insert("double");
+                }
+                serde_json::Value::Number(_) => {
+                    observation.kinds.insert("long");
+                }
+                serde_json::Value::String(s) => match detect_date_format(s) {
+                    Some(format) => {
+                        observation.kinds.insert("date");
+                        observation.date_formats.insert(format);
+                    }
+                    None => {
+                        observation.kinds.insert("string");
+                        observation.max_length = observation.max_length.max(s.len());
+                        observation.has_whitespace |= s.contains(char::is_whitespace);
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+// Picks one ES type that can hold every value seen for the field
+fn resolve_field_type(observation: &FieldObservation) -> (&'static str, Option<String>) {
+    let kinds = &observation.kinds;
+
+    if kinds.len() == 1 && kinds.contains("date") {
+        // Several layouts can coexist in one field via the || format syntax
+        let format = observation
+            .date_formats
+            .iter()
+            .copied()
+            .collect::<Vec<_>>()
+            .join("||");
+        return ("date", Some(format));
+    }
+
+    if kinds.iter().all(|k| *k == "long") {
+        return ("long", None);
+    }
+
+    if kinds.iter().all(|k| *k == "long" || *k == "double") {
+        return ("double", None);
+    }
+
+    if kinds.len() == 1 && kinds.contains("boolean") {
+        return ("boolean", None);
+    }
+
+    // Free text gets analysed; short identifier-like strings stay exact-match keywords
+    if observation.has_whitespace || observation.max_length > 256 {
+        ("text", None)
+    } else {
+        ("keyword", None)
+    }
+}
+
+fn build_mapping(fields: &[InferredField]) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+
+    for field in fields {
+        let mut definition = serde_json::json!({ "type": field.field_type });
+        if let Some(format) = &field.date_format {
+            definition["format"] = serde_json::Value::String(format.clone());
+        }
+        if field.field_type == "text" {
+            definition["fields"] = serde_json::json!({
+                "keyword": { "type": "keyword", "ignore_above": 256 }
+            });
+        }
+
+        // Dotted paths become nested object properties
+        let mut parts = field.path.split('.').peekable();
+        let mut current = &mut properties;
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                current.insert(part.to_string(), definition.clone());
+            } else {
+                let entry = current
+                    .entry(part.to_string())
+                    .or_insert_with(|| serde_json::json!({ "properties": {} }));
+                current = match entry["properties"].as_object_mut() {
+                    Some(children) => children,
+                    None => break,
+                };
+            }
+        }
+    }
+
+    serde_json::json!({ "properties": properties })
+}
+
+fn sample_file(
+    file_path: &str,
+    file_format: &str,
+    sample_size: usize,
+) -> Result<Vec<serde_json::Value>, String> {
+    let open = || open_input(std::path::Path::new(file_path)).map(|input| input.reader);
+
+    match file_format {
+        "csv" | "tsv" => {
+            let delimiter = if file_format == "tsv" { b'\t' } else { b',' };
+            let mut reader = csv::ReaderBuilder::new()
+                .delimiter(delimiter)
+                .from_reader(open()?);
+            let columns: Vec<String> = reader
+                .headers()
+                .map_err(|e| format!("Unable to read CSV header: {}", e))?
+                .iter()
+                .map(|h| h.trim().to_string())
+                .collect();
+
+            let mut records = Vec::new();
+            let mut column_kinds = ColumnKinds::default();
+            for record in reader.records().take(sample_size) {
+                let record = record.map_err(|e| format!("Unable to parse row: {}", e))?;
+                column_kinds.observe(&record);
+                records.push(record);
+            }
+
+            Ok(records
+                .iter()
+                .map(|record| {
+                    let row: serde_json::Map<String, serde_json::Value> = columns
+                        .iter()
+                        .zip(record.iter())
+                        .enumerate()
+                        .map(|(position, (column, raw))| {
+                            (column.clone(), column_kinds.value(position, raw))
+                        })
+                        .filter(|(_, value)| !value.is_null())
+                        .collect();
+                    serde_json::Value::Object(row)
+                })
+                .collect())
+        }
+        "ndjson" => {
+            let reader = open()?;
+            let mut rows = Vec::new();
+            for line in std::io::BufRead::lines(reader) {
+                let line = line.map_err(|e| e.to_string())?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                rows.push(serde_json::from_str(&line).map_err(|e| format!("Invalid JSON: {}", e))?);
+                if rows.len() >= sample_size {
+                    break;
+                }
+            }
+            Ok(rows)
+        }
+        "geojson" => {
+            let mut features = Vec::new();
+            let sampled = for_each_feature(open()?, |feature| {
+                features.push(feature);
+                if features.len() >= sample_size {
+                    // Stops reading, the rest of the file isn't needed
+                    Err("sampled".to_string())
+                } else {
+                    Ok(())
+                }
+            });
+            if features.len() < sample_size {
+                sampled?;
+            }
+
+            // Show features the way import_geojson would index them
+            Ok(features
+                .into_iter()
+                .map(|feature| {
+                    let mut row = feature["properties"].clone();
+                    if !row.is_object() {
+                        row = serde_json::json!({});
+                    }
+                    row["geometry"] = feature["geometry"].clone();
+                    row
+                })
+                .collect())
+        }
+        _ => {
+            let mut rows = Vec::new();
+            let mut deserializer = serde_json::Deserializer::from_reader(open()?);
+            let visitor = SampleVisitor {
+                samples: &mut rows,
+                limit: sample_size,
+            };
+
+            // Stopping early leaves the array unterminated, which is only an error if nothing was read
+            if let Err(e) = serde::Deserializer::deserialize_seq(&mut deserializer, visitor) {
+                if rows.is_empty() {
+                    return Err(format!("Invalid JSON array: {}", e));
+                }
+            }
+            Ok(rows)
+        }
+    }
+}
+
+#[command]
+pub async fn preview_import(
+    file_path: String,
+    sample_size: Option<usize>,
+) -> Result<ImportPreview, String> {
+    let sample_size = sample_size.unwrap_or(100).max(1);
+
+    // Look past a trailing .gz to the real format
+    let extension = std::path::Path::new(file_path.trim_end_matches(".gz"))
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    let file_format = match extension.as_str() {
+        "csv" => "csv",
+        "tsv" => "tsv",
+        "ndjson" | "jsonl" => "ndjson",
+        "geojson" => "geojson",
+        "json" => "json",
+        _ => return Err(format!("Unsupported import file type '.{}'", extension)),
+    };
+
+    let rows = sample_file(&file_path, file_format, sample_size)?;
+
+    let mut observations = std::collections::BTreeMap::new();
+    for row in &rows {
+        observe_value(&mut observations, "", row);
+    }
+
+    // GeoJSON geometries are mapped as a whole below, not field by field
+    let fields: Vec<InferredField> = observations
+        .iter()
+        .filter(|(path, _)| file_format != "geojson" || !path.starts_with("geometry."))
+        .map(|(path, observation)| {
+            let (field_type, date_format) = resolve_field_type(observation);
+            InferredField {
+                path: path.clone(),
+                field_type: field_type.to_string(),
+                date_format,
+                occurrences: observation.occurrences,
+            }
+        })
+        .collect();
+
+    let mut mapping = build_mapping(&fields);
+
+    if file_format == "geojson" {
+        let all_points = rows
+            .iter()
+            .all(|row| row["geometry"]["type"].as_str() == Some("Point"));
+        mapping["properties"]["geometry"] = serde_json::json!({
+            "type": if all_points { "geo_point" } else { "geo_shape" }
+        });
+    }
+
+    Ok(ImportPreview {
+        file_format: file_format.to_string(),
+        rows_sampled: rows.len() as u64,
+        fields,
+        mapping,
+        sample_rows: rows,
+    })
+}
+
+#[command]
+pub async fn import_index(
+    window: Window,
+    app: AppHandle,
+    dir: String,
+    new_name: String,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let task = TaskHandle::start(&app, "import", format!("Restore {} as {}", dir, new_name));
+    let result = run_import_index(&window, app, &task, dir, new_name, timeout_ms).await;
+    task.finish(result)
+}
+
+async fn run_import_index(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    dir: String,
+    new_name: String,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+    let dir_path = std::path::Path::new(&dir);
+
+    let read_json = |name: &str| -> Result<serde_json::Value, String> {
+        let contents = std::fs::read_to_string(dir_path.join(name))
+            .map_err(|e| format!("Unable to read {}: {}", name, e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid {}: {}", name, e))
+    };
+    let mappings = read_json("mapping.json")?;
+    let settings = read_json("settings.json")?;
+
+    // Create the index up front so documents land in the exported mapping, not a dynamic one
+    let path = format!("/{}", encode_segment(&new_name));
+    let body = serde_json::json!({ "settings": settings, "mappings": mappings });
+    let response = Transport::new(client.clone(), conn.clone())
+        .with_timeout(timeout_ms)
+        .send(Method::PUT, &path, Some(&body))
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string(),
+        };
+
+        return Err(format!(
+            "Failed to create index - Status: {}, Response: {}",
+            status, error_text
+        ));
+    }
+
+    // Compressed exports write docs.ndjson.gz instead
+    let docs_path = ["docs.ndjson", "docs.ndjson.gz"]
+        .iter()
+        .map(|name| dir_path.join(name))
+        .find(|path| path.exists())
+        .ok_or_else(|| format!("No docs.ndjson found in '{}'", dir))?;
+    let file_path = docs_path.to_string_lossy().to_string();
+    let input = open_input(&docs_path)?;
+    let total_bytes = input.total_bytes;
+    let bytes_read = input.bytes_read.clone();
+
+    let mut summary = ImportSummary::new(&new_name);
+    let mut batch: Vec<PendingDocument> = Vec::with_capacity(DEFAULT_BATCH_SIZE);
+    let mut batch_bytes = 0;
+    let max_bulk_bytes = app_settings().bulk_max_bytes;
+
+    for (position, line) in input.reader.lines().enumerate() {
+        let row = position as u64 + 1;
+        let line = line.map_err(|e| format!("Unable to read '{}': {}", file_path, e))?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        summary.rows_processed += 1;
+
+        let mut entry: serde_json::Value = match serde_json::from_str(&line) {
+            Ok(entry) => entry,
+            Err(e) => {
+                summary.record_error(row, format!("Invalid JSON: {}", e));
+                continue;
+            }
+        };
+
+        batch_bytes += line.len();
+        batch.push(PendingDocument {
+            row,
+            id: entry["_id"].as_str().map(|id| id.to_string()),
+            source: entry["_source"].take(),
+        });
+
+        if batch.len() >= DEFAULT_BATCH_SIZE || batch_bytes >= max_bulk_bytes {
+            flush_batch(
+                &client,
+                &conn,
+                &new_name,
+                &mut batch,
+                &mut summary,
+                timeout_ms,
+            )
+            .await?;
+            batch_bytes = 0;
+            report_progress(
+                &app,
+                task,
+                summary.progress(
+                    &file_path,
+                    bytes_read.load(std::sync::atomic::Ordering::Relaxed),
+                    total_bytes,
+                    false,
+                ),
+            )?;
+        }
+    }
+
+    flush_batch(
+        &client,
+        &conn,
+        &new_name,
+        &mut batch,
+        &mut summary,
+        timeout_ms,
+    )
+    .await?;
+    let _ = app.emit(
+        "import-progress",
+        summary.progress(&file_path, total_bytes, total_bytes, true),
+    );
+
+    println!(
+        "Restored {} of {} documents from {} into index: {}",
+        summary.rows_indexed, summary.rows_processed, dir, new_name
+    );
+
+    Ok(summary)
+}
+
+const MAX_GENERATED_DOCUMENTS: u64 = 10_000_000;
+const DEFAULT_GENERATED_DATE_RANGE_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+
+const FIRST_NAMES: &[&str] = &[
+    "Ada",
+    "Alan",
+    "Grace",
+    "Linus",
+    "Margaret",
+    "Dennis",
+    "Barbara",
+    "Ken",
+    "Frances",
+    "Edsger",
+    "Radia",
+    "Donald",
+    "Katherine",
+    "John",
+    "Sophie",
+    "Tim",
+    "Hedy",
+    "Guido",
+    "Anita",
+    "Bjarne",
+];
+const LAST_NAMES: &[&str] = &[
+    "Lovelace",
+    "Turing",
+    "Hopper",
+    "Torvalds",
+    "Hamilton",
+    "Ritchie",
+    "Liskov",
+    "Thompson",
+    "Allen",
+    "Dijkstra",
+    "Perlman",
+    "Knuth",
+    "Johnson",
+    "McCarthy",
+    "Wilson",
+    "Berners-Lee",
+    "Lamarr",
+    "van Rossum",
+    "Borg",
+    "Stroustrup",
+];
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net", "test.dev"];
+const WORDS: &[&str] = &[
+    "alpha", "bravo", "cluster", "delta", "engine", "feature", "gateway", "harbor", "index",
+    "journal", "kernel", "lambda", "matrix", "node", "orbit", "pipeline", "query", "replica",
+    "shard", "token", "update", "vector", "window", "xenon", "yield", "zone",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestDataField {
+    // Dotted names ("user.email") end up as object fields
+    pub name: String,
+    // "name", "first_name", "last_name", "email", "timestamp", "geo_point", "enum", "integer",
+    // "float", "boolean", "uuid", "text", "keyword" or "ip"
+    pub kind: String,
+    // Choices for "enum"
+    pub values: Option<Vec<serde_json::Value>>,
+    // Range for numbers, or epoch millis for timestamps (defaults to the last 30 days)
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+// xorshift64*, good enough for fake data and reproducible from a seed
+struct TestDataRng(u64);
+
+impl TestDataRng {
+    fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0x2545_f491_4f6c_dd1d)
+        });
+        // A zero state would only ever produce zeros
+        TestDataRng(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + self.unit() * (max - min)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[(self.next_u64() % items.len() as u64) as usize]
+    }
+}
+
+fn test_data_mapping_type(kind: &str) -> Result<&'static str, String> {
+    match kind {
+        "name" | "text" => Ok("text"),
+        "first_name" | "last_name" | "email" | "enum" | "uuid" | "keyword" => Ok("keyword"),
+        "timestamp" => Ok("date"),
+        "geo_point" => Ok("geo_point"),
+        "integer" => Ok("long"),
+        "float" => Ok("double"),
+        "boolean" => Ok("boolean"),
+        "ip" => Ok("ip"),
+        other => Err(format!("Unknown test data kind '{}'", other)),
+    }
+}
+
+fn generate_value(field: &TestDataField, rng: &mut TestDataRng) -> serde_json::Value {
+    match field.kind.as_str() {
+        "name" => serde_json::json!(format!(
+            "{} {}",
+            rng.pick(FIRST_NAMES),
+            rng.pick(LAST_NAMES)
+        )),
+        "first_name" => serde_json::json!(rng.pick(FIRST_NAMES)),
+        "last_name" => serde_json::json!(rng.pick(LAST_NAMES)),
+        "email" => {
+            let local = format!(
+                "{}.{}{}",
+                rng.pick(FIRST_NAMES),
+                rng.pick(LAST_NAMES),
+                rng.next_u64() % 1000
+            )
+            .to_lowercase()
+            .replace([' ', '-'], "");
+            serde_json::json!(format!("{}@{}", local, rng.pick(EMAIL_DOMAINS)))
+        }
+        "timestamp" => {
+            let now = chrono::Utc::now().timestamp_millis();
+            let min = field
+                .min
+                .unwrap_or((now - DEFAULT_GENERATED_DATE_RANGE_MS) as f64);
+            let max = field.max.unwrap_or(now as f64);
+            let millis = rng.range(min, max) as i64;
+            chrono::DateTime::from_timestamp_millis(millis)
+                .map(|t| serde_json::json!(t.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)))
+                .unwrap_or(serde_json::Value::Null)
+        }
+        "geo_point" => serde_json::json!({
+            "lat": (rng.range(-90.0, 90.0) * 1e6).round() / 1e6,
+            "lon": (rng.range(-180.0, 180.0) * 1e6).round() / 1e6
+        }),
+        "enum" => field
+            .values
+            .as_deref()
+            .filter(|values| !values.is_empty())
+            .map(|values| rng.pick(values).clone())
+            .unwrap_or(serde_json::Value::Null),
+        "integer" => {
+            let min = field.min.unwrap_or(0.0).floor();
+            let max = field.max.unwrap_or(1000.0).floor();
+            serde_json::json!(rng.range(min, max + 1.0).floor().min(max) as i64)
+        }
+        "float" => {
+            let value = rng.range(field.min.unwrap_or(0.0), field.max.unwrap_or(1000.0));
+            serde_json::json!((value * 100.0).round() / 100.0)
+        }
+        "boolean" => serde_json::json!(rng.unit() < 0.5),
+        "uuid" => {
+            let (high, low) = (rng.next_u64(), rng.next_u64());
+            serde_json::json!(format!(
+                "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+                high >> 32,
+                (high >> 16) & 0xffff,
+                high & 0x0fff,
+                ((low >> 48) & 0x3fff) | 0x8000,
+                low & 0xffff_ffff_ffff
+            ))
+        }
+        "text" => {
+            let words = 5 + rng.next_u64() % 11;
+            let sentence: Vec<&str> = (0..words).map(|_| *rng.pick(WORDS)).collect();
+            serde_json::json!(sentence.join(" "))
+        }
+        "keyword" => serde_json::json!(rng.pick(WORDS)),
+        "ip" => serde_json::json!(format!(
+            "10.{}.{}.{}",
+            rng.next_u64() % 256,
+            rng.next_u64() % 256,
+            1 + rng.next_u64() % 254
+        )),
+        _ => serde_json::Value::Null,
+    }
+}
+
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_test_data(
+    window: Window,
+    app: AppHandle,
+    index: String,
+    schema: Vec<TestDataField>,
+    count: u64,
+    create_mapping: Option<bool>,
+    seed: Option<u64>,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let task = TaskHandle::start(
+        &app,
+        "import",
+        format!("Generate {} test documents into {}", count, index),
+    );
+    let result = run_generate_test_data(
+        &window,
+        app,
+        &task,
+        index,
+        schema,
+        count,
+        create_mapping,
+        seed,
+        timeout_ms,
+    )
+    .await;
+    task.finish(result)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_generate_test_data(
+    window: &Window,
+    app: AppHandle,
+    task: &TaskHandle,
+    index: String,
+    schema: Vec<TestDataField>,
+    count: u64,
+    create_mapping: Option<bool>,
+    seed: Option<u64>,
+    timeout_ms: Option<u64>,
+) -> Result<ImportSummary, String> {
+    let (conn, client) = current_connection(window.label())?;
+
+    if schema.is_empty() {
+        return Err("The schema needs at least one field".to_string());
+    }
+    if count == 0 || count > MAX_GENERATED_DOCUMENTS {
+        return Err(format!(
+            "count must be between 1 and {}",
+            MAX_GENERATED_DOCUMENTS
+        ));
+    }
+
+    // Validate every field before anything is written
+    let mut properties = serde_json::Map::new();
+    for field in &schema {
+        let field_type = test_data_mapping_type(&field.kind)?;
+        if field.kind == "enum" && field.values.as_ref().is_none_or(|v| v.is_empty()) {
+            return Err(format!(
+                "Enum field '{}' needs at least one value",
+                field.name
+            ));
+        }
+        properties.insert(
+            field.name.clone(),
+            serde_json::json!({ "type": field_type }),
+        );
+    }
+
+    if create_mapping.unwrap_or(true) {
+        ensure_index_mapping(
+            &client,
+            &conn,
+            &index,
+            serde_json::Value::Object(properties),
+            timeout_ms,
+        )
+        .await?;
+    }
+
+    let mut rng = TestDataRng::new(seed);
+    let mut summary = ImportSummary::new(&index);
+    let mut batch: Vec<PendingDocument> = Vec::with_capacity(DEFAULT_BATCH_SIZE);
+    let label = format!("generated:{}", index);
+
+    for row in 1..=count {
+        let source: serde_json::Map<String, serde_json::Value> = schema
+            .iter()
+            .map(|field| (field.name.clone(), generate_value(field, &mut rng)))
+            .collect();
+        batch.push(PendingDocument {
+            row,
+            id: None,
+            source: serde_json::Value::Object(source),
+        });
+        summary.rows_processed += 1;
+
+        if batch.len() >= DEFAULT_BATCH_SIZE || row == count {
+            flush_batch(&client, &conn, &index, &mut batch, &mut summary, timeout_ms).await?;
+            // No file here, so the byte counters track documents instead
+            report_progress(
+                &app,
+                task,
+                summary.progress(&label, row, count, row == count),
+            )?;
+        }
+    }
+
+    println!(
+        "Generated {} of {} test documents into index: {}",
+        summary.rows_indexed, summary.rows_processed, index
+    );
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn infers_cell_values() {
+        assert_eq!(infer_value(""), json!(null));
+        assert_eq!(infer_value("  "), json!(null));
+        assert_eq!(infer_value("42"), json!(42));
+        assert_eq!(infer_value("-7"), json!(-7));
+        assert_eq!(infer_value("18446744073709551615"), json!(u64::MAX));
+        assert_eq!(
+            infer_value("99999999999999999999"),
+            json!("99999999999999999999")
+        );
+        assert_eq!(infer_value("3.25"), json!(3.25));
+        assert_eq!(infer_value("TRUE"), json!(true));
+        assert_eq!(infer_value("false"), json!(false));
+        assert_eq!(infer_value("NaN"), json!("NaN"));
+        assert_eq!(infer_value("hello"), json!("hello"));
+    }
+
+    #[test]
+    fn leading_zeros_stay_text() {
+        assert_eq!(infer_value("007"), json!("007"));
+        assert_eq!(infer_value("-01"), json!("-01"));
+        assert_eq!(infer_value("00.5"), json!("00.5"));
+        assert_eq!(infer_value("0"), json!(0));
+        assert_eq!(infer_value("0.5"), json!(0.5));
+        assert_eq!(infer_value("-0.5"), json!(-0.5));
+    }
+
+    #[test]
+    fn mixed_columns_stay_text() {
+        let mut kinds = ColumnKinds::default();
+        kinds.observe(&csv::StringRecord::from(vec!["123", "1", "true", "x"]));
+        kinds.observe(&csv::StringRecord::from(vec!["007", "", "false", "2"]));
+
+        assert_eq!(kinds.value(0, "123"), json!("123"));
+        assert_eq!(kinds.value(1, "1"), json!(1));
+        assert_eq!(kinds.value(1, ""), json!(null));
+        assert_eq!(kinds.value(2, "true"), json!(true));
+        assert_eq!(kinds.value(3, "2"), json!("2"));
+        // Columns past the scanned ones infer on their own
+        assert_eq!(kinds.value(4, "5"), json!(5));
+    }
+
+    #[test]
+    fn date_formats() {
+        assert_eq!(
+            detect_date_format("2024-01-31T10:15:00Z"),
+            Some("strict_date_optional_time")
+        );
+        assert_eq!(detect_date_format("2024-01-31"), Some("strict_date"));
+        assert_eq!(
+            detect_date_format("2024-01-31 10:15:00"),
+            Some("yyyy-MM-dd HH:mm:ss")
+        );
+        assert_eq!(detect_date_format("2024/01/31"), Some("yyyy/MM/dd"));
+        assert_eq!(detect_date_format("31/01/2024"), Some("dd/MM/yyyy"));
+        assert_eq!(detect_date_format("01/31/2024"), Some("MM/dd/yyyy"));
+        assert_eq!(detect_date_format("2024"), None);
+        assert_eq!(detect_date_format("not a date"), None);
+    }
+
+    fn observe(values: &[serde_json::Value]) -> FieldObservation {
+        let mut observations = std::collections::BTreeMap::new();
+        for value in values {
+            observe_value(&mut observations, "field", value);
+        }
+        observations.remove("field").unwrap_or_default()
+    }
+
+    #[test]
+    fn field_types() {
+        assert_eq!(
+            resolve_field_type(&observe(&[json!(1), json!(2)])),
+            ("long", None)
+        );
+        assert_eq!(
+            resolve_field_type(&observe(&[json!(1), json!(2.5)])),
+            ("double", None)
+        );
+        assert_eq!(
+            resolve_field_type(&observe(&[json!(true)])),
+            ("boolean", None)
+        );
+        assert_eq!(
+            resolve_field_type(&observe(&[json!("2024-01-31"), json!("2024/01/31")])),
+            ("date", Some("strict_date||yyyy/MM/dd".to_string()))
+        );
+        assert_eq!(
+            resolve_field_type(&observe(&[json!("abc-123")])),
+            ("keyword", None)
+        );
+        assert_eq!(
+            resolve_field_type(&observe(&[json!("some words")])),
+            ("text", None)
+        );
+        assert_eq!(
+            resolve_field_type(&observe(&[json!(1), json!("abc")])),
+            ("keyword", None)
+        );
+        assert_eq!(
+            resolve_field_type(&observe(&[json!([1, 2])])),
+            ("long", None)
+        );
+    }
+
+    fn features(geojson: &str) -> Result<Vec<serde_json::Value>, String> {
+        let mut features = Vec::new();
+        for_each_feature(geojson.as_bytes(), |feature| {
+            features.push(feature);
+            Ok(())
+        })?;
+        Ok(features)
+    }
+
+    #[test]
+    fn geojson_features() {
+        let collection = features(
+            r#"{"features": [{"type": "Feature", "id": 1}, {"type": "Feature", "id": 2}],
+                "type": "FeatureCollection", "name": "places"}"#,
+        )
+        .unwrap();
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection[1]["id"], json!(2));
+
+        let single = features(r#"{"type": "Feature", "geometry": null}"#).unwrap();
+        assert_eq!(single, vec![json!({ "type": "Feature", "geometry": null })]);
+
+        let geometry = features(r#"{"type": "Point", "coordinates": [1, 2]}"#).unwrap();
+        assert_eq!(geometry[0]["geometry"]["coordinates"], json!([1, 2]));
+        assert_eq!(geometry[0]["type"], json!("Feature"));
+
+        assert!(features(r#"{"type": "FeatureCollection"}"#).is_err());
+        assert!(features(r#"{"name": "nothing"}"#).is_err());
+        assert!(features(r#"{"type": "FeatureCollection", "features": [{}"#).is_err());
+        assert!(features(r#"[1, 2]"#).is_err());
+    }
+}
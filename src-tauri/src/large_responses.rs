@@ -0,0 +1,241 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/large_responses.rs
+
+use crate::transport::{cancellable, encode_segment, Transport};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{command, Window};
+
+const LARGEST_INDICES_IN_SUMMARY: usize = 10;
+
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+
+// Bodies spooled to disk, by handle. The files are removed on release or when the
+// connection they came from goes away.
+static LARGE_RESPONSES: Lazy<Mutex<HashMap<String, LargeResponse>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+struct LargeResponse {
+    connection_id: String,
+    file: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeResponseHandle {
+    pub handle: String,
+    // "mappings" or "nodes_stats"
+    pub endpoint: String,
+    pub bytes: u64,
+    pub summary: serde_json::Value,
+}
+
+fn endpoint_path(endpoint: &str, target: Option<&str>) -> Result<String, String> {
+    match endpoint {
+        "mappings" => Ok(format!(
+            "/{}/_mapping",
+            target
+                .map(encode_segment)
+                .unwrap_or_else(|| "_all".to_string())
+        )),
+        "nodes_stats" => Ok(match target {
+            Some(nodes) => format!("/_nodes/{}/stats", encode_segment(nodes)),
+            None => "/_nodes/stats".to_string(),
+        }),
+        other => Err(format!(
+            "Unsupported endpoint '{}', expected 'mappings' or 'nodes_stats'",
+            other
+        )),
+    }
+}
+
+fn count_fields(properties: &serde_json::Value) -> u64 {
+    properties
+        .as_object()
+        .into_iter()
+        .flat_map(|fields| fields.values())
+        .map(|field| {
+            1 + count_fields(&field["properties"])
+                + field["fields"]
+                    .as_object()
+                    .map_or(0, |multi| multi.len() as u64)
+        })
+        .sum()
+}
+
+fn summarize_mappings(body: &serde_json::Value) -> serde_json::Value {
+    let mut indices: Vec<(&String, u64)> = body
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(index, mapping)| (index, count_fields(&mapping["mappings"]["properties"])))
+        .collect();
+    indices.sort_by_key(|(_, fields)| std::cmp::Reverse(*fields));
+
+    serde_json::json!({
+        "indices": indices.len(),
+        "fields": indices.iter().map(|(_, fields)| fields).sum::<u64>(),
+        "largest": indices
+            .iter()
+            .take(LARGEST_INDICES_IN_SUMMARY)
+            .map(|(index, fields)| serde_json::json!({ "index": index, "fields": fields }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn summarize_nodes_stats(body: &serde_json::Value) -> serde_json::Value {
+    let nodes: Vec<serde_json::Value> = body["nodes"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(id, node)| {
+            serde_json::json!({
+                "id": id,
+                "name": node["name"],
+                "roles": node["roles"],
+                "heap_used_percent": node["jvm"]["mem"]["heap_used_percent"],
+                "cpu_percent": node["os"]["cpu"]["percent"],
+                "disk_total_bytes": node["fs"]["total"]["total_in_bytes"],
+                "disk_available_bytes": node["fs"]["total"]["available_in_bytes"],
+                "docs": node["indices"]["docs"]["count"],
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "cluster_name": body["cluster_name"],
+        "nodes": nodes,
+    })
+}
+
+fn spool_file(handle: &str) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join("elastiko");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir.join(format!("{}.json", handle)))
+}
+
+// Parses the spooled file off the async runtime; only the parsed value is ever held, never the text
+async fn parse_file(file: PathBuf) -> Result<serde_json::Value, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let reader = std::io::BufReader::new(
+            std::fs::File::open(&file)
+                .map_err(|e| format!("Failed to open {}: {}", file.display(), e))?,
+        );
+        serde_json::from_reader(reader).map_err(|e| format!("Invalid JSON response: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Removes the spooled files of every handle opened on the connection
+pub(crate) fn release_large_responses(connection_id: &str) {
+    let released: Vec<LargeResponse> = {
+        let mut responses = LARGE_RESPONSES.lock();
+        let handles: Vec<String> = responses
+            .iter()
+            .filter(|(_, response)| response.connection_id == connection_id)
+            .map(|(handle, _)| handle.clone())
+            .collect();
+        handles
+            .iter()
+            .filter_map(|handle| responses.remove(handle))
+            .collect()
+    };
+
+    for response in released {
+        let _ = std::fs::remove_file(&response.file);
+    }
+}
+
+// Streams a potentially huge response to a temp file and returns a handle with a summary.
+// `target` is an index pattern for "mappings" and a node filter for "nodes_stats". Passing a
+// request_id lets cancel_request stop the download part way.
+#[command]
+pub async fn fetch_large_response(
+    window: Window,
+    endpoint: String,
+    target: Option<String>,
+    request_id: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<LargeResponseHandle, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let path = endpoint_path(&endpoint, target.as_deref())?;
+
+    let handle = format!(
+        "{:x}-{}",
+        std::process::id(),
+        NEXT_HANDLE.fetch_add(1, Ordering::Relaxed)
+    );
+    let file = spool_file(&handle)?;
+
+    let bytes = match cancellable(request_id, transport.download(&path, &file)).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            let _ = std::fs::remove_file(&file);
+            return Err(format!("Failed to fetch {} - {}", endpoint, e));
+        }
+    };
+
+    let summary = match parse_file(file.clone()).await {
+        Ok(body) if endpoint == "mappings" => summarize_mappings(&body),
+        Ok(body) => summarize_nodes_stats(&body),
+        Err(e) => {
+            let _ = std::fs::remove_file(&file);
+            return Err(e);
+        }
+    };
+
+    LARGE_RESPONSES.lock().insert(
+        handle.clone(),
+        LargeResponse {
+            connection_id: transport.connection_id().to_string(),
+            file,
+        },
+    );
+
+    Ok(LargeResponseHandle {
+        handle,
+        endpoint,
+        bytes,
+        summary,
+    })
+}
+
+// Returns part of a spooled body by JSON pointer (e.g. "/logs-1/mappings"), or all of it
+#[command]
+pub async fn read_large_response(
+    handle: String,
+    pointer: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let file = LARGE_RESPONSES
+        .lock()
+        .get(&handle)
+        .map(|response| response.file.clone())
+        .ok_or_else(|| format!("Unknown or released response handle: {}", handle))?;
+
+    let mut body = parse_file(file).await?;
+
+    match pointer.as_deref() {
+        None | Some("") => Ok(body),
+        Some(pointer) => body
+            .pointer_mut(pointer)
+            .map(serde_json::Value::take)
+            .ok_or_else(|| format!("Nothing found at {} in the response", pointer)),
+    }
+}
+
+#[command]
+pub fn release_large_response(handle: String) -> Result<bool, String> {
+    let released = LARGE_RESPONSES.lock().remove(&handle);
+    match released {
+        Some(response) => {
+            let _ = std::fs::remove_file(&response.file);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -1,20 +1,121 @@
 // Elastiko - Elasticsearch GUI Client
 // elastico/src-tauri/src/lib.rs
 
+mod analysis;
+mod audit;
+mod bulk;
+mod cluster_config;
+mod confirmations;
+mod demo;
 mod elasticsearch;
+mod export;
+mod import;
+mod large_responses;
+mod monitoring;
+mod search_contexts;
+mod secrets;
+mod storage;
+mod sync;
+mod tasks;
+mod transport;
 
 use elasticsearch::{
-    connect_to_elasticsearch, create_elasticsearch_document, create_elasticsearch_index,
-    delete_all_documents_in_index, delete_elasticsearch_documents, delete_elasticsearch_index,
-    disconnect_from_elasticsearch, execute_elasticsearch_query, get_elasticsearch_cluster_health,
-    get_elasticsearch_index_mappings, get_elasticsearch_index_settings, get_elasticsearch_indices,
+    ack_watch, add_remote_cluster, add_voting_config_exclusions, change_user_password,
+    check_privileges, clear_voting_config_exclusions, clone_snapshot, compare_clusters,
+    connect_to_elasticsearch, create_api_key, create_elasticsearch_document,
+    create_elasticsearch_index, create_snapshot, debug_grok, delete_all_documents_in_index,
+    delete_auto_follow_pattern, delete_autoscaling_policy, delete_dangling_index,
+    delete_elasticsearch_documents, delete_elasticsearch_index, delete_logstash_pipeline,
+    delete_node_shutdown, delete_role, delete_role_mapping, delete_rollup_job, delete_runtime_field,
+    delete_snapshot, delete_snapshot_repository, delete_transform, delete_user, delete_watch,
+    disconnect_from_elasticsearch, execute_elasticsearch_query, execute_painless, execute_watch,
+    follow_index, get_autoscaling_capacity, get_autoscaling_policy, get_cat_nodes,
+    get_cluster_features, get_cluster_plugins, get_cluster_recovery, get_current_user_info,
+    get_deprecation_info, get_elasticsearch_cluster_health, get_elasticsearch_index_mappings,
+    get_elasticsearch_index_settings, get_elasticsearch_indices, get_fielddata_usage,
+    get_follower_stats, get_grok_patterns, get_health_report, get_license_info,
+    get_logstash_pipeline, get_node_allocation, get_node_shutdown_status, get_remote_cluster_info,
+    get_rest_action_usage, get_rollup_job, get_rollup_support, get_security_token, get_shard_map,
+    get_snapshot_details, get_snapshot_status, get_template_usage, get_thread_pool_stats,
+    get_transform_stats, get_voting_config_exclusions, get_watch, graph_explore,
+    import_dangling_index, invalidate_api_keys, invalidate_security_token, list_api_keys,
+    list_auto_follow_patterns, list_dangling_indices, list_follower_indices,
+    list_logstash_pipelines, list_role_mappings, list_roles, list_rollup_jobs,
+    list_snapshot_repositories, list_snapshots, list_transforms, list_users, list_watches,
+    pause_follow, preview_search_request, preview_transform, put_auto_follow_pattern,
+    put_autoscaling_policy, put_logstash_pipeline, put_node_shutdown, put_role, put_role_mapping,
+    put_rollup_job, put_runtime_field, put_transform, put_user, put_watch, query_api_keys,
+    refresh_security_token, register_snapshot_repository, remove_remote_cluster, reset_features,
+    restore_snapshot, resume_follow, rollup_search, search_with_runtime_fields,
+    set_auto_follow_pattern_active, set_user_enabled, set_watch_active, simulate_index_name,
+    start_basic, start_rollup_job, start_transform, start_trial, stop_rollup_job, stop_transform,
+    swap_alias, track_restore_progress, track_snapshot_progress, unfollow_index,
+    verify_snapshot_repository,
 };
+use import::{
+    generate_test_data, import_csv, import_geojson, import_index, import_json_array, import_ndjson,
+    preview_import,
+};
+use export::{
+    export_incremental, export_index, export_query_to_csv, export_query_to_ndjson,
+    export_query_to_parquet, format_hits,
+};
+use monitoring::{
+    check_disk_pressure, get_index_throughput, get_metrics_collector_status, get_metrics_range,
+    list_metric_names, set_cluster_notifications, start_metrics_collector, stop_metrics_collector,
+};
+use tasks::{
+    cancel_app_task, cancel_my_task, clear_finished_app_tasks, find_my_tasks, list_app_tasks,
+    reindex_with_transform, resume_task, start_delete_by_query_task, start_force_merge_task,
+    start_reindex_task, start_snapshot_task,
+};
+use transport::{
+    cancel_request, clear_response_cache, get_http_client_stats, get_request_queue_stats,
+    get_retry_policy, list_active_requests, set_retry_policy,
+};
+use storage::{
+    add_query_history, clear_query_history, clear_task_logs, delete_saved_connection,
+    delete_saved_query, delete_workspace, discard_task_checkpoint, get_app_settings,
+    list_export_watermarks, list_query_history, list_saved_connections, list_saved_queries,
+    list_task_checkpoints, list_task_logs, list_workspaces, restore_workspace, save_connection,
+    save_query, save_workspace, update_app_settings,
+};
+use demo::{get_demo_mode_status, start_demo_mode, stop_demo_mode};
+use analysis::{
+    analyze_index_quality, find_duplicates, get_autocomplete_bundle, get_field_stats,
+    get_flattened_fields, get_time_series, get_top_field_values, sample_documents,
+};
+use cluster_config::{export_cluster_config, import_cluster_config};
+use search_contexts::{
+    cleanup_search_contexts, close_point_in_time, list_search_contexts, open_point_in_time,
+};
+use confirmations::request_confirmation_token;
+use audit::{
+    audit_aliases, diff_index_against_template, find_stuck_ilm_indices, get_shard_balance_report,
+    retry_ilm_indices, verify_reindex,
+};
+use large_responses::{fetch_large_response, read_large_response, release_large_response};
+use sync::{list_sync_jobs, remove_sync_job, stop_sync_job, sync_index};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_http::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(|app| {
+            // Fall back to defaults rather than refusing to start over a settings problem
+            if let Err(e) = storage::load_app_settings(app.handle()) {
+                println!("Failed to load app settings: {}", e);
+            }
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            // Each window keeps its own connection, drop it once the window is gone
+            if let tauri::WindowEvent::Destroyed = event {
+                elasticsearch::forget_window_connection(window.label());
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             connect_to_elasticsearch,
             disconnect_from_elasticsearch,
@@ -27,7 +128,209 @@ pub fn run() {
             create_elasticsearch_index,
             create_elasticsearch_document,
             get_elasticsearch_index_mappings,
-            get_elasticsearch_index_settings
+            get_elasticsearch_index_settings,
+            get_node_allocation,
+            get_cat_nodes,
+            get_thread_pool_stats,
+            get_fielddata_usage,
+            get_cluster_plugins,
+            get_remote_cluster_info,
+            add_remote_cluster,
+            remove_remote_cluster,
+            get_voting_config_exclusions,
+            add_voting_config_exclusions,
+            clear_voting_config_exclusions,
+            put_node_shutdown,
+            get_node_shutdown_status,
+            delete_node_shutdown,
+            get_deprecation_info,
+            get_license_info,
+            start_trial,
+            start_basic,
+            get_health_report,
+            compare_clusters,
+            list_snapshot_repositories,
+            register_snapshot_repository,
+            verify_snapshot_repository,
+            delete_snapshot_repository,
+            create_snapshot,
+            restore_snapshot,
+            get_snapshot_status,
+            track_snapshot_progress,
+            track_restore_progress,
+            list_snapshots,
+            get_snapshot_details,
+            delete_snapshot,
+            clone_snapshot,
+            debug_grok,
+            get_grok_patterns,
+            import_csv,
+            import_ndjson,
+            import_json_array,
+            import_geojson,
+            preview_import,
+            list_logstash_pipelines,
+            get_logstash_pipeline,
+            put_logstash_pipeline,
+            delete_logstash_pipeline,
+            export_query_to_csv,
+            export_query_to_ndjson,
+            export_query_to_parquet,
+            export_index,
+            import_index,
+            format_hits,
+            create_api_key,
+            list_api_keys,
+            query_api_keys,
+            invalidate_api_keys,
+            list_users,
+            put_user,
+            change_user_password,
+            set_user_enabled,
+            delete_user,
+            list_roles,
+            put_role,
+            delete_role,
+            list_role_mappings,
+            put_role_mapping,
+            delete_role_mapping,
+            get_current_user_info,
+            check_privileges,
+            get_security_token,
+            refresh_security_token,
+            invalidate_security_token,
+            list_watches,
+            get_watch,
+            put_watch,
+            set_watch_active,
+            execute_watch,
+            ack_watch,
+            delete_watch,
+            start_metrics_collector,
+            stop_metrics_collector,
+            get_metrics_collector_status,
+            list_metric_names,
+            get_metrics_range,
+            set_cluster_notifications,
+            get_index_throughput,
+            check_disk_pressure,
+            list_app_tasks,
+            cancel_app_task,
+            clear_finished_app_tasks,
+            start_reindex_task,
+            start_delete_by_query_task,
+            start_force_merge_task,
+            start_snapshot_task,
+            get_shard_map,
+            get_retry_policy,
+            set_retry_policy,
+            list_active_requests,
+            cancel_request,
+            list_saved_connections,
+            save_connection,
+            delete_saved_connection,
+            add_query_history,
+            list_query_history,
+            clear_query_history,
+            list_saved_queries,
+            save_query,
+            delete_saved_query,
+            list_task_logs,
+            clear_task_logs,
+            get_app_settings,
+            update_app_settings,
+            clear_response_cache,
+            get_request_queue_stats,
+            save_workspace,
+            list_workspaces,
+            restore_workspace,
+            delete_workspace,
+            get_demo_mode_status,
+            start_demo_mode,
+            stop_demo_mode,
+            list_transforms,
+            get_transform_stats,
+            put_transform,
+            preview_transform,
+            start_transform,
+            stop_transform,
+            delete_transform,
+            get_rollup_support,
+            list_rollup_jobs,
+            get_rollup_job,
+            put_rollup_job,
+            start_rollup_job,
+            stop_rollup_job,
+            delete_rollup_job,
+            rollup_search,
+            graph_explore,
+            follow_index,
+            list_follower_indices,
+            get_follower_stats,
+            pause_follow,
+            resume_follow,
+            unfollow_index,
+            list_auto_follow_patterns,
+            put_auto_follow_pattern,
+            set_auto_follow_pattern_active,
+            delete_auto_follow_pattern,
+            get_autoscaling_policy,
+            put_autoscaling_policy,
+            delete_autoscaling_policy,
+            get_autoscaling_capacity,
+            get_flattened_fields,
+            get_field_stats,
+            get_top_field_values,
+            get_time_series,
+            analyze_index_quality,
+            find_duplicates,
+            sample_documents,
+            generate_test_data,
+            export_cluster_config,
+            import_cluster_config,
+            swap_alias,
+            reindex_with_transform,
+            get_autocomplete_bundle,
+            list_search_contexts,
+            cleanup_search_contexts,
+            open_point_in_time,
+            close_point_in_time,
+            preview_search_request,
+            put_runtime_field,
+            delete_runtime_field,
+            search_with_runtime_fields,
+            execute_painless,
+            simulate_index_name,
+            get_cluster_recovery,
+            list_dangling_indices,
+            import_dangling_index,
+            delete_dangling_index,
+            get_cluster_features,
+            reset_features,
+            request_confirmation_token,
+            get_rest_action_usage,
+            get_template_usage,
+            audit_aliases,
+            find_stuck_ilm_indices,
+            retry_ilm_indices,
+            get_shard_balance_report,
+            verify_reindex,
+            diff_index_against_template,
+            find_my_tasks,
+            cancel_my_task,
+            fetch_large_response,
+            read_large_response,
+            release_large_response,
+            get_http_client_stats,
+            resume_task,
+            list_task_checkpoints,
+            discard_task_checkpoint,
+            export_incremental,
+            list_export_watermarks,
+            sync_index,
+            list_sync_jobs,
+            stop_sync_job,
+            remove_sync_job
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
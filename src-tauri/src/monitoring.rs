@@ -0,0 +1,863 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/monitoring.rs
+
+use crate::elasticsearch::{current_connection, fetch_connection_json, ElasticsearchConnection};
+use crate::storage::{app_settings, open_db};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use reqwest::Client as ReqwestClient;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::{command, AppHandle, Emitter, Window};
+use tauri_plugin_notification::NotificationExt;
+
+const MIN_SAMPLE_INTERVAL_SECS: u64 = 5;
+// A week of samples at the default interval is roughly 20k rows per metric
+const DEFAULT_RETENTION_HOURS: u64 = 24 * 7;
+
+struct CollectorState {
+    interval_secs: u64,
+    retention_hours: u64,
+    last_sample_at: Option<i64>,
+    last_error: Option<String>,
+    notify: bool,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static COLLECTOR: Lazy<Mutex<Option<CollectorState>>> = Lazy::new(|| Mutex::new(None));
+// Latest per-index rates by connection id, derived from the last two collector samples
+static THROUGHPUT: Lazy<Mutex<HashMap<String, BTreeMap<String, IndexThroughput>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSample {
+    pub connection_id: String,
+    pub timestamp: i64,
+    pub metrics: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricPoint {
+    pub timestamp: i64,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSeries {
+    pub metric: String,
+    pub points: Vec<MetricPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectorStatus {
+    pub running: bool,
+    pub interval_secs: u64,
+    pub retention_hours: u64,
+    pub last_sample_at: Option<i64>,
+    pub last_error: Option<String>,
+    pub notify: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexThroughput {
+    pub index: String,
+    pub docs_per_sec: f64,
+    pub queries_per_sec: f64,
+    pub sampled_at: i64,
+    // Seconds between the two samples the rates were derived from
+    pub window_secs: f64,
+}
+
+// Cumulative indexing and query counters per index at one point in time
+struct IndexCounters {
+    timestamp: i64,
+    counters: BTreeMap<String, (f64, f64)>,
+}
+
+// Disk usage percentages at which Elasticsearch stops allocating, relocates, and blocks writes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DiskWatermarks {
+    pub low: f64,
+    pub high: f64,
+    pub flood_stage: f64,
+}
+
+impl Default for DiskWatermarks {
+    fn default() -> Self {
+        DiskWatermarks {
+            low: 85.0,
+            high: 90.0,
+            flood_stage: 95.0,
+        }
+    }
+}
+
+impl DiskWatermarks {
+    // 0 below low, then 1/2/3 for low, high and flood stage
+    fn stage(&self, used_percent: f64) -> u8 {
+        if used_percent >= self.flood_stage {
+            3
+        } else if used_percent >= self.high {
+            2
+        } else if used_percent >= self.low {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+// What the previous sample looked like, so only changes raise a notification
+#[derive(Default)]
+struct AlertState {
+    status: f64,
+    unassigned_shards: f64,
+    disk_stages: BTreeMap<String, u8>,
+}
+
+fn status_value(status: &str) -> f64 {
+    match status {
+        "green" => 0.0,
+        "yellow" => 1.0,
+        _ => 2.0,
+    }
+}
+
+// Samples cluster-wide and per-node figures; node metrics are keyed "node.<name>.<metric>"
+async fn collect_sample(
+    client: &ReqwestClient,
+    conn: &ElasticsearchConnection,
+) -> Result<BTreeMap<String, f64>, String> {
+    let health = fetch_connection_json(client, conn, "/_cluster/health", None).await?;
+    let stats = fetch_connection_json(client, conn, "/_cluster/stats", None).await?;
+    let nodes = fetch_connection_json(client, conn, "/_nodes/stats/jvm,os,fs", None).await?;
+
+    let mut metrics = BTreeMap::new();
+    let number = |value: &serde_json::Value| value.as_f64().unwrap_or(0.0);
+
+    metrics.insert(
+        "cluster.status".to_string(),
+        status_value(health["status"].as_str().unwrap_or("")),
+    );
+    for key in [
+        "number_of_nodes",
+        "number_of_data_nodes",
+        "active_primary_shards",
+        "active_shards",
+        "relocating_shards",
+        "initializing_shards",
+        "unassigned_shards",
+        "number_of_pending_tasks",
+    ] {
+        metrics.insert(format!("cluster.{}", key), number(&health[key]));
+    }
+
+    metrics.insert(
+        "cluster.indices_count".to_string(),
+        number(&stats["indices"]["count"]),
+    );
+    metrics.insert(
+        "cluster.docs_count".to_string(),
+        number(&stats["indices"]["docs"]["count"]),
+    );
+    metrics.insert(
+        "cluster.store_size_bytes".to_string(),
+        number(&stats["indices"]["store"]["size_in_bytes"]),
+    );
+    metrics.insert(
+        "cluster.heap_used_bytes".to_string(),
+        number(&stats["nodes"]["jvm"]["mem"]["heap_used_in_bytes"]),
+    );
+    metrics.insert(
+        "cluster.heap_max_bytes".to_string(),
+        number(&stats["nodes"]["jvm"]["mem"]["heap_max_in_bytes"]),
+    );
+    metrics.insert(
+        "cluster.fs_total_bytes".to_string(),
+        number(&stats["nodes"]["fs"]["total_in_bytes"]),
+    );
+    metrics.insert(
+        "cluster.fs_available_bytes".to_string(),
+        number(&stats["nodes"]["fs"]["available_in_bytes"]),
+    );
+
+    if let Some(nodes) = nodes["nodes"].as_object() {
+        for node in nodes.values() {
+            let name = node["name"].as_str().unwrap_or("unknown");
+            let total_disk = number(&node["fs"]["total"]["total_in_bytes"]);
+            let available_disk = number(&node["fs"]["total"]["available_in_bytes"]);
+
+            metrics.insert(
+                format!("node.{}.cpu_percent", name),
+                number(&node["os"]["cpu"]["percent"]),
+            );
+            metrics.insert(
+                format!("node.{}.heap_used_percent", name),
+                number(&node["jvm"]["mem"]["heap_used_percent"]),
+            );
+            if total_disk > 0.0 {
+                metrics.insert(
+                    format!("node.{}.disk_used_percent", name),
+                    (total_disk - available_disk) / total_disk * 100.0,
+                );
+            }
+        }
+    }
+
+    Ok(metrics)
+}
+
+async fn collect_index_counters(
+    client: &ReqwestClient,
+    conn: &ElasticsearchConnection,
+    timestamp: i64,
+) -> Result<IndexCounters, String> {
+    let stats = fetch_connection_json(
+        client,
+        conn,
+        "/_stats/indexing,search?level=indices&filter_path=indices.*.primaries.indexing.index_total,indices.*.total.search.query_total",
+        None,
+    )
+    .await?;
+
+    // Primaries only for indexing so replicas don't double the rate; queries run on any copy
+    let counters = stats["indices"]
+        .as_object()
+        .map(|indices| {
+            indices
+                .iter()
+                .filter(|(name, _)| !name.starts_with('.'))
+                .map(|(name, index)| {
+                    (
+                        name.clone(),
+                        (
+                            index["primaries"]["indexing"]["index_total"]
+                                .as_f64()
+                                .unwrap_or(0.0),
+                            index["total"]["search"]["query_total"]
+                                .as_f64()
+                                .unwrap_or(0.0),
+                        ),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(IndexCounters {
+        timestamp,
+        counters,
+    })
+}
+
+fn derive_throughput(previous: &IndexCounters, current: &IndexCounters) -> Vec<IndexThroughput> {
+    let window_secs = (current.timestamp - previous.timestamp) as f64 / 1000.0;
+    if window_secs <= 0.0 {
+        return Vec::new();
+    }
+
+    current
+        .counters
+        .iter()
+        .filter_map(|(index, (indexed, queried))| {
+            let (previous_indexed, previous_queried) = previous.counters.get(index)?;
+
+            // Counters restart from zero when an index is reopened or recreated
+            let rate = |now: f64, before: f64| (now - before).max(0.0) / window_secs;
+
+            Some(IndexThroughput {
+                index: index.clone(),
+                docs_per_sec: rate(*indexed, *previous_indexed),
+                queries_per_sec: rate(*queried, *previous_queried),
+                sampled_at: current.timestamp,
+                window_secs,
+            })
+        })
+        .collect()
+}
+
+fn store_sample(
+    app: &AppHandle,
+    sample: &MetricsSample,
+    retention_hours: u64,
+) -> Result<(), String> {
+    let mut db = open_db(app)?;
+    let tx = db.transaction().map_err(|e| e.to_string())?;
+
+    {
+        let mut insert = tx
+            .prepare_cached(
+                "INSERT INTO metric_samples (connection_id, timestamp, metric, value)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )
+            .map_err(|e| e.to_string())?;
+        for (metric, value) in &sample.metrics {
+            insert
+                .execute(rusqlite::params![
+                    sample.connection_id,
+                    sample.timestamp,
+                    metric,
+                    value
+                ])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    let cutoff = sample.timestamp - (retention_hours * 3600 * 1000) as i64;
+    tx.execute(
+        "DELETE FROM metric_samples WHERE timestamp < ?1",
+        rusqlite::params![cutoff],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())
+}
+
+// Watermarks may be percentages or ratios; absolute byte values can't be compared with usage
+// percentages and leave the default in place
+fn parse_watermark_percent(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some(percent) = value.strip_suffix('%') {
+        return percent.trim().parse().ok();
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|ratio| *ratio <= 1.0)
+        .map(|ratio| ratio * 100.0)
+}
+
+async fn fetch_disk_watermarks(
+    client: &ReqwestClient,
+    conn: &ElasticsearchConnection,
+    timeout_ms: Option<u64>,
+) -> Result<DiskWatermarks, String> {
+    let settings = fetch_connection_json(
+        client,
+        conn,
+        "/_cluster/settings?include_defaults=true&flat_settings=true",
+        timeout_ms,
+    )
+    .await?;
+
+    let mut watermarks = DiskWatermarks::default();
+
+    // Later sections win: transient overrides persistent, which overrides the defaults
+    for section in ["defaults", "persistent", "transient"] {
+        let lookup = |stage: &str| {
+            settings[section][format!("cluster.routing.allocation.disk.watermark.{}", stage)]
+                .as_str()
+                .and_then(parse_watermark_percent)
+        };
+        if let Some(low) = lookup("low") {
+            watermarks.low = low;
+        }
+        if let Some(high) = lookup("high") {
+            watermarks.high = high;
+        }
+        if let Some(flood_stage) = lookup("flood_stage") {
+            watermarks.flood_stage = flood_stage;
+        }
+    }
+
+    Ok(watermarks)
+}
+
+fn status_name(status: f64) -> &'static str {
+    if status >= 2.0 {
+        "red"
+    } else if status >= 1.0 {
+        "yellow"
+    } else {
+        "green"
+    }
+}
+
+// Compares a sample with the previous one and returns (title, body) for each new problem
+fn evaluate_alerts(
+    cluster_name: &str,
+    previous: &AlertState,
+    sample: &MetricsSample,
+    watermarks: &DiskWatermarks,
+) -> (AlertState, Vec<(String, String)>) {
+    let mut alerts = Vec::new();
+    let metric = |key: &str| sample.metrics.get(key).copied().unwrap_or(0.0);
+
+    let status = metric("cluster.status");
+    if status > previous.status {
+        alerts.push((
+            format!("{} is {}", cluster_name, status_name(status).to_uppercase()),
+            format!(
+                "Cluster health changed from {} to {}",
+                status_name(previous.status),
+                status_name(status)
+            ),
+        ));
+    }
+
+    let unassigned_shards = metric("cluster.unassigned_shards");
+    if unassigned_shards > 0.0 && previous.unassigned_shards == 0.0 {
+        alerts.push((
+            format!("{} has unassigned shards", cluster_name),
+            format!("{} shards are currently unassigned", unassigned_shards),
+        ));
+    }
+
+    let stage_names = ["", "low", "high", "flood stage"];
+    let mut disk_stages = BTreeMap::new();
+    for (key, used_percent) in &sample.metrics {
+        let node = match key
+            .strip_prefix("node.")
+            .and_then(|rest| rest.strip_suffix(".disk_used_percent"))
+        {
+            Some(node) => node,
+            None => continue,
+        };
+
+        let stage = watermarks.stage(*used_percent);
+        if stage > previous.disk_stages.get(node).copied().unwrap_or(0) {
+            alerts.push((
+                format!(
+                    "{}: disk {} watermark exceeded",
+                    cluster_name, stage_names[stage as usize]
+                ),
+                format!("Node {} is at {:.1}% disk usage", node, used_percent),
+            ));
+        }
+        disk_stages.insert(node.to_string(), stage);
+    }
+
+    (
+        AlertState {
+            status,
+            unassigned_shards,
+            disk_stages,
+        },
+        alerts,
+    )
+}
+
+fn record_collector_result(timestamp: i64, error: Option<String>) {
+    if let Some(state) = COLLECTOR.lock().as_mut() {
+        if error.is_none() {
+            state.last_sample_at = Some(timestamp);
+        }
+        state.last_error = error;
+    }
+}
+
+#[command]
+pub fn start_metrics_collector(
+    window: Window,
+    app: AppHandle,
+    interval_secs: Option<u64>,
+    retention_hours: Option<u64>,
+    notify: Option<bool>,
+) -> Result<CollectorStatus, String> {
+    let interval_secs = interval_secs
+        .unwrap_or_else(|| app_settings().metrics_interval_secs)
+        .max(MIN_SAMPLE_INTERVAL_SECS);
+    let retention_hours = retention_hours.unwrap_or(DEFAULT_RETENTION_HOURS).max(1);
+    let notify = notify.unwrap_or(true);
+
+    // Fail up front rather than from inside the background task
+    open_db(&app)?;
+
+    // The collector follows the connection of the window that started it
+    let window_label = window.label().to_string();
+
+    let task = tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut alert_states: BTreeMap<String, AlertState> = BTreeMap::new();
+        let mut index_counters: BTreeMap<String, IndexCounters> = BTreeMap::new();
+
+        loop {
+            ticker.tick().await;
+
+            // Samples always go to whichever cluster that window is connected to at the time
+            let (conn, client) = match current_connection(&window_label) {
+                Ok(connection) => connection,
+                Err(_) => continue,
+            };
+
+            let timestamp = chrono::Utc::now().timestamp_millis();
+            let result = match collect_sample(&client, &conn).await {
+                Ok(mut metrics) => {
+                    if let Ok(counters) = collect_index_counters(&client, &conn, timestamp).await {
+                        if let Some(previous) = index_counters.get(&conn.id) {
+                            let throughput = derive_throughput(previous, &counters);
+                            for rates in &throughput {
+                                metrics.insert(
+                                    format!("index.{}.docs_per_sec", rates.index),
+                                    rates.docs_per_sec,
+                                );
+                                metrics.insert(
+                                    format!("index.{}.queries_per_sec", rates.index),
+                                    rates.queries_per_sec,
+                                );
+                            }
+                            THROUGHPUT.lock().insert(
+                                conn.id.clone(),
+                                throughput
+                                    .into_iter()
+                                    .map(|rates| (rates.index.clone(), rates))
+                                    .collect(),
+                            );
+                        }
+                        index_counters.insert(conn.id.clone(), counters);
+                    }
+
+                    let sample = MetricsSample {
+                        connection_id: conn.id.clone(),
+                        timestamp,
+                        metrics,
+                    };
+                    let stored = store_sample(&app, &sample, retention_hours);
+
+                    let notify = COLLECTOR.lock().as_ref().map(|s| s.notify).unwrap_or(false);
+                    let watermarks = fetch_disk_watermarks(&client, &conn, None)
+                        .await
+                        .unwrap_or_default();
+                    let previous = alert_states.remove(&conn.id).unwrap_or_default();
+                    let (state, alerts) =
+                        evaluate_alerts(&conn.name, &previous, &sample, &watermarks);
+                    alert_states.insert(conn.id.clone(), state);
+
+                    if notify {
+                        for (title, body) in alerts {
+                            if let Err(e) =
+                                app.notification().builder().title(title).body(body).show()
+                            {
+                                println!("Failed to show notification: {}", e);
+                            }
+                        }
+                    }
+
+                    let _ = app.emit("metrics-sample", sample);
+                    stored
+                }
+                Err(e) => Err(e),
+            };
+
+            record_collector_result(timestamp, result.err());
+        }
+    });
+
+    let mut collector = COLLECTOR.lock();
+    if let Some(previous) = collector.take() {
+        previous.task.abort();
+    }
+    *collector = Some(CollectorState {
+        interval_secs,
+        retention_hours,
+        last_sample_at: None,
+        last_error: None,
+        notify,
+        task,
+    });
+
+    println!(
+        "Started metrics collector: every {}s, keeping {}h of history",
+        interval_secs, retention_hours
+    );
+
+    Ok(CollectorStatus {
+        running: true,
+        interval_secs,
+        retention_hours,
+        last_sample_at: None,
+        last_error: None,
+        notify,
+    })
+}
+
+#[command]
+pub fn stop_metrics_collector() -> Result<bool, String> {
+    match COLLECTOR.lock().take() {
+        Some(state) => {
+            state.task.abort();
+            THROUGHPUT.lock().clear();
+            println!("Stopped metrics collector");
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[command]
+pub fn get_metrics_collector_status() -> Result<CollectorStatus, String> {
+    let collector = COLLECTOR.lock();
+
+    Ok(match collector.as_ref() {
+        Some(state) => CollectorStatus {
+            running: true,
+            interval_secs: state.interval_secs,
+            retention_hours: state.retention_hours,
+            last_sample_at: state.last_sample_at,
+            last_error: state.last_error.clone(),
+            notify: state.notify,
+        },
+        None => CollectorStatus {
+            running: false,
+            interval_secs: 0,
+            retention_hours: 0,
+            last_sample_at: None,
+            last_error: None,
+            notify: false,
+        },
+    })
+}
+
+#[command]
+pub fn set_cluster_notifications(enabled: bool) -> Result<bool, String> {
+    let mut collector = COLLECTOR.lock();
+    let state = collector
+        .as_mut()
+        .ok_or("The metrics collector is not running")?;
+    state.notify = enabled;
+    Ok(enabled)
+}
+
+#[command]
+pub fn get_index_throughput(window: Window, index: String) -> Result<IndexThroughput, String> {
+    if COLLECTOR.lock().is_none() {
+        return Err("The metrics collector is not running".to_string());
+    }
+
+    let (conn, _) = current_connection(window.label())?;
+    let throughput = THROUGHPUT.lock();
+
+    throughput
+        .get(&conn.id)
+        .and_then(|indices| indices.get(&index))
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "No throughput for index '{}' yet, it needs two samples from the metrics collector",
+                index
+            )
+        })
+}
+
+fn resolve_connection_id(window: &Window, connection_id: Option<String>) -> Result<String, String> {
+    match connection_id {
+        Some(id) => Ok(id),
+        None => current_connection(window.label()).map(|(conn, _)| conn.id),
+    }
+}
+
+#[command]
+pub fn list_metric_names(
+    window: Window,
+    app: AppHandle,
+    connection_id: Option<String>,
+) -> Result<Vec<String>, String> {
+    let connection_id = resolve_connection_id(&window, connection_id)?;
+    let db = open_db(&app)?;
+
+    let mut query = db
+        .prepare(
+            "SELECT DISTINCT metric FROM metric_samples WHERE connection_id = ?1 ORDER BY metric",
+        )
+        .map_err(|e| e.to_string())?;
+    let names = query
+        .query_map(rusqlite::params![connection_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(names)
+}
+
+#[command]
+pub fn get_metrics_range(
+    window: Window,
+    app: AppHandle,
+    metrics: Vec<String>,
+    from: i64,
+    to: i64,
+    max_points: Option<u32>,
+    connection_id: Option<String>,
+) -> Result<Vec<MetricSeries>, String> {
+    if to <= from {
+        return Err("The end of the range must be after its start".to_string());
+    }
+
+    let connection_id = resolve_connection_id(&window, connection_id)?;
+    let db = open_db(&app)?;
+
+    // Long ranges are averaged into buckets so a sparkline never gets more points than it can draw
+    let bucket_ms = match max_points {
+        Some(points) if points > 0 => ((to - from) / points as i64).max(1),
+        _ => 1,
+    };
+
+    let mut query = db
+        .prepare_cached(
+            "SELECT (timestamp / ?1) * ?1 AS bucket, AVG(value)
+             FROM metric_samples
+             WHERE connection_id = ?2 AND metric = ?3 AND timestamp >= ?4 AND timestamp <= ?5
+             GROUP BY bucket
+             ORDER BY bucket",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut series = Vec::new();
+    for metric in metrics {
+        let points = query
+            .query_map(
+                rusqlite::params![bucket_ms, connection_id, metric, from, to],
+                |row| {
+                    Ok(MetricPoint {
+                        timestamp: row.get(0)?,
+                        value: row.get(1)?,
+                    })
+                },
+            )
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<MetricPoint>, _>>()
+            .map_err(|e| e.to_string())?;
+
+        series.push(MetricSeries { metric, points });
+    }
+
+    Ok(series)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDiskPressure {
+    pub node: String,
+    pub host: String,
+    pub shards: u32,
+    pub disk_used_bytes: u64,
+    pub disk_avail_bytes: u64,
+    pub disk_total_bytes: u64,
+    pub disk_used_percent: f64,
+    // "ok", "low", "high" or "flood_stage"
+    pub stage: String,
+    // Bytes that can still be written before the node crosses the next watermark
+    pub headroom_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskPressureReport {
+    pub watermarks: DiskWatermarks,
+    pub nodes: Vec<NodeDiskPressure>,
+    pub nodes_over_low: Vec<String>,
+    pub nodes_over_high: Vec<String>,
+    pub nodes_over_flood_stage: Vec<String>,
+    // Indices already blocked for writes by the flood stage watermark
+    pub read_only_indices: Vec<String>,
+}
+
+#[command]
+pub async fn check_disk_pressure(
+    window: Window,
+    timeout_ms: Option<u64>,
+) -> Result<DiskPressureReport, String> {
+    let (conn, client) = current_connection(window.label())?;
+
+    let watermarks = fetch_disk_watermarks(&client, &conn, timeout_ms).await?;
+    let allocation = fetch_connection_json(
+        &client,
+        &conn,
+        "/_cat/allocation?format=json&bytes=b",
+        timeout_ms,
+    )
+    .await?;
+    let blocks = fetch_connection_json(
+        &client,
+        &conn,
+        "/_all/_settings/index.blocks.read_only_allow_delete?expand_wildcards=all",
+        timeout_ms,
+    )
+    .await?;
+
+    let stage_names = ["ok", "low", "high", "flood_stage"];
+    let thresholds = [watermarks.low, watermarks.high, watermarks.flood_stage];
+
+    let mut nodes = Vec::new();
+    for row in allocation.as_array().into_iter().flatten() {
+        let bytes = |key: &str| row[key].as_str().and_then(|v| v.parse::<u64>().ok());
+
+        // The UNASSIGNED row has no disk figures
+        let (used, avail, total) =
+            match (bytes("disk.used"), bytes("disk.avail"), bytes("disk.total")) {
+                (Some(used), Some(avail), Some(total)) if total > 0 => (used, avail, total),
+                _ => continue,
+            };
+
+        let used_percent = used as f64 / total as f64 * 100.0;
+        let stage = watermarks.stage(used_percent);
+        let headroom_bytes = thresholds
+            .get(stage as usize)
+            .map(|threshold| ((total as f64 * threshold / 100.0) - used as f64).max(0.0) as u64);
+
+        nodes.push(NodeDiskPressure {
+            node: row["node"].as_str().unwrap_or("").to_string(),
+            host: row["host"].as_str().unwrap_or("").to_string(),
+            shards: row["shards"]
+                .as_str()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            disk_used_bytes: used,
+            disk_avail_bytes: avail,
+            disk_total_bytes: total,
+            disk_used_percent: used_percent,
+            stage: stage_names[stage as usize].to_string(),
+            headroom_bytes,
+        });
+    }
+
+    // Fullest nodes first
+    nodes.sort_by(|a, b| b.disk_used_percent.total_cmp(&a.disk_used_percent));
+
+    let over = |min_stage: u8| -> Vec<String> {
+        nodes
+            .iter()
+            .filter(|node| watermarks.stage(node.disk_used_percent) >= min_stage)
+            .map(|node| node.node.clone())
+            .collect()
+    };
+
+    let mut read_only_indices: Vec<String> = blocks
+        .as_object()
+        .map(|indices| {
+            indices
+                .iter()
+                .filter(|(_, index)| {
+                    index["settings"]["index"]["blocks"]["read_only_allow_delete"].as_str()
+                        == Some("true")
+                })
+                .map(|(name, _)| name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    read_only_indices.sort();
+
+    Ok(DiskPressureReport {
+        watermarks,
+        nodes_over_low: over(1),
+        nodes_over_high: over(2),
+        nodes_over_flood_stage: over(3),
+        nodes,
+        read_only_indices,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watermark_percentages() {
+        assert_eq!(parse_watermark_percent("85%"), Some(85.0));
+        assert_eq!(parse_watermark_percent(" 90.5 % "), Some(90.5));
+        assert_eq!(parse_watermark_percent("0.95"), Some(95.0));
+        assert_eq!(parse_watermark_percent("1"), Some(100.0));
+        assert_eq!(parse_watermark_percent("500mb"), None);
+        assert_eq!(parse_watermark_percent("20"), None);
+        assert_eq!(parse_watermark_percent("high%"), None);
+    }
+}
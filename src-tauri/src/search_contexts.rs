@@ -0,0 +1,190 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/search_contexts.rs
+
+use crate::elasticsearch::describe_error_response;
+use crate::transport::{encode_segment, Transport};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{command, Window};
+
+const DEFAULT_PIT_KEEP_ALIVE: &str = "5m";
+
+// Scroll and point-in-time contexts the app opened, by connection id. They hold segments
+// open on the cluster until they expire, so anything left behind is closed explicitly.
+static SEARCH_CONTEXTS: Lazy<Mutex<HashMap<String, Vec<SearchContext>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchContext {
+    // "scroll" or "pit"
+    pub kind: String,
+    pub id: String,
+    pub index: String,
+    pub opened_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchContextCleanup {
+    pub closed: usize,
+    // Contexts the cluster wouldn't release; they still expire on their own
+    pub failed: Vec<String>,
+}
+
+pub(crate) fn track_search_context(connection_id: &str, kind: &str, id: &str, index: &str) {
+    SEARCH_CONTEXTS
+        .lock()
+        .entry(connection_id.to_string())
+        .or_default()
+        .push(SearchContext {
+            kind: kind.to_string(),
+            id: id.to_string(),
+            index: index.to_string(),
+            opened_at: chrono::Utc::now().timestamp_millis(),
+        });
+}
+
+pub(crate) fn untrack_search_context(connection_id: &str, id: &str) {
+    let mut contexts = SEARCH_CONTEXTS.lock();
+    if let Some(open) = contexts.get_mut(connection_id) {
+        open.retain(|context| context.id != id);
+        if open.is_empty() {
+            contexts.remove(connection_id);
+        }
+    }
+}
+
+// Scroll ids can change from one page to the next
+pub(crate) fn replace_search_context(connection_id: &str, old_id: &str, new_id: &str) {
+    if let Some(context) = SEARCH_CONTEXTS
+        .lock()
+        .get_mut(connection_id)
+        .and_then(|open| open.iter_mut().find(|context| context.id == old_id))
+    {
+        context.id = new_id.to_string();
+    }
+}
+
+async fn close_context(transport: &Transport, context: &SearchContext) -> Result<(), String> {
+    let (path, body) = match context.kind.as_str() {
+        "pit" => ("/_pit", serde_json::json!({ "id": context.id })),
+        _ => (
+            "/_search/scroll",
+            serde_json::json!({ "scroll_id": context.id }),
+        ),
+    };
+
+    let response = transport.send(Method::DELETE, path, Some(&body)).await?;
+    // Already expired or closed elsewhere, either way it's gone
+    if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
+        return Ok(());
+    }
+
+    Err(format!(
+        "request to {} failed: {}",
+        path,
+        describe_error_response(response).await
+    ))
+}
+
+// Closes every context tracked for the transport's connection
+pub(crate) async fn release_search_contexts(transport: &Transport) -> SearchContextCleanup {
+    let contexts = SEARCH_CONTEXTS
+        .lock()
+        .remove(transport.connection_id())
+        .unwrap_or_default();
+
+    let mut closed = 0;
+    let mut failed = Vec::new();
+    for context in &contexts {
+        match close_context(transport, context).await {
+            Ok(()) => closed += 1,
+            Err(e) => failed.push(format!("{} {}: {}", context.kind, context.index, e)),
+        }
+    }
+
+    if closed > 0 {
+        println!(
+            "Successfully closed {} search contexts for connection: {}",
+            closed,
+            transport.connection_id()
+        );
+    }
+
+    SearchContextCleanup { closed, failed }
+}
+
+#[command]
+pub fn list_search_contexts(window: Window) -> Result<Vec<SearchContext>, String> {
+    let transport = Transport::current(window.label())?;
+    Ok(SEARCH_CONTEXTS
+        .lock()
+        .get(transport.connection_id())
+        .cloned()
+        .unwrap_or_default())
+}
+
+#[command]
+pub async fn cleanup_search_contexts(
+    window: Window,
+    timeout_ms: Option<u64>,
+) -> Result<SearchContextCleanup, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    Ok(release_search_contexts(&transport).await)
+}
+
+#[command]
+pub async fn open_point_in_time(
+    window: Window,
+    index: String,
+    keep_alive: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<String, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let keep_alive = keep_alive.unwrap_or_else(|| DEFAULT_PIT_KEEP_ALIVE.to_string());
+
+    let result = transport
+        .json(
+            Method::POST,
+            &format!(
+                "/{}/_pit?keep_alive={}",
+                encode_segment(&index),
+                encode_segment(&keep_alive)
+            ),
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to open point in time - {}", e))?;
+    let id = result["id"]
+        .as_str()
+        .ok_or("Elasticsearch did not return a point in time id")?
+        .to_string();
+
+    track_search_context(transport.connection_id(), "pit", &id, &index);
+
+    Ok(id)
+}
+
+#[command]
+pub async fn close_point_in_time(
+    window: Window,
+    id: String,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let context = SearchContext {
+        kind: "pit".to_string(),
+        id: id.clone(),
+        index: String::new(),
+        opened_at: 0,
+    };
+
+    close_context(&transport, &context)
+        .await
+        .map_err(|e| format!("Failed to close point in time - {}", e))?;
+    untrack_search_context(transport.connection_id(), &id);
+
+    Ok(true)
+}
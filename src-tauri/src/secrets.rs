@@ -0,0 +1,95 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/secrets.rs
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::io::Write;
+use std::path::Path;
+
+// Lives next to the database but is only readable by the current user, so a copied or
+// synced database on its own doesn't give away any credentials
+const KEY_FILE: &str = "elastiko.key";
+const KEY_LEN: usize = 32;
+
+fn write_key_file(path: &Path, key: &[u8]) -> Result<(), String> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Unable to create '{}': {}", path.display(), e))?;
+    file.write_all(key)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Unable to write '{}': {}", path.display(), e))
+}
+
+fn load_key(dir: &Path) -> Result<LessSafeKey, String> {
+    let path = dir.join(KEY_FILE);
+
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let mut key = vec![0u8; KEY_LEN];
+            SystemRandom::new()
+                .fill(&mut key)
+                .map_err(|_| "Unable to generate an encryption key".to_string())?;
+            write_key_file(&path, &key)?;
+            key
+        }
+        Err(e) => return Err(format!("Unable to read '{}': {}", path.display(), e)),
+    };
+
+    UnboundKey::new(&CHACHA20_POLY1305, &bytes)
+        .map(LessSafeKey::new)
+        .map_err(|_| format!("'{}' is not a valid encryption key", path.display()))
+}
+
+// Encrypts with the install's key; the result is base64 of the nonce followed by the ciphertext
+pub(crate) fn seal(dir: &Path, plaintext: &str) -> Result<String, String> {
+    let key = load_key(dir)?;
+
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "Unable to generate a nonce".to_string())?;
+
+    let mut sealed = plaintext.as_bytes().to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::empty(),
+        &mut sealed,
+    )
+    .map_err(|_| "Unable to encrypt the connection secrets".to_string())?;
+
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&sealed);
+    Ok(STANDARD.encode(out))
+}
+
+pub(crate) fn open(dir: &Path, sealed: &str) -> Result<String, String> {
+    let key = load_key(dir)?;
+    let bytes = STANDARD
+        .decode(sealed)
+        .map_err(|e| format!("Stored secrets are corrupt: {}", e))?;
+    if bytes.len() < NONCE_LEN {
+        return Err("Stored secrets are corrupt".to_string());
+    }
+
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| "Stored secrets are corrupt".to_string())?;
+    let mut buffer = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::empty(), &mut buffer)
+        .map_err(|_| {
+            "Stored secrets can't be decrypted, the encryption key has changed".to_string()
+        })?;
+
+    String::from_utf8(plaintext.to_vec()).map_err(|e| e.to_string())
+}
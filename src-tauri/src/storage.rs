@@ -0,0 +1,1127 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/storage.rs
+
+use crate::elasticsearch::{connect_to_elasticsearch, current_connection, ElasticsearchConnection};
+use crate::secrets;
+use crate::tasks::AppTask;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{command, AppHandle, Manager, Window};
+
+const DB_FILE: &str = "elastiko.db";
+const MAX_QUERY_HISTORY: i64 = 500;
+const DEFAULT_HISTORY_LIMIT: u32 = 100;
+const DEFAULT_TASK_LOG_LIMIT: u32 = 200;
+
+static NEXT_LOCAL_ID: AtomicU64 = AtomicU64::new(1);
+
+// Loaded at startup so code without an AppHandle (e.g. the transport) can read it
+static SETTINGS: Lazy<Mutex<AppSettings>> = Lazy::new(|| Mutex::new(AppSettings::default()));
+
+// Applied in order; the database's user_version records how many have run. Never edit an entry
+// once it has shipped, append a new one instead
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE saved_connections (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        data TEXT NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE TABLE query_history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        connection_id TEXT NOT NULL,
+        index_name TEXT NOT NULL,
+        query TEXT NOT NULL,
+        executed_at INTEGER NOT NULL,
+        took_ms INTEGER,
+        total_hits INTEGER
+    );
+    CREATE INDEX query_history_lookup ON query_history (connection_id, executed_at);
+    CREATE TABLE saved_queries (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        connection_id TEXT,
+        index_name TEXT NOT NULL,
+        query TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS metric_samples (
+        connection_id TEXT NOT NULL,
+        timestamp INTEGER NOT NULL,
+        metric TEXT NOT NULL,
+        value REAL NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS metric_samples_lookup
+        ON metric_samples (connection_id, metric, timestamp);
+    CREATE TABLE task_logs (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        task_id TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        description TEXT NOT NULL,
+        state TEXT NOT NULL,
+        error TEXT,
+        es_task_id TEXT,
+        result TEXT,
+        started_at INTEGER NOT NULL,
+        finished_at INTEGER
+    );",
+    "CREATE TABLE app_settings (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );",
+    "CREATE TABLE workspaces (
+        name TEXT PRIMARY KEY,
+        data TEXT NOT NULL,
+        updated_at INTEGER NOT NULL
+    );",
+    "CREATE TABLE task_checkpoints (
+        task_id TEXT PRIMARY KEY,
+        data TEXT NOT NULL,
+        updated_at INTEGER NOT NULL
+    );",
+    "CREATE TABLE export_watermarks (
+        connection_id TEXT NOT NULL,
+        index_name TEXT NOT NULL,
+        field TEXT NOT NULL,
+        value TEXT NOT NULL,
+        updated_at INTEGER NOT NULL,
+        PRIMARY KEY (connection_id, index_name, field)
+    );",
+    "ALTER TABLE saved_connections ADD COLUMN secrets TEXT;",
+    "UPDATE workspaces SET data = json_set(
+        json_remove(data, '$.connection'),
+        '$.connection_id',
+        json_extract(data, '$.connection.id')
+    );",
+];
+
+// The user_version from which saved connections keep their credentials in the sealed
+// secrets column instead of in data
+const SECRETS_MIGRATION: usize = 6;
+// The user_version from which workspaces refer to their connection by id only
+const WORKSPACE_CONNECTION_ID_MIGRATION: usize = 7;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    // Applied to searches that don't set their own size
+    pub default_page_size: u32,
+    // Applied to requests that don't pass timeout_ms or set a longer timeout themselves
+    pub default_timeout_ms: u64,
+    // How often background tasks and snapshot/restore tracking poll the cluster
+    pub task_poll_interval_ms: u64,
+    pub progress_poll_interval_ms: u64,
+    // Used by the metrics collector when it is started without an interval
+    pub metrics_interval_secs: u64,
+    // Destructive commands refuse to run without a confirmation token while this is on
+    pub confirm_before_delete: bool,
+    // Relative export paths are resolved against this directory
+    pub export_directory: Option<String>,
+    // How long mappings, settings and similar metadata are served from cache; 0 disables it
+    pub cache_ttl_secs: u64,
+    // Requests beyond this many per cluster wait in a queue
+    pub max_concurrent_requests: usize,
+    // Bulk requests are split so none is larger than this
+    pub bulk_max_bytes: usize,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            default_page_size: 20,
+            default_timeout_ms: 30_000,
+            task_poll_interval_ms: 1000,
+            progress_poll_interval_ms: 2000,
+            metrics_interval_secs: 30,
+            confirm_before_delete: true,
+            export_directory: None,
+            cache_ttl_secs: 60,
+            max_concurrent_requests: 6,
+            // Well below the default 100MB http.max_content_length
+            bulk_max_bytes: 5 * 1024 * 1024,
+        }
+    }
+}
+
+// The credentials of a saved connection, stored encrypted rather than in its JSON
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ConnectionSecrets {
+    password: Option<String>,
+    api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryHistoryEntry {
+    pub id: i64,
+    pub connection_id: String,
+    pub index: String,
+    pub query: String,
+    pub executed_at: i64,
+    pub took_ms: Option<i64>,
+    pub total_hits: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQuery {
+    // Left empty when saving a new query; one is assigned
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    // None for queries that apply to any connection
+    pub connection_id: Option<String>,
+    pub index: String,
+    pub query: String,
+    #[serde(default)]
+    pub created_at: i64,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceTab {
+    pub title: String,
+    pub index: String,
+    pub query: String,
+}
+
+// The parts of a session only the UI knows about
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceState {
+    pub selected_indices: Vec<String>,
+    // Saved query ids
+    pub pinned_queries: Vec<String>,
+    pub tabs: Vec<WorkspaceTab>,
+    pub active_tab: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    // A saved connection, looked up again on restore
+    pub connection_id: Option<String>,
+    pub state: WorkspaceState,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceSummary {
+    pub name: String,
+    pub connection_name: Option<String>,
+    pub tab_count: usize,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoredWorkspace {
+    pub workspace: Workspace,
+    // The connect result, as returned by connect_to_elasticsearch
+    pub connection: Option<serde_json::Value>,
+    // Set when the saved connection couldn't be reopened; the rest of the workspace still applies
+    pub connection_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskLog {
+    pub id: i64,
+    pub task_id: String,
+    pub kind: String,
+    pub description: String,
+    pub state: String,
+    pub error: Option<String>,
+    pub es_task_id: Option<String>,
+    pub result: Option<serde_json::Value>,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+// Where an interrupted import or export got to, so resume_task can carry on from there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskCheckpoint {
+    pub task_id: String,
+    // "import_csv", "import_ndjson" or "export_ndjson"
+    pub kind: String,
+    pub connection_id: String,
+    pub description: String,
+    // Arguments of the original call, so the resumed run repeats it exactly
+    pub params: serde_json::Value,
+    // Byte offset, rows or search_after cursor done so far, plus the running totals
+    pub position: serde_json::Value,
+    pub updated_at: i64,
+}
+
+// The newest value of `field` seen by the last incremental export of an index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportWatermark {
+    pub connection_id: String,
+    pub index: String,
+    pub field: String,
+    pub value: serde_json::Value,
+    pub updated_at: i64,
+}
+
+// Returns the schema version the database was at before
+fn run_migrations(db: &mut rusqlite::Connection) -> Result<usize, String> {
+    let applied: usize = db
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+
+    for (version, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let tx = db.transaction().map_err(|e| e.to_string())?;
+        tx.execute_batch(migration)
+            .map_err(|e| format!("Migration {} failed: {}", version + 1, e))?;
+        tx.pragma_update(None, "user_version", version + 1)
+            .map_err(|e| e.to_string())?;
+        tx.commit().map_err(|e| e.to_string())?;
+
+        println!("Applied storage migration {}", version + 1);
+    }
+
+    Ok(applied)
+}
+
+pub(crate) fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Unable to create '{}': {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+// Opens the application database in the app data dir, bringing its schema up to date
+pub(crate) fn open_db(app: &AppHandle) -> Result<rusqlite::Connection, String> {
+    let dir = data_dir(app)?;
+    let mut db = rusqlite::Connection::open(dir.join(DB_FILE)).map_err(|e| e.to_string())?;
+
+    // The metrics collector writes while commands read, so wait briefly instead of failing
+    db.busy_timeout(std::time::Duration::from_secs(5))
+        .map_err(|e| e.to_string())?;
+    let previous = run_migrations(&mut db)?;
+    if previous < SECRETS_MIGRATION {
+        seal_legacy_connection_secrets(&db, &dir)?;
+    }
+
+    // Rewritten rows leave plaintext credentials behind in free pages until the file is rebuilt
+    if previous > 0 && previous < WORKSPACE_CONNECTION_ID_MIGRATION {
+        db.execute_batch("VACUUM").map_err(|e| e.to_string())?;
+    }
+
+    Ok(db)
+}
+
+fn write_saved_connection(
+    db: &rusqlite::Connection,
+    dir: &Path,
+    connection: &ElasticsearchConnection,
+) -> Result<(), String> {
+    let mut stripped = connection.clone();
+    let secrets = ConnectionSecrets {
+        password: stripped.password.take(),
+        api_key: stripped.api_key.take(),
+    };
+    let sealed = if secrets.password.is_some() || secrets.api_key.is_some() {
+        let plaintext = serde_json::to_string(&secrets).map_err(|e| e.to_string())?;
+        Some(secrets::seal(dir, &plaintext)?)
+    } else {
+        None
+    };
+    let data = serde_json::to_string(&stripped).map_err(|e| e.to_string())?;
+
+    db.execute(
+        "INSERT INTO saved_connections (id, name, data, secrets, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(id) DO UPDATE SET name = ?2, data = ?3, secrets = ?4, updated_at = ?5",
+        rusqlite::params![connection.id, connection.name, data, sealed, now_millis()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+fn read_saved_connection(
+    dir: &Path,
+    data: &str,
+    sealed: Option<&str>,
+) -> Result<ElasticsearchConnection, String> {
+    let mut connection: ElasticsearchConnection =
+        serde_json::from_str(data).map_err(|e| e.to_string())?;
+
+    if let Some(sealed) = sealed {
+        let secrets: ConnectionSecrets =
+            serde_json::from_str(&secrets::open(dir, sealed)?).map_err(|e| e.to_string())?;
+        connection.password = secrets.password;
+        connection.api_key = secrets.api_key;
+    }
+
+    Ok(connection)
+}
+
+// Connections saved before the secrets column existed still have their credentials in data
+fn seal_legacy_connection_secrets(db: &rusqlite::Connection, dir: &Path) -> Result<(), String> {
+    let mut query = db
+        .prepare("SELECT data FROM saved_connections WHERE secrets IS NULL")
+        .map_err(|e| e.to_string())?;
+    let rows = query
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut sealed = 0;
+    for data in rows {
+        let connection = read_saved_connection(dir, &data, None)?;
+        if connection.password.is_some() || connection.api_key.is_some() {
+            write_saved_connection(db, dir, &connection)?;
+            sealed += 1;
+        }
+    }
+
+    if sealed > 0 {
+        println!("Encrypted the credentials of {} saved connections", sealed);
+    }
+
+    Ok(())
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+fn new_local_id(prefix: &str) -> String {
+    format!(
+        "{}-{}-{}",
+        prefix,
+        now_millis(),
+        NEXT_LOCAL_ID.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+// Keeps a record of finished background tasks so they survive a restart
+pub(crate) fn log_task(app: &AppHandle, task: &AppTask) -> Result<(), String> {
+    let db = open_db(app)?;
+    let result = match &task.result {
+        Some(result) => Some(serde_json::to_string(result).map_err(|e| e.to_string())?),
+        None => None,
+    };
+
+    db.execute(
+        "INSERT INTO task_logs
+            (task_id, kind, description, state, error, es_task_id, result, started_at, finished_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        rusqlite::params![
+            task.id,
+            task.kind,
+            task.description,
+            task.state,
+            task.error,
+            task.es_task_id,
+            result,
+            task.started_at,
+            task.finished_at
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[command]
+pub fn list_saved_connections(app: AppHandle) -> Result<Vec<ElasticsearchConnection>, String> {
+    let db = open_db(&app)?;
+    let dir = data_dir(&app)?;
+
+    let mut query = db
+        .prepare("SELECT data, secrets FROM saved_connections ORDER BY name COLLATE NOCASE")
+        .map_err(|e| e.to_string())?;
+    let rows = query
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<(String, Option<String>)>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    rows.iter()
+        .map(|(data, sealed)| read_saved_connection(&dir, data, sealed.as_deref()))
+        .collect()
+}
+
+pub(crate) fn load_saved_connection(
+    app: &AppHandle,
+    id: &str,
+) -> Result<Option<ElasticsearchConnection>, String> {
+    let db = open_db(app)?;
+    let dir = data_dir(app)?;
+
+    let row: Option<(String, Option<String>)> = db
+        .query_row(
+            "SELECT data, secrets FROM saved_connections WHERE id = ?1",
+            rusqlite::params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    row.map(|(data, sealed)| read_saved_connection(&dir, &data, sealed.as_deref()))
+        .transpose()
+}
+
+#[command]
+pub fn save_connection(
+    app: AppHandle,
+    connection: ElasticsearchConnection,
+) -> Result<bool, String> {
+    if connection.id.trim().is_empty() {
+        return Err("A connection id is required".to_string());
+    }
+    if let Some(defaults) = &connection.defaults {
+        if defaults
+            .page_size
+            .is_some_and(|size| size == 0 || size > 10_000)
+        {
+            return Err("The default page size must be between 1 and 10000".to_string());
+        }
+        if defaults.timeout_ms.is_some_and(|ms| ms < 1000) {
+            return Err("The default timeout must be at least 1000 ms".to_string());
+        }
+        if let Some(track_total_hits) = &defaults.track_total_hits {
+            if !track_total_hits.is_boolean() && !track_total_hits.is_u64() {
+                return Err("track_total_hits must be true, false or a hit count".to_string());
+            }
+        }
+    }
+
+    let db = open_db(&app)?;
+    write_saved_connection(&db, &data_dir(&app)?, &connection)?;
+
+    println!("Successfully saved connection: {}", connection.name);
+    Ok(true)
+}
+
+#[command]
+pub fn delete_saved_connection(app: AppHandle, id: String) -> Result<bool, String> {
+    let db = open_db(&app)?;
+
+    let deleted = db
+        .execute(
+            "DELETE FROM saved_connections WHERE id = ?1",
+            rusqlite::params![id],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(deleted > 0)
+}
+
+#[command]
+pub fn add_query_history(
+    app: AppHandle,
+    connection_id: String,
+    index: String,
+    query: String,
+    took_ms: Option<i64>,
+    total_hits: Option<i64>,
+) -> Result<i64, String> {
+    let mut db = open_db(&app)?;
+    let tx = db.transaction().map_err(|e| e.to_string())?;
+
+    tx.execute(
+        "INSERT INTO query_history (connection_id, index_name, query, executed_at, took_ms, total_hits)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![connection_id, index, query, now_millis(), took_ms, total_hits],
+    )
+    .map_err(|e| e.to_string())?;
+    let id = tx.last_insert_rowid();
+
+    // History is a convenience, so only the most recent entries per connection are kept
+    tx.execute(
+        "DELETE FROM query_history WHERE connection_id = ?1 AND id NOT IN (
+            SELECT id FROM query_history WHERE connection_id = ?1
+            ORDER BY executed_at DESC, id DESC LIMIT ?2
+        )",
+        rusqlite::params![connection_id, MAX_QUERY_HISTORY],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+#[command]
+pub fn list_query_history(
+    app: AppHandle,
+    connection_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<QueryHistoryEntry>, String> {
+    let db = open_db(&app)?;
+
+    let mut query = db
+        .prepare(
+            "SELECT id, connection_id, index_name, query, executed_at, took_ms, total_hits
+             FROM query_history
+             WHERE ?1 IS NULL OR connection_id = ?1
+             ORDER BY executed_at DESC, id DESC
+             LIMIT ?2",
+        )
+        .map_err(|e| e.to_string())?;
+    let entries = query
+        .query_map(
+            rusqlite::params![connection_id, limit.unwrap_or(DEFAULT_HISTORY_LIMIT)],
+            |row| {
+                Ok(QueryHistoryEntry {
+                    id: row.get(0)?,
+                    connection_id: row.get(1)?,
+                    index: row.get(2)?,
+                    query: row.get(3)?,
+                    executed_at: row.get(4)?,
+                    took_ms: row.get(5)?,
+                    total_hits: row.get(6)?,
+                })
+            },
+        )
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(entries)
+}
+
+#[command]
+pub fn clear_query_history(app: AppHandle, connection_id: Option<String>) -> Result<usize, String> {
+    let db = open_db(&app)?;
+
+    db.execute(
+        "DELETE FROM query_history WHERE ?1 IS NULL OR connection_id = ?1",
+        rusqlite::params![connection_id],
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[command]
+pub fn list_saved_queries(
+    app: AppHandle,
+    connection_id: Option<String>,
+) -> Result<Vec<SavedQuery>, String> {
+    let db = open_db(&app)?;
+
+    // Queries saved without a connection show up everywhere
+    let mut query = db
+        .prepare(
+            "SELECT id, name, connection_id, index_name, query, created_at, updated_at
+             FROM saved_queries
+             WHERE ?1 IS NULL OR connection_id IS NULL OR connection_id = ?1
+             ORDER BY name COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+    let queries = query
+        .query_map(rusqlite::params![connection_id], |row| {
+            Ok(SavedQuery {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                connection_id: row.get(2)?,
+                index: row.get(3)?,
+                query: row.get(4)?,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(queries)
+}
+
+#[command]
+pub fn save_query(app: AppHandle, query: SavedQuery) -> Result<SavedQuery, String> {
+    if query.name.trim().is_empty() {
+        return Err("A name is required to save a query".to_string());
+    }
+
+    let db = open_db(&app)?;
+    let now = now_millis();
+    let saved = SavedQuery {
+        id: if query.id.is_empty() {
+            new_local_id("query")
+        } else {
+            query.id
+        },
+        created_at: if query.created_at > 0 {
+            query.created_at
+        } else {
+            now
+        },
+        updated_at: now,
+        ..query
+    };
+
+    db.execute(
+        "INSERT INTO saved_queries (id, name, connection_id, index_name, query, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT(id) DO UPDATE SET
+            name = ?2, connection_id = ?3, index_name = ?4, query = ?5, updated_at = ?7",
+        rusqlite::params![
+            saved.id,
+            saved.name,
+            saved.connection_id,
+            saved.index,
+            saved.query,
+            saved.created_at,
+            saved.updated_at
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    println!("Successfully saved query: {}", saved.name);
+    Ok(saved)
+}
+
+#[command]
+pub fn delete_saved_query(app: AppHandle, id: String) -> Result<bool, String> {
+    let db = open_db(&app)?;
+
+    let deleted = db
+        .execute(
+            "DELETE FROM saved_queries WHERE id = ?1",
+            rusqlite::params![id],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(deleted > 0)
+}
+
+#[command]
+pub fn list_task_logs(app: AppHandle, limit: Option<u32>) -> Result<Vec<TaskLog>, String> {
+    let db = open_db(&app)?;
+
+    let mut query = db
+        .prepare(
+            "SELECT id, task_id, kind, description, state, error, es_task_id, result,
+                    started_at, finished_at
+             FROM task_logs
+             ORDER BY started_at DESC, id DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let logs = query
+        .query_map(
+            rusqlite::params![limit.unwrap_or(DEFAULT_TASK_LOG_LIMIT)],
+            |row| {
+                let result: Option<String> = row.get(7)?;
+                Ok(TaskLog {
+                    id: row.get(0)?,
+                    task_id: row.get(1)?,
+                    kind: row.get(2)?,
+                    description: row.get(3)?,
+                    state: row.get(4)?,
+                    error: row.get(5)?,
+                    es_task_id: row.get(6)?,
+                    result: result.and_then(|r| serde_json::from_str(&r).ok()),
+                    started_at: row.get(8)?,
+                    finished_at: row.get(9)?,
+                })
+            },
+        )
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(logs)
+}
+
+#[command]
+pub fn clear_task_logs(app: AppHandle) -> Result<usize, String> {
+    let db = open_db(&app)?;
+
+    db.execute("DELETE FROM task_logs", [])
+        .map_err(|e| e.to_string())
+}
+
+// Written after every batch that made it to the cluster; replaces the task's previous checkpoint
+pub(crate) fn save_task_checkpoint(
+    app: &AppHandle,
+    checkpoint: &TaskCheckpoint,
+) -> Result<(), String> {
+    let db = open_db(app)?;
+    let data = serde_json::to_string(checkpoint).map_err(|e| e.to_string())?;
+
+    db.execute(
+        "INSERT INTO task_checkpoints (task_id, data, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(task_id) DO UPDATE SET data = excluded.data, updated_at = excluded.updated_at",
+        rusqlite::params![checkpoint.task_id, data, checkpoint.updated_at],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub(crate) fn load_task_checkpoint(
+    app: &AppHandle,
+    task_id: &str,
+) -> Result<Option<TaskCheckpoint>, String> {
+    let db = open_db(app)?;
+
+    let data: Option<String> = db
+        .query_row(
+            "SELECT data FROM task_checkpoints WHERE task_id = ?1",
+            rusqlite::params![task_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    data.map(|data| serde_json::from_str(&data).map_err(|e| e.to_string()))
+        .transpose()
+}
+
+pub(crate) fn remove_task_checkpoint(app: &AppHandle, task_id: &str) -> Result<bool, String> {
+    let db = open_db(app)?;
+
+    let removed = db
+        .execute(
+            "DELETE FROM task_checkpoints WHERE task_id = ?1",
+            rusqlite::params![task_id],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(removed > 0)
+}
+
+// Interrupted imports and exports that can be picked up again with resume_task
+#[command]
+pub fn list_task_checkpoints(app: AppHandle) -> Result<Vec<TaskCheckpoint>, String> {
+    let db = open_db(&app)?;
+
+    let mut query = db
+        .prepare("SELECT data FROM task_checkpoints ORDER BY updated_at DESC")
+        .map_err(|e| e.to_string())?;
+    let rows = query
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    rows.iter()
+        .map(|data| serde_json::from_str(data).map_err(|e| e.to_string()))
+        .collect()
+}
+
+// Gives up on resuming a task; whatever it already transferred stays where it is
+#[command]
+pub fn discard_task_checkpoint(app: AppHandle, task_id: String) -> Result<bool, String> {
+    remove_task_checkpoint(&app, &task_id)
+}
+
+pub(crate) fn load_export_watermark(
+    app: &AppHandle,
+    connection_id: &str,
+    index: &str,
+    field: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let db = open_db(app)?;
+
+    let value: Option<String> = db
+        .query_row(
+            "SELECT value FROM export_watermarks
+             WHERE connection_id = ?1 AND index_name = ?2 AND field = ?3",
+            rusqlite::params![connection_id, index, field],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    value
+        .map(|value| serde_json::from_str(&value).map_err(|e| e.to_string()))
+        .transpose()
+}
+
+pub(crate) fn save_export_watermark(
+    app: &AppHandle,
+    watermark: &ExportWatermark,
+) -> Result<(), String> {
+    let db = open_db(app)?;
+    let value = serde_json::to_string(&watermark.value).map_err(|e| e.to_string())?;
+
+    db.execute(
+        "INSERT INTO export_watermarks (connection_id, index_name, field, value, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(connection_id, index_name, field)
+         DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        rusqlite::params![
+            watermark.connection_id,
+            watermark.index,
+            watermark.field,
+            value,
+            watermark.updated_at
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[command]
+pub fn list_export_watermarks(
+    app: AppHandle,
+    connection_id: Option<String>,
+) -> Result<Vec<ExportWatermark>, String> {
+    let db = open_db(&app)?;
+
+    let mut query = db
+        .prepare(
+            "SELECT connection_id, index_name, field, value, updated_at
+             FROM export_watermarks
+             WHERE ?1 IS NULL OR connection_id = ?1
+             ORDER BY updated_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let watermarks = query
+        .query_map(rusqlite::params![connection_id], |row| {
+            let value: String = row.get(3)?;
+            Ok(ExportWatermark {
+                connection_id: row.get(0)?,
+                index: row.get(1)?,
+                field: row.get(2)?,
+                value: serde_json::from_str(&value).unwrap_or(serde_json::Value::Null),
+                updated_at: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(watermarks)
+}
+
+pub(crate) fn app_settings() -> AppSettings {
+    SETTINGS.lock().clone()
+}
+
+fn read_app_settings(db: &rusqlite::Connection) -> Result<AppSettings, String> {
+    let stored: Option<String> = match db.query_row(
+        "SELECT value FROM app_settings WHERE key = 'app'",
+        [],
+        |row| row.get(0),
+    ) {
+        Ok(value) => Some(value),
+        Err(rusqlite::Error::QueryReturnedNoRows) => None,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    // Fields added in later versions fall back to their defaults
+    match stored {
+        Some(value) => serde_json::from_str(&value).map_err(|e| e.to_string()),
+        None => Ok(AppSettings::default()),
+    }
+}
+
+pub(crate) fn load_app_settings(app: &AppHandle) -> Result<AppSettings, String> {
+    let db = open_db(app)?;
+    let settings = read_app_settings(&db)?;
+    *SETTINGS.lock() = settings.clone();
+
+    Ok(settings)
+}
+
+#[command]
+pub fn get_app_settings(app: AppHandle) -> Result<AppSettings, String> {
+    load_app_settings(&app)
+}
+
+#[command]
+pub fn update_app_settings(app: AppHandle, settings: AppSettings) -> Result<AppSettings, String> {
+    if settings.default_page_size == 0 || settings.default_page_size > 10_000 {
+        return Err("The default page size must be between 1 and 10000".to_string());
+    }
+    if settings.default_timeout_ms < 1000 {
+        return Err("The default timeout must be at least 1000 ms".to_string());
+    }
+    if settings.task_poll_interval_ms < 250 || settings.progress_poll_interval_ms < 250 {
+        return Err("Polling intervals must be at least 250 ms".to_string());
+    }
+    if settings.max_concurrent_requests == 0 {
+        return Err("At least one concurrent request must be allowed".to_string());
+    }
+    if settings.bulk_max_bytes < 64 * 1024 || settings.bulk_max_bytes > 100 * 1024 * 1024 {
+        return Err("The bulk request size must be between 64KB and 100MB".to_string());
+    }
+    if settings.metrics_interval_secs < 5 {
+        return Err("The metrics interval must be at least 5 seconds".to_string());
+    }
+
+    let settings = AppSettings {
+        export_directory: settings
+            .export_directory
+            .map(|dir| dir.trim().to_string())
+            .filter(|dir| !dir.is_empty()),
+        ..settings
+    };
+
+    let db = open_db(&app)?;
+    let value = serde_json::to_string(&settings).map_err(|e| e.to_string())?;
+    db.execute(
+        "INSERT INTO app_settings (key, value) VALUES ('app', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+        rusqlite::params![value],
+    )
+    .map_err(|e| e.to_string())?;
+
+    *SETTINGS.lock() = settings.clone();
+
+    println!("Successfully saved app settings");
+    Ok(settings)
+}
+
+// None when the workspace has no connection or it was deleted since
+fn saved_connection_name(
+    db: &rusqlite::Connection,
+    connection_id: Option<&str>,
+) -> Result<Option<String>, String> {
+    let Some(id) = connection_id else {
+        return Ok(None);
+    };
+
+    db.query_row(
+        "SELECT name FROM saved_connections WHERE id = ?1",
+        rusqlite::params![id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn read_workspace(db: &rusqlite::Connection, name: &str) -> Result<Workspace, String> {
+    let data: String = db
+        .query_row(
+            "SELECT data FROM workspaces WHERE name = ?1",
+            rusqlite::params![name],
+            |row| row.get(0),
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => format!("Workspace '{}' was not found", name),
+            e => e.to_string(),
+        })?;
+
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+#[command]
+pub fn save_workspace(
+    window: Window,
+    app: AppHandle,
+    name: String,
+    state: WorkspaceState,
+) -> Result<WorkspaceSummary, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("A name is required to save a workspace".to_string());
+    }
+
+    // Not being connected is fine, the workspace just won't reconnect on restore. Only the id is
+    // kept, so a connection that was never saved can't be reopened either
+    let workspace = Workspace {
+        name: name.clone(),
+        connection_id: current_connection(window.label())
+            .ok()
+            .map(|(conn, _)| conn.id),
+        state,
+        updated_at: now_millis(),
+    };
+
+    let db = open_db(&app)?;
+    let connection_name = saved_connection_name(&db, workspace.connection_id.as_deref())?;
+    let data = serde_json::to_string(&workspace).map_err(|e| e.to_string())?;
+    db.execute(
+        "INSERT INTO workspaces (name, data, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(name) DO UPDATE SET data = ?2, updated_at = ?3",
+        rusqlite::params![name, data, workspace.updated_at],
+    )
+    .map_err(|e| e.to_string())?;
+
+    println!("Successfully saved workspace: {}", name);
+    Ok(WorkspaceSummary {
+        name,
+        connection_name,
+        tab_count: workspace.state.tabs.len(),
+        updated_at: workspace.updated_at,
+    })
+}
+
+#[command]
+pub fn list_workspaces(app: AppHandle) -> Result<Vec<WorkspaceSummary>, String> {
+    let db = open_db(&app)?;
+
+    let mut query = db
+        .prepare("SELECT data FROM workspaces ORDER BY updated_at DESC")
+        .map_err(|e| e.to_string())?;
+    let rows = query
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    rows.iter()
+        .map(|data| {
+            let workspace: Workspace = serde_json::from_str(data).map_err(|e| e.to_string())?;
+            Ok(WorkspaceSummary {
+                connection_name: saved_connection_name(&db, workspace.connection_id.as_deref())?,
+                name: workspace.name,
+                tab_count: workspace.state.tabs.len(),
+                updated_at: workspace.updated_at,
+            })
+        })
+        .collect()
+}
+
+#[command]
+pub async fn restore_workspace(
+    window: Window,
+    app: AppHandle,
+    name: String,
+) -> Result<RestoredWorkspace, String> {
+    let workspace = {
+        let db = open_db(&app)?;
+        read_workspace(&db, &name)?
+    };
+
+    let saved = match &workspace.connection_id {
+        Some(id) => Some(
+            load_saved_connection(&app, id)?
+                .ok_or_else(|| format!("The saved connection '{}' no longer exists", id)),
+        ),
+        None => None,
+    };
+
+    let (connection, connection_error) = match saved {
+        Some(Ok(conn)) => match connect_to_elasticsearch(window.clone(), conn, None).await {
+            Ok(result) => (Some(result), None),
+            Err(e) => (None, Some(e)),
+        },
+        Some(Err(e)) => (None, Some(e)),
+        None => (None, None),
+    };
+
+    println!("Restored workspace: {}", name);
+    Ok(RestoredWorkspace {
+        workspace,
+        connection,
+        connection_error,
+    })
+}
+
+#[command]
+pub fn delete_workspace(app: AppHandle, name: String) -> Result<bool, String> {
+    let db = open_db(&app)?;
+
+    let deleted = db
+        .execute(
+            "DELETE FROM workspaces WHERE name = ?1",
+            rusqlite::params![name],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(deleted > 0)
+}
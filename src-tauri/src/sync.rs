@@ -0,0 +1,467 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/sync.rs
+
+use crate::bulk::{BulkAction, BulkBuilder};
+use crate::elasticsearch::{connection_by_id, shared_client, ElasticsearchConnection};
+use crate::export::ScrollCursor;
+use crate::storage::load_saved_connection;
+use crate::transport::{encode_segment, Transport};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use reqwest::Client as ReqwestClient;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::{command, AppHandle, Emitter};
+use tokio::sync::Notify;
+
+const MIN_SYNC_INTERVAL_SECS: u64 = 10;
+const MAX_REPORTED_ERRORS: usize = 100;
+const CONFLICT_STRATEGIES: &[&str] = &["overwrite", "skip"];
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+struct SyncJob {
+    status: SyncJobStatus,
+    stop: Arc<AtomicBool>,
+    wake: Arc<Notify>,
+}
+
+static SYNC_JOBS: Lazy<Mutex<HashMap<String, SyncJob>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncOptions {
+    // Seconds between runs; without it the job runs once
+    pub interval_secs: Option<u64>,
+    // "overwrite" (default) replaces documents that already exist in the destination,
+    // "skip" leaves them alone
+    pub conflict_strategy: Option<String>,
+    // Starting watermark; by default the newest `since_field` value already in the destination
+    pub since_value: Option<serde_json::Value>,
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncJobStatus {
+    pub id: String,
+    pub source_connection_id: String,
+    pub source_index: String,
+    pub dest_connection_id: String,
+    pub dest_index: String,
+    pub since_field: String,
+    pub conflict_strategy: String,
+    pub interval_secs: Option<u64>,
+    // "running", "idle" between runs, "completed", "failed" or "stopped"
+    pub state: String,
+    pub runs: u64,
+    pub docs_copied: u64,
+    // Documents left alone because they already existed, with the "skip" strategy
+    pub docs_skipped: u64,
+    pub docs_failed: u64,
+    pub watermark: Option<serde_json::Value>,
+    pub last_run_at: Option<i64>,
+    pub last_error: Option<String>,
+    pub errors: Vec<String>,
+}
+
+fn update_job(id: &str, apply: impl FnOnce(&mut SyncJobStatus)) -> Option<SyncJobStatus> {
+    SYNC_JOBS.lock().get_mut(id).map(|job| {
+        apply(&mut job.status);
+        job.status.clone()
+    })
+}
+
+fn publish(app: &AppHandle, id: &str, apply: impl FnOnce(&mut SyncJobStatus)) {
+    if let Some(status) = update_job(id, apply) {
+        let _ = app.emit("sync-progress", status);
+    }
+}
+
+// Open connections are used as they are, anything else comes from the saved connections
+fn resolve_connection(
+    app: &AppHandle,
+    connection_id: &str,
+) -> Result<(ElasticsearchConnection, ReqwestClient), String> {
+    let conn = match connection_by_id(connection_id) {
+        Some(conn) => conn,
+        None => load_saved_connection(app, connection_id)?
+            .ok_or_else(|| format!("Connection '{}' was not found", connection_id))?,
+    };
+    Ok((conn, shared_client()?))
+}
+
+// The newest `since_field` value already in the destination, as a sort value would report it
+async fn dest_watermark(
+    transport: &Transport,
+    index: &str,
+    since_field: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let body = serde_json::json!({
+        "size": 0,
+        "aggs": { "latest": { "max": { "field": since_field } } }
+    });
+
+    let response = transport
+        .send(
+            Method::POST,
+            &format!("/{}/_search", encode_segment(index)),
+            Some(&body),
+        )
+        .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to read the newest {} in {} - Status: {}",
+            since_field,
+            index,
+            response.status()
+        ));
+    }
+
+    let result: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let latest = &result["aggregations"]["latest"]["value"];
+    Ok((!latest.is_null()).then(|| latest.clone()))
+}
+
+struct SyncTarget {
+    source: (ElasticsearchConnection, ReqwestClient),
+    dest: Transport,
+    source_index: String,
+    dest_index: String,
+    since_field: String,
+    skip_existing: bool,
+    timeout_ms: Option<u64>,
+}
+
+// Where the next run starts: the newest since_field value copied, plus the ids already copied
+// with exactly that value. since_field values needn't be unique, so the ids break ties that
+// would otherwise be copied again every run. Sorting on _id would need fielddata on it, which
+// is off by default since 8.0, so the ids are kept instead of a search_after position.
+#[derive(Debug, Clone)]
+struct Watermark {
+    value: Option<serde_json::Value>,
+    ids: Vec<String>,
+}
+
+impl Watermark {
+    fn new(value: Option<serde_json::Value>) -> Self {
+        Watermark {
+            value,
+            ids: Vec::new(),
+        }
+    }
+
+    // Documents after the watermark, newer values and the untouched ids sharing its value
+    fn query(&self, since_field: &str) -> serde_json::Value {
+        let Some(value) = &self.value else {
+            return serde_json::json!({ "exists": { "field": since_field } });
+        };
+        if self.ids.is_empty() {
+            return serde_json::json!({ "range": { since_field: { "gte": value } } });
+        }
+        serde_json::json!({
+            "bool": {
+                "should": [
+                    { "range": { since_field: { "gt": value } } },
+                    {
+                        "bool": {
+                            "filter": { "range": { since_field: { "gte": value, "lte": value } } },
+                            "must_not": { "ids": { "values": self.ids } }
+                        }
+                    }
+                ],
+                "minimum_should_match": 1
+            }
+        })
+    }
+
+    // Moves past a copied hit, which comes in since_field order
+    fn advance(&mut self, hit: &serde_json::Value) {
+        let Some(value) = hit["sort"].get(0) else {
+            return;
+        };
+        if self.value.as_ref() != Some(value) {
+            self.value = Some(value.clone());
+            self.ids.clear();
+        }
+        if let Some(id) = hit["_id"].as_str() {
+            self.ids.push(id.to_string());
+        }
+    }
+}
+
+// Copies everything after the watermark, moving the watermark along page by page. It never
+// moves past the first document that failed to copy, so the next run retries from there.
+async fn run_sync(
+    app: &AppHandle,
+    id: &str,
+    target: &SyncTarget,
+    stop: &AtomicBool,
+    watermark: &mut Watermark,
+) -> Result<(), String> {
+    let query = watermark.query(&target.since_field);
+    let body = serde_json::json!({
+        "query": query,
+        "sort": [{ target.since_field.as_str(): "asc" }]
+    });
+
+    let (conn, client) = target.source.clone();
+    let mut cursor = ScrollCursor::open(
+        client,
+        conn,
+        &target.source_index,
+        &body.to_string(),
+        target.timeout_ms,
+    )
+    .await?;
+
+    let result: Result<(), String> = async {
+        // Set once a document fails, after which later pages are still copied but the
+        // watermark stays put
+        let mut held = false;
+        while !stop.load(Ordering::Relaxed) {
+            let page = cursor.next_page().await?;
+            if page.is_empty() {
+                break;
+            }
+
+            let mut bulk = BulkBuilder::new(target.dest.clone());
+            for (position, hit) in page.iter().enumerate() {
+                let id = hit["_id"].as_str().unwrap_or_default();
+                let source = hit["_source"].clone();
+                let action = if target.skip_existing {
+                    BulkAction::create(position as u64, &target.dest_index, id, source)
+                } else {
+                    BulkAction::index(position as u64, &target.dest_index, Some(id), source)
+                };
+                bulk.push(action).await?;
+            }
+            let outcome = bulk.finish().await?;
+
+            let skipped = outcome
+                .errors
+                .iter()
+                .filter(|error| target.skip_existing && error.status == 409)
+                .count() as u64;
+            let failed: Vec<_> = outcome
+                .errors
+                .iter()
+                .filter(|error| !(target.skip_existing && error.status == 409))
+                .collect();
+            let failures: Vec<String> = failed
+                .iter()
+                .map(|error| {
+                    format!(
+                        "{}: {}",
+                        page[error.tag as usize]["_id"].as_str().unwrap_or_default(),
+                        error.reason
+                    )
+                })
+                .collect();
+
+            // Hits come sorted by since_field, so the lowest failed position is the oldest
+            let first_failed = failed.iter().map(|error| error.tag as usize).min();
+            if !held {
+                // Stopping short of the failed hit leaves it after the watermark for the next run
+                let copied = first_failed.unwrap_or(page.len());
+                for hit in &page[..copied] {
+                    watermark.advance(hit);
+                }
+                held = first_failed.is_some();
+            }
+
+            publish(app, id, |status| {
+                status.docs_copied += outcome.succeeded;
+                status.docs_skipped += skipped;
+                status.docs_failed += failures.len() as u64;
+                for failure in failures {
+                    if status.errors.len() < MAX_REPORTED_ERRORS {
+                        status.errors.push(failure);
+                    }
+                }
+                status.watermark = watermark.value.clone();
+            });
+        }
+        Ok(())
+    }
+    .await;
+
+    cursor.close().await;
+    result
+}
+
+// Starts a job that copies documents from a watermark on `since_field` onwards from one
+// cluster to another with scroll and bulk, once or every `interval_secs`. Meant for keeping
+// a dev or staging index fed from production, not as a replacement for cross-cluster replication.
+#[command]
+pub async fn sync_index(
+    app: AppHandle,
+    source_conn: String,
+    source_index: String,
+    dest_conn: String,
+    dest_index: String,
+    since_field: String,
+    options: Option<SyncOptions>,
+) -> Result<SyncJobStatus, String> {
+    let options = options.unwrap_or_default();
+    let conflict_strategy = options
+        .conflict_strategy
+        .unwrap_or_else(|| "overwrite".to_string());
+    if !CONFLICT_STRATEGIES.contains(&conflict_strategy.as_str()) {
+        return Err(format!(
+            "Unknown conflict strategy '{}', expected one of: {}",
+            conflict_strategy,
+            CONFLICT_STRATEGIES.join(", ")
+        ));
+    }
+    if since_field.trim().is_empty() {
+        return Err("A field to track new documents by is required".to_string());
+    }
+    if source_conn == dest_conn && source_index == dest_index {
+        return Err("The source and destination are the same index".to_string());
+    }
+    let interval_secs = options
+        .interval_secs
+        .map(|secs| secs.max(MIN_SYNC_INTERVAL_SECS));
+
+    let source = resolve_connection(&app, &source_conn)?;
+    let (dest, dest_client) = resolve_connection(&app, &dest_conn)?;
+    let dest = Transport::new(dest_client, dest).with_timeout(options.timeout_ms);
+
+    let mut watermark = Watermark::new(match options.since_value {
+        Some(value) => Some(value),
+        None => dest_watermark(&dest, &dest_index, &since_field).await?,
+    });
+
+    let id = format!("sync-{}", NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed));
+    let status = SyncJobStatus {
+        id: id.clone(),
+        source_connection_id: source_conn,
+        source_index: source_index.clone(),
+        dest_connection_id: dest_conn,
+        dest_index: dest_index.clone(),
+        since_field: since_field.clone(),
+        conflict_strategy: conflict_strategy.clone(),
+        interval_secs,
+        state: "running".to_string(),
+        runs: 0,
+        docs_copied: 0,
+        docs_skipped: 0,
+        docs_failed: 0,
+        watermark: watermark.value.clone(),
+        last_run_at: None,
+        last_error: None,
+        errors: Vec::new(),
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let wake = Arc::new(Notify::new());
+    SYNC_JOBS.lock().insert(
+        id.clone(),
+        SyncJob {
+            status: status.clone(),
+            stop: stop.clone(),
+            wake: wake.clone(),
+        },
+    );
+
+    let target = SyncTarget {
+        source,
+        dest,
+        source_index,
+        dest_index,
+        since_field,
+        skip_existing: conflict_strategy == "skip",
+        timeout_ms: options.timeout_ms,
+    };
+
+    let job_id = id.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            publish(&app, &job_id, |status| status.state = "running".to_string());
+            let result = run_sync(&app, &job_id, &target, &stop, &mut watermark).await;
+
+            let stopped = stop.load(Ordering::Relaxed);
+            publish(&app, &job_id, |status| {
+                status.runs += 1;
+                status.last_run_at = Some(chrono::Utc::now().timestamp_millis());
+                status.last_error = result.as_ref().err().cloned();
+                status.state = match (&result, interval_secs, stopped) {
+                    (_, _, true) => "stopped",
+                    (Err(_), None, _) => "failed",
+                    (Ok(_), None, _) => "completed",
+                    // A periodic job rides out failures, the next run tries again
+                    (_, Some(_), _) => "idle",
+                }
+                .to_string();
+            });
+
+            let interval = match interval_secs {
+                Some(secs) if !stopped => secs,
+                _ => break,
+            };
+            tokio::select! {
+                _ = tokio::time::sleep(std::time::Duration::from_secs(interval)) => {}
+                _ = wake.notified() => {}
+            }
+            if stop.load(Ordering::Relaxed) {
+                publish(&app, &job_id, |status| status.state = "stopped".to_string());
+                break;
+            }
+        }
+    });
+
+    println!(
+        "Started sync job {}: {} -> {}",
+        id, status.source_index, status.dest_index
+    );
+
+    Ok(status)
+}
+
+#[command]
+pub fn list_sync_jobs() -> Result<Vec<SyncJobStatus>, String> {
+    let mut jobs: Vec<SyncJobStatus> = SYNC_JOBS
+        .lock()
+        .values()
+        .map(|job| job.status.clone())
+        .collect();
+    jobs.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(jobs)
+}
+
+// Stops after the page being copied; the job stays listed with its totals until removed
+#[command]
+pub fn stop_sync_job(id: String) -> Result<bool, String> {
+    let jobs = SYNC_JOBS.lock();
+    let job = jobs
+        .get(&id)
+        .ok_or_else(|| format!("Sync job '{}' was not found", id))?;
+
+    if job.stop.swap(true, Ordering::Relaxed) {
+        return Ok(false);
+    }
+    job.wake.notify_one();
+    println!("Stopping sync job: {}", id);
+
+    Ok(true)
+}
+
+#[command]
+pub fn remove_sync_job(id: String) -> Result<bool, String> {
+    let mut jobs = SYNC_JOBS.lock();
+    match jobs.get(&id) {
+        Some(job)
+            if matches!(job.status.state.as_str(), "running" | "idle")
+                && !job.stop.load(Ordering::Relaxed) =>
+        {
+            Err(format!("Sync job '{}' is still active, stop it first", id))
+        }
+        Some(_) => Ok(jobs.remove(&id).is_some()),
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,912 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/tasks.rs
+
+use crate::confirmations::require_confirmation;
+use crate::elasticsearch::{current_connection, describe_error_body, fetch_snapshot_progress};
+use crate::export::resume_export_ndjson;
+use crate::import::{resume_import_csv, resume_import_ndjson};
+use crate::storage::{
+    app_settings, load_task_checkpoint, log_task, remove_task_checkpoint, save_task_checkpoint,
+    TaskCheckpoint,
+};
+use crate::transport::{encode_segment, opaque_id_prefix, Transport};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::{command, AppHandle, Emitter, Window};
+
+const PROGRESS_EVENT_INTERVAL_MS: u64 = 1000;
+
+struct TaskEntry {
+    info: AppTask,
+    cancelled: Arc<AtomicBool>,
+}
+
+static TASKS: Lazy<Mutex<Vec<TaskEntry>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static NEXT_TASK_ID: AtomicU64 = AtomicU64::new(1);
+// Checkpoints outlive the app, so task ids must not repeat from one launch to the next
+static TASK_ID_PREFIX: Lazy<String> =
+    Lazy::new(|| format!("task-{:x}", chrono::Utc::now().timestamp_millis()));
+static EMITTER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppTask {
+    pub id: String,
+    // "reindex", "delete_by_query", "force_merge", "snapshot", "import", "export", or
+    // "track_snapshot"/"track_restore" for progress polling
+    pub kind: String,
+    pub description: String,
+    // "running", "completed", "failed" or "cancelled"
+    pub state: String,
+    // None when the operation doesn't report how far along it is
+    pub progress_percent: Option<f64>,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    pub error: Option<String>,
+    // Backing Elasticsearch task ("node:id") for server-side operations
+    pub es_task_id: Option<String>,
+    pub result: Option<serde_json::Value>,
+}
+
+// Handed to the code doing the work so it can report progress and notice cancellation
+#[derive(Clone)]
+pub(crate) struct TaskHandle {
+    id: String,
+    cancelled: Arc<AtomicBool>,
+    app: AppHandle,
+}
+
+impl TaskHandle {
+    pub(crate) fn start(app: &AppHandle, kind: &str, description: String) -> Self {
+        let id = format!(
+            "{}-{}",
+            TASK_ID_PREFIX.as_str(),
+            NEXT_TASK_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        TASKS.lock().push(TaskEntry {
+            info: AppTask {
+                id: id.clone(),
+                kind: kind.to_string(),
+                description,
+                state: "running".to_string(),
+                progress_percent: None,
+                started_at: chrono::Utc::now().timestamp_millis(),
+                finished_at: None,
+                error: None,
+                es_task_id: None,
+                result: None,
+            },
+            cancelled: cancelled.clone(),
+        });
+
+        ensure_progress_emitter(app);
+
+        TaskHandle {
+            id,
+            cancelled,
+            app: app.clone(),
+        }
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut AppTask)) {
+        if let Some(entry) = TASKS
+            .lock()
+            .iter_mut()
+            .find(|entry| entry.info.id == self.id)
+        {
+            apply(&mut entry.info);
+        }
+    }
+
+    pub(crate) fn id(&self) -> &str {
+        &self.id
+    }
+
+    // Records how far a resumable operation got; failing to save only costs the ability to resume
+    pub(crate) fn checkpoint(
+        &self,
+        kind: &str,
+        connection_id: &str,
+        params: &serde_json::Value,
+        position: serde_json::Value,
+    ) {
+        let description = TASKS
+            .lock()
+            .iter()
+            .find(|entry| entry.info.id == self.id)
+            .map(|entry| entry.info.description.clone())
+            .unwrap_or_default();
+
+        let checkpoint = TaskCheckpoint {
+            task_id: self.id.clone(),
+            kind: kind.to_string(),
+            connection_id: connection_id.to_string(),
+            description,
+            params: params.clone(),
+            position,
+            updated_at: chrono::Utc::now().timestamp_millis(),
+        };
+        if let Err(e) = save_task_checkpoint(&self.app, &checkpoint) {
+            println!("Failed to checkpoint task {}: {}", self.id, e);
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_progress(&self, percent: f64) {
+        self.update(|task| task.progress_percent = Some(percent.clamp(0.0, 100.0)));
+    }
+
+    // Records progress and bails out of the operation once the user has cancelled it
+    pub(crate) fn progress(&self, percent: f64) -> Result<(), String> {
+        self.set_progress(percent);
+        if self.is_cancelled() {
+            Err("Cancelled".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn set_es_task_id(&self, es_task_id: &str) {
+        self.update(|task| task.es_task_id = Some(es_task_id.to_string()));
+    }
+
+    fn set_result(&self, result: serde_json::Value) {
+        self.update(|task| task.result = Some(result));
+    }
+
+    // Marks the task done from the operation's outcome and passes the outcome through
+    pub(crate) fn finish<T>(self, result: Result<T, String>) -> Result<T, String> {
+        let cancelled = self.is_cancelled();
+
+        self.update(|task| {
+            task.finished_at = Some(chrono::Utc::now().timestamp_millis());
+            match &result {
+                Ok(_) => {
+                    task.state = "completed".to_string();
+                    task.progress_percent = Some(100.0);
+                }
+                Err(_) if cancelled => task.state = "cancelled".to_string(),
+                Err(e) => {
+                    task.state = "failed".to_string();
+                    task.error = Some(e.clone());
+                }
+            }
+        });
+
+        // A finished task has nothing left to resume
+        if result.is_ok() {
+            if let Err(e) = remove_task_checkpoint(&self.app, &self.id) {
+                println!("Failed to remove checkpoint of task {}: {}", self.id, e);
+            }
+        }
+
+        let finished = TASKS
+            .lock()
+            .iter()
+            .find(|entry| entry.info.id == self.id)
+            .map(|entry| entry.info.clone());
+        if let Some(finished) = finished {
+            if let Err(e) = log_task(&self.app, &finished) {
+                println!("Failed to record task {}: {}", self.id, e);
+            }
+        }
+
+        result
+    }
+}
+
+fn task_snapshot() -> Vec<AppTask> {
+    TASKS
+        .lock()
+        .iter()
+        .map(|entry| entry.info.clone())
+        .collect()
+}
+
+// Emits the whole task list while anything is running, plus once more after the last one ends
+fn ensure_progress_emitter(app: &AppHandle) {
+    if EMITTER_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(PROGRESS_EVENT_INTERVAL_MS)).await;
+
+            // Decided under the lock so a task registered right now can't be missed
+            let (tasks, idle) = {
+                let entries = TASKS.lock();
+                let idle = entries.iter().all(|entry| entry.info.state != "running");
+                if idle {
+                    EMITTER_RUNNING.store(false, Ordering::SeqCst);
+                }
+                (
+                    entries
+                        .iter()
+                        .map(|entry| entry.info.clone())
+                        .collect::<Vec<_>>(),
+                    idle,
+                )
+            };
+
+            let _ = app.emit("app-task-progress", tasks);
+            if idle {
+                break;
+            }
+        }
+    });
+}
+
+#[command]
+pub fn list_app_tasks() -> Result<Vec<AppTask>, String> {
+    Ok(task_snapshot())
+}
+
+#[command]
+pub fn cancel_app_task(id: String) -> Result<bool, String> {
+    let tasks = TASKS.lock();
+    let entry = tasks
+        .iter()
+        .find(|entry| entry.info.id == id)
+        .ok_or_else(|| format!("Task '{}' was not found", id))?;
+
+    if entry.info.state != "running" {
+        return Ok(false);
+    }
+
+    // Workers check the flag between batches; server-side tasks are cancelled by their poller
+    entry.cancelled.store(true, Ordering::Relaxed);
+    println!("Cancellation requested for task: {}", id);
+
+    Ok(true)
+}
+
+// Picks up an interrupted import or export from its last checkpoint as a new task. The window
+// must be connected to the cluster the task was started on.
+#[command]
+pub async fn resume_task(
+    window: Window,
+    app: AppHandle,
+    task_id: String,
+    timeout_ms: Option<u64>,
+) -> Result<serde_json::Value, String> {
+    let checkpoint = load_task_checkpoint(&app, &task_id)?
+        .ok_or_else(|| format!("Task '{}' has no checkpoint to resume from", task_id))?;
+
+    if TASKS
+        .lock()
+        .iter()
+        .any(|entry| entry.info.id == task_id && entry.info.state == "running")
+    {
+        return Err(format!("Task '{}' is still running", task_id));
+    }
+
+    let transport = Transport::current(window.label())?;
+    if transport.connection_id() != checkpoint.connection_id {
+        return Err(format!(
+            "Task '{}' was started on a different connection, connect to it to resume",
+            task_id
+        ));
+    }
+
+    let kind = match checkpoint.kind.as_str() {
+        "export_ndjson" => "export",
+        _ => "import",
+    };
+    let description = match checkpoint.description.ends_with(" (resumed)") {
+        true => checkpoint.description.clone(),
+        false => format!("{} (resumed)", checkpoint.description),
+    };
+    let task = TaskHandle::start(&app, kind, description);
+
+    // The checkpoint moves to the new task so it can be resumed again if this run stops too
+    let moved = TaskCheckpoint {
+        task_id: task.id().to_string(),
+        ..checkpoint.clone()
+    };
+    let result = match save_task_checkpoint(&app, &moved)
+        .and_then(|_| remove_task_checkpoint(&app, &task_id))
+    {
+        Ok(_) => match checkpoint.kind.as_str() {
+            "import_csv" => resume_import_csv(&window, app, &task, &checkpoint, timeout_ms)
+                .await
+                .and_then(|summary| serde_json::to_value(summary).map_err(|e| e.to_string())),
+            "import_ndjson" => resume_import_ndjson(&window, app, &task, &checkpoint, timeout_ms)
+                .await
+                .and_then(|summary| serde_json::to_value(summary).map_err(|e| e.to_string())),
+            "export_ndjson" => resume_export_ndjson(&window, app, &task, &checkpoint, timeout_ms)
+                .await
+                .and_then(|summary| serde_json::to_value(summary).map_err(|e| e.to_string())),
+            other => Err(format!("Tasks of kind '{}' can't be resumed", other)),
+        },
+        Err(e) => Err(e),
+    };
+
+    task.finish(result)
+}
+
+#[command]
+pub fn clear_finished_app_tasks() -> Result<usize, String> {
+    let mut tasks = TASKS.lock();
+    let before = tasks.len();
+    tasks.retain(|entry| entry.info.state == "running");
+    Ok(before - tasks.len())
+}
+
+// Submits a wait_for_completion=false request and tracks the Elasticsearch task it creates
+async fn start_es_task(
+    window: &Window,
+    app: &AppHandle,
+    kind: &str,
+    description: String,
+    path: &str,
+    body: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<AppTask, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+
+    let submitted = transport
+        .json(Method::POST, path, body.as_ref())
+        .await
+        .map_err(|e| format!("Failed to start {} - {}", kind.replace('_', " "), e))?;
+    let es_task_id = submitted["task"]
+        .as_str()
+        .ok_or("Elasticsearch did not return a task id")?
+        .to_string();
+
+    let task = TaskHandle::start(app, kind, description);
+    task.set_es_task_id(&es_task_id);
+
+    println!("Started {} task: {}", kind, es_task_id);
+
+    let poller = task.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = poll_es_task(&transport, &poller, &es_task_id).await;
+        let _ = poller.finish(result);
+    });
+
+    task_snapshot()
+        .into_iter()
+        .find(|info| info.id == task.id)
+        .ok_or_else(|| "Task disappeared before it could be reported".to_string())
+}
+
+async fn poll_es_task(
+    transport: &Transport,
+    task: &TaskHandle,
+    es_task_id: &str,
+) -> Result<(), String> {
+    let mut cancel_sent = false;
+
+    loop {
+        tokio::time::sleep(std::time::Duration::from_millis(
+            app_settings().task_poll_interval_ms,
+        ))
+        .await;
+
+        if task.is_cancelled() && !cancel_sent {
+            transport
+                .json(
+                    Method::POST,
+                    &format!("/_tasks/{}/_cancel", es_task_id),
+                    None,
+                )
+                .await?;
+            cancel_sent = true;
+        }
+
+        let status_data = transport
+            .get_json(&format!("/_tasks/{}", es_task_id))
+            .await?;
+        let status = &status_data["task"]["status"];
+
+        // Reindex and delete-by-query report counts; force merge reports nothing
+        if let Some(total) = status["total"].as_u64().filter(|total| *total > 0) {
+            let done = [
+                "created",
+                "updated",
+                "deleted",
+                "noops",
+                "version_conflicts",
+            ]
+            .iter()
+            .filter_map(|key| status[*key].as_u64())
+            .sum::<u64>();
+            task.set_progress(done as f64 / total as f64 * 100.0);
+        }
+
+        if !status_data["completed"].as_bool().unwrap_or(false) {
+            continue;
+        }
+
+        if let Some(reason) = status_data["error"]["reason"].as_str() {
+            return Err(reason.to_string());
+        }
+
+        let response = status_data["response"].clone();
+        let failures = response["failures"]
+            .as_array()
+            .map(|f| f.len())
+            .unwrap_or(0);
+        task.set_result(response);
+
+        if cancel_sent {
+            return Err("Cancelled".to_string());
+        }
+        if failures > 0 {
+            return Err(format!("Completed with {} failures", failures));
+        }
+        return Ok(());
+    }
+}
+
+#[command]
+pub async fn start_reindex_task(
+    window: Window,
+    app: AppHandle,
+    source_index: String,
+    dest_index: String,
+    query: Option<serde_json::Value>,
+    requests_per_second: Option<f64>,
+    timeout_ms: Option<u64>,
+) -> Result<AppTask, String> {
+    let mut source = serde_json::json!({ "index": source_index });
+    if let Some(query) = query {
+        source["query"] = query;
+    }
+
+    let mut path = "/_reindex?wait_for_completion=false".to_string();
+    if let Some(rate) = requests_per_second {
+        path.push_str(&format!("&requests_per_second={}", rate));
+    }
+
+    start_es_task(
+        &window,
+        &app,
+        "reindex",
+        format!("Reindex {} into {}", source_index, dest_index),
+        &path,
+        Some(serde_json::json!({
+            "source": source,
+            "dest": { "index": dest_index }
+        })),
+        timeout_ms,
+    )
+    .await
+}
+
+// Needs a token from request_confirmation_token("start_delete_by_query_task", index)
+#[command]
+pub async fn start_delete_by_query_task(
+    window: Window,
+    app: AppHandle,
+    index: String,
+    query: serde_json::Value,
+    confirmation_token: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<AppTask, String> {
+    let (conn, _) = current_connection(window.label())?;
+    require_confirmation(
+        &conn.id,
+        "start_delete_by_query_task",
+        &index,
+        confirmation_token.as_deref(),
+    )?;
+
+    start_es_task(
+        &window,
+        &app,
+        "delete_by_query",
+        format!("Delete matching documents from {}", index),
+        &format!(
+            "/{}/_delete_by_query?wait_for_completion=false&conflicts=proceed",
+            encode_segment(&index)
+        ),
+        Some(serde_json::json!({ "query": query })),
+        timeout_ms,
+    )
+    .await
+}
+
+#[command]
+pub async fn start_force_merge_task(
+    window: Window,
+    app: AppHandle,
+    index: String,
+    max_num_segments: Option<u32>,
+    only_expunge_deletes: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<AppTask, String> {
+    let mut path = format!(
+        "/{}/_forcemerge?wait_for_completion=false",
+        encode_segment(&index)
+    );
+    if let Some(segments) = max_num_segments {
+        path.push_str(&format!("&max_num_segments={}", segments));
+    }
+    if only_expunge_deletes.unwrap_or(false) {
+        path.push_str("&only_expunge_deletes=true");
+    }
+
+    start_es_task(
+        &window,
+        &app,
+        "force_merge",
+        format!("Force merge {}", index),
+        &path,
+        None,
+        timeout_ms,
+    )
+    .await
+}
+
+#[command]
+pub async fn start_snapshot_task(
+    window: Window,
+    app: AppHandle,
+    repository: String,
+    snapshot: String,
+    indices: Option<Vec<String>>,
+    include_global_state: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<AppTask, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+
+    let mut snapshot_json = serde_json::json!({
+        "include_global_state": include_global_state.unwrap_or(true)
+    });
+    if let Some(indices) = indices.filter(|i| !i.is_empty()) {
+        snapshot_json["indices"] = serde_json::json!(indices.join(","));
+    }
+
+    let response = transport
+        .send(
+            Method::PUT,
+            &format!(
+                "/_snapshot/{}/{}",
+                encode_segment(&repository),
+                encode_segment(&snapshot)
+            ),
+            Some(&snapshot_json),
+        )
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = match response.text().await {
+            Ok(text) => describe_error_body(&text),
+            Err(_) => "Unable to read error response".to_string(),
+        };
+        return Err(format!(
+            "Failed to create snapshot - Status: {}, Response: {}",
+            status, error_text
+        ));
+    }
+
+    let task = TaskHandle::start(
+        &app,
+        "snapshot",
+        format!("Snapshot {} to {}", snapshot, repository),
+    );
+
+    let poller = task.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = poll_snapshot(&transport, &poller, &repository, &snapshot).await;
+        let _ = poller.finish(result);
+    });
+
+    println!("Started snapshot task: {}", task.id);
+
+    task_snapshot()
+        .into_iter()
+        .find(|info| info.id == task.id)
+        .ok_or_else(|| "Task disappeared before it could be reported".to_string())
+}
+
+async fn poll_snapshot(
+    transport: &Transport,
+    task: &TaskHandle,
+    repository: &str,
+    snapshot: &str,
+) -> Result<(), String> {
+    let mut cancel_sent = false;
+
+    loop {
+        tokio::time::sleep(std::time::Duration::from_millis(
+            app_settings().task_poll_interval_ms,
+        ))
+        .await;
+
+        // Deleting a running snapshot is how Elasticsearch aborts it
+        if task.is_cancelled() && !cancel_sent {
+            transport
+                .send(
+                    Method::DELETE,
+                    &format!(
+                        "/_snapshot/{}/{}",
+                        encode_segment(repository),
+                        encode_segment(snapshot)
+                    ),
+                    None,
+                )
+                .await?;
+            cancel_sent = true;
+        }
+
+        let progress = match fetch_snapshot_progress(transport, repository, snapshot).await {
+            Ok(progress) => progress,
+            // An aborted snapshot disappears from the repository
+            Err(_) if cancel_sent => return Err("Cancelled".to_string()),
+            Err(e) => return Err(e),
+        };
+        task.set_progress(progress.percent);
+
+        if !progress.finished {
+            continue;
+        }
+
+        let state = progress.state.clone();
+        task.set_result(serde_json::to_value(&progress).map_err(|e| e.to_string())?);
+
+        return match state.as_str() {
+            "SUCCESS" => Ok(()),
+            _ if cancel_sent => Err("Cancelled".to_string()),
+            _ => Err(format!("Snapshot finished in state {}", state)),
+        };
+    }
+}
+
+// Creates the destination with the new mapping, reindexes through a painless script and
+// checks the document counts line up once Elasticsearch reports the task complete
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn reindex_with_transform(
+    window: Window,
+    app: AppHandle,
+    source_index: String,
+    dest_index: String,
+    dest_mapping: serde_json::Value,
+    painless_script: Option<String>,
+    script_params: Option<serde_json::Value>,
+    slices: Option<u32>,
+    timeout_ms: Option<u64>,
+) -> Result<AppTask, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let dest_path = format!("/{}", encode_segment(&dest_index));
+
+    if transport
+        .send(Method::HEAD, &dest_path, None)
+        .await?
+        .status()
+        .is_success()
+    {
+        return Err(format!("Destination index '{}' already exists", dest_index));
+    }
+
+    // Accept either a bare mapping or a full create-index body with settings
+    let create_body =
+        if dest_mapping.get("mappings").is_some() || dest_mapping.get("settings").is_some() {
+            dest_mapping
+        } else {
+            serde_json::json!({ "mappings": dest_mapping })
+        };
+    transport
+        .json(Method::PUT, &dest_path, Some(&create_body))
+        .await
+        .map_err(|e| format!("Failed to create destination index - {}", e))?;
+
+    let mut body = serde_json::json!({
+        "source": { "index": source_index },
+        "dest": { "index": dest_index }
+    });
+    if let Some(script) = painless_script.filter(|s| !s.trim().is_empty()) {
+        body["script"] = serde_json::json!({ "source": script, "lang": "painless" });
+        if let Some(params) = script_params {
+            body["script"]["params"] = params;
+        }
+    }
+
+    let slices = match slices {
+        Some(count) if count > 0 => count.to_string(),
+        _ => "auto".to_string(),
+    };
+    let submitted = match transport
+        .json(
+            Method::POST,
+            &format!("/_reindex?wait_for_completion=false&slices={}", slices),
+            Some(&body),
+        )
+        .await
+    {
+        Ok(submitted) => submitted,
+        Err(e) => {
+            // Leave nothing behind so the wizard can simply be retried
+            let _ = transport.send(Method::DELETE, &dest_path, None).await;
+            return Err(format!("Failed to start reindex - {}", e));
+        }
+    };
+    let es_task_id = submitted["task"]
+        .as_str()
+        .ok_or("Elasticsearch did not return a task id")?
+        .to_string();
+
+    let task = TaskHandle::start(
+        &app,
+        "reindex",
+        format!(
+            "Reindex {} into {} with transform",
+            source_index, dest_index
+        ),
+    );
+    task.set_es_task_id(&es_task_id);
+
+    println!("Started reindex task: {}", es_task_id);
+
+    let poller = task.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = match poll_es_task(&transport, &poller, &es_task_id).await {
+            Ok(()) => verify_reindex_counts(&transport, &poller, &source_index, &dest_index).await,
+            Err(e) => Err(e),
+        };
+        let _ = poller.finish(result);
+    });
+
+    task_snapshot()
+        .into_iter()
+        .find(|info| info.id == task.id)
+        .ok_or_else(|| "Task disappeared before it could be reported".to_string())
+}
+
+// Documents the script dropped with ctx.op = 'noop' or 'delete' are expected to be missing
+async fn verify_reindex_counts(
+    transport: &Transport,
+    task: &TaskHandle,
+    source_index: &str,
+    dest_index: &str,
+) -> Result<(), String> {
+    let response = task_snapshot()
+        .into_iter()
+        .find(|info| info.id == task.id)
+        .and_then(|info| info.result)
+        .unwrap_or_default();
+
+    transport
+        .json(
+            Method::POST,
+            &format!("/{}/_refresh", encode_segment(dest_index)),
+            None,
+        )
+        .await?;
+
+    let count = |index: &str| {
+        let path = format!("/{}/_count", encode_segment(index));
+        async move {
+            transport
+                .get_json(&path)
+                .await
+                .map(|data| data["count"].as_u64().unwrap_or(0))
+        }
+    };
+    let source_count = count(source_index).await?;
+    let dest_count = count(dest_index).await?;
+    let skipped =
+        response["noops"].as_u64().unwrap_or(0) + response["deleted"].as_u64().unwrap_or(0);
+    let matches = dest_count + skipped == source_count;
+
+    let mut result = response;
+    result["verification"] = serde_json::json!({
+        "source_count": source_count,
+        "dest_count": dest_count,
+        "skipped": skipped,
+        "matches": matches
+    });
+    task.set_result(result);
+
+    if matches {
+        Ok(())
+    } else {
+        Err(format!(
+            "Document count mismatch: {} in {}, {} in {} ({} skipped by the script)",
+            source_count, source_index, dest_count, dest_index, skipped
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterTask {
+    // "node_id:task_number", what `_tasks/{id}/_cancel` expects
+    pub task_id: String,
+    pub node: String,
+    pub action: String,
+    pub description: String,
+    pub running_time_ms: u64,
+    pub cancellable: bool,
+    pub opaque_id: String,
+    // The app's own request id, matching list_active_requests
+    pub request_id: Option<u64>,
+}
+
+// Tasks on the cluster that were started by requests from this app instance
+#[command]
+pub async fn find_my_tasks(
+    window: Window,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<ClusterTask>, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+    let prefix = opaque_id_prefix();
+
+    let tasks_data = transport
+        .get_json("/_tasks?detailed=true&group_by=none")
+        .await?;
+
+    let mut tasks: Vec<ClusterTask> = tasks_data["tasks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|task| {
+            let opaque_id = task["headers"]["X-Opaque-Id"].as_str()?;
+            let request_id = opaque_id.strip_prefix(prefix)?.parse().ok();
+            Some(ClusterTask {
+                task_id: format!(
+                    "{}:{}",
+                    task["node"].as_str().unwrap_or(""),
+                    task["id"].as_u64().unwrap_or(0)
+                ),
+                node: task["node"].as_str().unwrap_or("").to_string(),
+                action: task["action"].as_str().unwrap_or("").to_string(),
+                description: task["description"].as_str().unwrap_or("").to_string(),
+                running_time_ms: task["running_time_in_nanos"].as_u64().unwrap_or(0) / 1_000_000,
+                cancellable: task["cancellable"].as_bool().unwrap_or(false),
+                opaque_id: opaque_id.to_string(),
+                request_id,
+            })
+        })
+        .collect();
+
+    // The heaviest queries are usually the longest running
+    tasks.sort_by_key(|task| std::cmp::Reverse(task.running_time_ms));
+
+    Ok(tasks)
+}
+
+// Only cancels tasks this app instance started, so it can't be used to kill other users' work
+#[command]
+pub async fn cancel_my_task(
+    window: Window,
+    task_id: String,
+    timeout_ms: Option<u64>,
+) -> Result<bool, String> {
+    let transport = Transport::current(window.label())?.with_timeout(timeout_ms);
+
+    let task_data = transport
+        .get_json(&format!("/_tasks/{}", encode_segment(&task_id)))
+        .await?;
+    let owned = task_data["task"]["headers"]["X-Opaque-Id"]
+        .as_str()
+        .is_some_and(|opaque_id| opaque_id.starts_with(opaque_id_prefix()));
+    if !owned {
+        return Err(format!("Task {} wasn't started by this app", task_id));
+    }
+
+    transport
+        .json(
+            Method::POST,
+            &format!("/_tasks/{}/_cancel", encode_segment(&task_id)),
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to cancel task {} - {}", task_id, e))?;
+
+    println!("Successfully cancelled task: {}", task_id);
+
+    Ok(true)
+}
@@ -0,0 +1,1175 @@
+// Elastiko - Elasticsearch GUI Client
+// elastico/src-tauri/src/transport.rs
+
+use crate::elasticsearch::{
+    connection_by_id, connection_timeout, create_auth_headers, current_connection,
+    describe_error_response, get_base_url, ElasticsearchConnection,
+};
+use crate::storage::app_settings;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS, NON_ALPHANUMERIC};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{Client as ReqwestClient, Method, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tauri::command;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{oneshot, Semaphore};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    // Total attempts including the first one; 1 disables retries
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 250,
+            max_delay_ms: 5000,
+        }
+    }
+}
+
+const MAX_RETRY_AFTER_SECS: u64 = 60;
+
+static RETRY_POLICY: Lazy<Mutex<RetryPolicy>> = Lazy::new(|| Mutex::new(RetryPolicy::default()));
+
+static RESPONSE_CACHE: Lazy<Mutex<HashMap<(String, String), CachedJson>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static LIMITERS: Lazy<Mutex<HashMap<String, ConnectionLimiter>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+// Tells this app instance's requests apart from other users' and other app windows' on a
+// shared cluster; every request is stamped with `X-Opaque-Id: <prefix><request id>`
+static OPAQUE_ID_PREFIX: Lazy<String> = Lazy::new(|| {
+    format!(
+        "elastiko-{:x}{:x}-",
+        std::process::id(),
+        chrono::Utc::now().timestamp_millis()
+    )
+});
+
+// Request, DNS and connection timings by lowercase host name, see get_http_client_stats
+static HOST_STATS: Lazy<Mutex<HashMap<String, HostStats>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static ACTIVE_REQUESTS: Lazy<Mutex<HashMap<u64, InFlightRequest>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Commands running under an id the UI chose, see cancellable
+static CANCELLABLE_COMMANDS: Lazy<Mutex<HashMap<String, oneshot::Sender<()>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+tokio::task_local! {
+    static CALLER_REQUEST_ID: String;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveRequest {
+    pub id: u64,
+    pub method: String,
+    pub path: String,
+    pub started_at: u64,
+    // "queued" while waiting for a free slot on its connection or backing off, then "sending"
+    pub state: String,
+    // Shows up in `_tasks` and the slow logs, see find_my_tasks
+    pub opaque_id: String,
+    // The id the UI gave the command that sent this, if it gave one
+    pub request_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HttpClientStats {
+    pub connection_id: String,
+    pub host: String,
+    pub requests: u64,
+    // Requests that never got a response (connect errors, timeouts, resets)
+    pub failed_requests: u64,
+    // Time to response headers per attempt; retries count as requests of their own
+    pub avg_request_ms: f64,
+    pub max_request_ms: u64,
+    pub dns_lookups: u64,
+    pub dns_failures: u64,
+    pub avg_dns_ms: f64,
+    pub max_dns_ms: u64,
+    pub resolved_addresses: Vec<String>,
+    // Connections the client's connector opened; every other request reused a pooled one
+    pub connections_opened: u64,
+    pub connect_failures: u64,
+    pub reused_requests: u64,
+    pub reuse_ratio: Option<f64>,
+    // TCP connect plus TLS handshake of each new connection, without its DNS lookup
+    pub avg_handshake_ms: f64,
+    pub max_handshake_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestQueueStats {
+    pub origin: String,
+    pub limit: usize,
+    pub in_flight: usize,
+    pub queued: usize,
+}
+
+struct ConnectionLimiter {
+    limit: usize,
+    semaphore: Arc<Semaphore>,
+    queued: Arc<AtomicUsize>,
+}
+
+// Counts a request as queued for exactly as long as it waits, even if it's cancelled meanwhile
+struct QueuedGuard(Arc<AtomicUsize>);
+
+impl Drop for QueuedGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Default)]
+struct HostStats {
+    requests: u64,
+    failed_requests: u64,
+    request_ms_total: u64,
+    request_ms_max: u64,
+    dns_lookups: u64,
+    dns_failures: u64,
+    dns_ms_total: u64,
+    dns_ms_max: u64,
+    resolved_addresses: Vec<String>,
+    connections_opened: u64,
+    connect_failures: u64,
+    handshake_ms_total: u64,
+    handshake_ms_max: u64,
+}
+
+struct CachedJson {
+    // Scheme, host and port, so a write to a cluster drops everything cached for it
+    origin: String,
+    value: serde_json::Value,
+    stored_at: std::time::Instant,
+}
+
+struct InFlightRequest {
+    info: ActiveRequest,
+    cancel: oneshot::Sender<()>,
+}
+
+#[derive(Debug)]
+pub(crate) enum TransportError {
+    Http(reqwest::Error),
+    Cancelled(u64),
+}
+
+impl std::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransportError::Http(e) => write!(f, "{}", e),
+            TransportError::Cancelled(id) => write!(f, "Request {} was cancelled", id),
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+// Removes the registry entry however the request ends, including when the caller drops the future
+struct RegistrationGuard(u64);
+
+impl Drop for RegistrationGuard {
+    fn drop(&mut self) {
+        ACTIVE_REQUESTS.lock().remove(&self.0);
+    }
+}
+
+struct CancellableGuard(String);
+
+impl Drop for CancellableGuard {
+    fn drop(&mut self) {
+        CANCELLABLE_COMMANDS.lock().remove(&self.0);
+    }
+}
+
+// Everything that would end or split a path segment; `,` and `*` are left alone so
+// multi-target expressions like `logs-*,metrics` keep working
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'&')
+    .add(b'+')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+const QUERY_VALUE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~')
+    .remove(b',')
+    .remove(b'*');
+
+// Index names, document ids, user names etc. must go through this before landing in a URL path
+pub(crate) fn encode_segment(value: &str) -> String {
+    utf8_percent_encode(value, PATH_SEGMENT).to_string()
+}
+
+// Encodes each name on its own and joins them with the comma Elasticsearch uses for lists
+pub(crate) fn encode_segments(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| encode_segment(value))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub(crate) fn encode_query(value: &str) -> String {
+    utf8_percent_encode(value, QUERY_VALUE).to_string()
+}
+
+// POST endpoints that only read, so repeating them can't change anything
+const READ_ONLY_POST_ENDPOINTS: &[&str] = &[
+    "_search",
+    "_msearch",
+    "_count",
+    "_mget",
+    "_field_caps",
+    "_validate",
+    "_explain",
+    "_has_privileges",
+    "_preview",
+    "_rollup_search",
+    "explore",
+];
+
+// Read-only POST endpoints whose last segment is shared with ones that write, e.g. watcher's
+// `_execute` runs the watch's actions
+const READ_ONLY_POST_PATHS: &[&str] = &["/_scripts/painless/_execute"];
+
+pub(crate) trait RequestExt {
+    fn with_timeout(self, timeout_ms: Option<u64>) -> Self;
+
+    fn send_with_retry(
+        self,
+    ) -> impl std::future::Future<Output = Result<Response, TransportError>> + Send;
+}
+
+impl RequestExt for RequestBuilder {
+    // Overrides the client-wide timeout for this request when the caller asked for one
+    fn with_timeout(self, timeout_ms: Option<u64>) -> Self {
+        match timeout_ms {
+            Some(ms) => self.timeout(std::time::Duration::from_millis(ms)),
+            None => self,
+        }
+    }
+
+    fn send_with_retry(
+        self,
+    ) -> impl std::future::Future<Output = Result<Response, TransportError>> + Send {
+        send_tracked(self)
+    }
+}
+
+fn is_read_only_post(path: &str) -> bool {
+    READ_ONLY_POST_PATHS.contains(&path)
+        || path
+            .rsplit('/')
+            .next()
+            .map(|endpoint| READ_ONLY_POST_ENDPOINTS.contains(&endpoint))
+            .unwrap_or(false)
+}
+
+fn is_read_only(method: &Method, path: &str) -> bool {
+    match *method {
+        Method::GET | Method::HEAD | Method::OPTIONS => true,
+        Method::POST => is_read_only_post(path),
+        _ => false,
+    }
+}
+
+fn is_idempotent(method: &Method, path: &str) -> bool {
+    match *method {
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS => true,
+        Method::POST => is_read_only_post(path),
+        _ => false,
+    }
+}
+
+fn should_retry_status(status: StatusCode, idempotent: bool, retry_rejections: bool) -> bool {
+    match status {
+        // A 429 means the request was rejected before doing any work, so any method may retry
+        StatusCode::TOO_MANY_REQUESTS => retry_rejections,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT => {
+            idempotent
+        }
+        _ => false,
+    }
+}
+
+// Refused or reset connections are what a restarting node looks like; timeouts are not retried
+fn is_transient(error: &reqwest::Error) -> bool {
+    if error.is_connect() {
+        return true;
+    }
+
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        if let Some(io_error) = cause.downcast_ref::<std::io::Error>() {
+            return matches!(
+                io_error.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+            );
+        }
+        source = cause.source();
+    }
+
+    false
+}
+
+// Exponential backoff with "equal jitter": half the delay is fixed, half is random
+fn backoff_delay(policy: &RetryPolicy, attempt: u32) -> std::time::Duration {
+    let exponential = policy
+        .base_delay_ms
+        .saturating_mul(1u64 << attempt.saturating_sub(1).min(16));
+    let capped = exponential.min(policy.max_delay_ms).max(1);
+
+    let noise = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(0);
+
+    std::time::Duration::from_millis(capped / 2 + noise % (capped / 2 + 1))
+}
+
+// How long a 429 asked us to wait, from `Retry-After` in seconds or as an HTTP date. Capped
+// so a misconfigured proxy can't stall a request indefinitely.
+pub(crate) fn retry_after(response: &Response) -> Option<std::time::Duration> {
+    let value = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?;
+    parse_retry_after(value)
+}
+
+fn parse_retry_after(value: &str) -> Option<std::time::Duration> {
+    let value = value.trim();
+    let seconds = match value.parse::<u64>() {
+        Ok(seconds) => seconds,
+        Err(_) => {
+            let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+            (at.timestamp() - chrono::Utc::now().timestamp()).max(0) as u64
+        }
+    };
+
+    Some(std::time::Duration::from_secs(
+        seconds.min(MAX_RETRY_AFTER_SECS),
+    ))
+}
+
+// Registers the request so `cancel_request` can abort it while it is in flight
+pub(crate) async fn send_tracked(request: RequestBuilder) -> Result<Response, TransportError> {
+    let (method, path, origin, host, has_timeout, writes) =
+        match request.try_clone().and_then(|r| r.build().ok()) {
+            Some(built) => (
+                built.method().to_string(),
+                built.url().path().to_string(),
+                built.url().origin().ascii_serialization(),
+                built.url().host_str().unwrap_or("").to_string(),
+                built.timeout().is_some(),
+                !is_read_only(built.method(), built.url().path()),
+            ),
+            None => (
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                false,
+                true,
+            ),
+        };
+
+    // Requests that didn't ask for their own timeout get the one from the app settings
+    let request = if has_timeout {
+        request
+    } else {
+        request.timeout(std::time::Duration::from_millis(
+            app_settings().default_timeout_ms,
+        ))
+    };
+
+    let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    let opaque_id = format!("{}{}", OPAQUE_ID_PREFIX.as_str(), id);
+    let request = request.header("X-Opaque-Id", &opaque_id);
+    let (cancel, cancelled) = oneshot::channel();
+    let started_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    ACTIVE_REQUESTS.lock().insert(
+        id,
+        InFlightRequest {
+            info: ActiveRequest {
+                id,
+                method,
+                path,
+                started_at,
+                state: "queued".to_string(),
+                opaque_id,
+                request_id: CALLER_REQUEST_ID.try_with(|id| id.clone()).ok(),
+            },
+            cancel,
+        },
+    );
+    let _guard = RegistrationGuard(id);
+
+    let result = tokio::select! {
+        result = send_limited(id, &origin, &host, request) => result.map_err(TransportError::Http),
+        Ok(()) = cancelled => {
+            println!("Cancelled request {}", id);
+            Err(TransportError::Cancelled(id))
+        }
+    };
+
+    // Even a failed or cancelled write may have changed something, so don't trust the cache
+    if writes {
+        RESPONSE_CACHE
+            .lock()
+            .retain(|_, cached| !origin.is_empty() && cached.origin != origin);
+    }
+
+    result
+}
+
+// Runs a command's work under an id chosen by the UI, so cancel_request can abort all of it,
+// including reading a response body that is still coming in. Without an id the work just runs.
+pub(crate) async fn cancellable<T>(
+    request_id: Option<String>,
+    work: impl std::future::Future<Output = Result<T, String>>,
+) -> Result<T, String> {
+    let Some(request_id) = request_id else {
+        return work.await;
+    };
+
+    let (cancel, cancelled) = oneshot::channel();
+    {
+        let mut commands = CANCELLABLE_COMMANDS.lock();
+        if commands.contains_key(&request_id) {
+            return Err(format!("Request id '{}' is already in use", request_id));
+        }
+        commands.insert(request_id.clone(), cancel);
+    }
+    let _guard = CancellableGuard(request_id.clone());
+
+    tokio::select! {
+        result = CALLER_REQUEST_ID.scope(request_id.clone(), work) => result,
+        Ok(()) = cancelled => {
+            println!("Cancelled request {}", request_id);
+            Err(format!("Request {} was cancelled", request_id))
+        }
+    }
+}
+
+pub(crate) fn opaque_id_prefix() -> &'static str {
+    OPAQUE_ID_PREFIX.as_str()
+}
+
+// Returns a cached response body if one was stored for this connection and URL within the TTL
+pub(crate) fn cached_json(conn_id: &str, url: &str) -> Option<serde_json::Value> {
+    let ttl = std::time::Duration::from_secs(app_settings().cache_ttl_secs);
+    let mut cache = RESPONSE_CACHE.lock();
+    let key = (conn_id.to_string(), url.to_string());
+
+    match cache.get(&key) {
+        Some(cached) if cached.stored_at.elapsed() < ttl => Some(cached.value.clone()),
+        Some(_) => {
+            cache.remove(&key);
+            None
+        }
+        None => None,
+    }
+}
+
+pub(crate) fn store_cached_json(conn_id: &str, url: &str, value: &serde_json::Value) {
+    if app_settings().cache_ttl_secs == 0 {
+        return;
+    }
+
+    let origin = reqwest::Url::parse(url)
+        .map(|parsed| parsed.origin().ascii_serialization())
+        .unwrap_or_default();
+
+    RESPONSE_CACHE.lock().insert(
+        (conn_id.to_string(), url.to_string()),
+        CachedJson {
+            origin,
+            value: value.clone(),
+            stored_at: std::time::Instant::now(),
+        },
+    );
+}
+
+fn limiter_for(origin: &str) -> (Arc<Semaphore>, Arc<AtomicUsize>) {
+    let limit = app_settings().max_concurrent_requests.max(1);
+    let mut limiters = LIMITERS.lock();
+
+    // A changed limit gets a fresh semaphore; requests holding the old one finish undisturbed
+    let limiter = limiters
+        .entry(origin.to_string())
+        .or_insert_with(|| ConnectionLimiter {
+            limit,
+            semaphore: Arc::new(Semaphore::new(limit)),
+            queued: Arc::new(AtomicUsize::new(0)),
+        });
+    if limiter.limit != limit {
+        limiter.limit = limit;
+        limiter.semaphore = Arc::new(Semaphore::new(limit));
+    }
+
+    (limiter.semaphore.clone(), limiter.queued.clone())
+}
+
+tokio::task_local! {
+    // Host of the request being sent, so a connection opened for it is counted against that host
+    static REQUEST_HOST: String;
+    // DNS time of the connection being opened, so the handshake time can leave it out
+    static CONNECT_DNS_MS: Arc<AtomicU64>;
+}
+
+// The client resolves hosts through this to time lookups
+pub(crate) struct TimedResolver;
+
+impl Resolve for TimedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_ascii_lowercase();
+        let connect_dns_ms = CONNECT_DNS_MS.try_with(|ms| ms.clone()).ok();
+        Box::pin(async move {
+            let started = std::time::Instant::now();
+            let result = tokio::net::lookup_host((host.as_str(), 0))
+                .await
+                .map(|addrs| addrs.collect::<Vec<SocketAddr>>());
+            let elapsed = started.elapsed().as_millis() as u64;
+            if let Some(connect_dns_ms) = connect_dns_ms {
+                connect_dns_ms.fetch_add(elapsed, Ordering::Relaxed);
+            }
+
+            let mut stats = HOST_STATS.lock();
+            let host_stats = stats.entry(host).or_default();
+            host_stats.dns_lookups += 1;
+            host_stats.dns_ms_total += elapsed;
+            host_stats.dns_ms_max = host_stats.dns_ms_max.max(elapsed);
+
+            match result {
+                Ok(addrs) => {
+                    host_stats.resolved_addresses =
+                        addrs.iter().map(|addr| addr.ip().to_string()).collect();
+                    Ok(Box::new(addrs.into_iter()) as Addrs)
+                }
+                Err(e) => {
+                    host_stats.dns_failures += 1;
+                    Err(e.into())
+                }
+            }
+        })
+    }
+}
+
+// Wraps the client's connector, which resolves, connects and runs the TLS handshake for every
+// connection the pool opens, so each one is counted and timed
+#[derive(Clone)]
+pub(crate) struct ConnectTimingLayer;
+
+impl<S> tower_layer::Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTiming { inner }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ConnectTiming<S> {
+    inner: S,
+}
+
+impl<S, R> tower_service::Service<R> for ConnectTiming<S>
+where
+    S: tower_service::Service<R>,
+    S::Future: Send + 'static,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        // The pool starts connecting while the request's own future is polled, even when the
+        // connection is finished in the background later
+        let host = REQUEST_HOST
+            .try_with(|host| host.clone())
+            .ok()
+            .filter(|host| !host.is_empty());
+        let dns_ms = Arc::new(AtomicU64::new(0));
+        let connecting = CONNECT_DNS_MS.scope(dns_ms.clone(), self.inner.call(request));
+
+        Box::pin(async move {
+            let started = std::time::Instant::now();
+            let result = connecting.await;
+            if let Some(host) = host {
+                let elapsed = started.elapsed().as_millis() as u64;
+                record_connect(
+                    &host,
+                    elapsed.saturating_sub(dns_ms.load(Ordering::Relaxed)),
+                    result.is_ok(),
+                );
+            }
+            result
+        })
+    }
+}
+
+fn record_connect(host: &str, handshake_ms: u64, connected: bool) {
+    let mut stats = HOST_STATS.lock();
+    let host_stats = stats.entry(host.to_string()).or_default();
+    if connected {
+        host_stats.connections_opened += 1;
+        host_stats.handshake_ms_total += handshake_ms;
+        host_stats.handshake_ms_max = host_stats.handshake_ms_max.max(handshake_ms);
+    } else {
+        host_stats.connect_failures += 1;
+    }
+}
+
+fn record_request(host: &str, elapsed_ms: u64, failed: bool) {
+    let mut stats = HOST_STATS.lock();
+    let host_stats = stats.entry(host.to_ascii_lowercase()).or_default();
+    host_stats.requests += 1;
+    host_stats.request_ms_total += elapsed_ms;
+    host_stats.request_ms_max = host_stats.request_ms_max.max(elapsed_ms);
+    if failed {
+        host_stats.failed_requests += 1;
+    }
+}
+
+// Waits for a free slot on the request's cluster so rapid clicking can't flood it. The slot is
+// held for one attempt at a time and given back while backing off, so a retrying request never
+// keeps others waiting through its pause.
+async fn send_limited(
+    id: u64,
+    origin: &str,
+    host: &str,
+    request: RequestBuilder,
+) -> Result<Response, reqwest::Error> {
+    let policy = *RETRY_POLICY.lock();
+
+    // Streaming bodies can't be cloned, so those requests only get a single attempt
+    let (method, path) = match request.try_clone().and_then(|r| r.build().ok()) {
+        Some(built) => (built.method().clone(), built.url().path().to_string()),
+        None => return send_attempt(id, origin, host, request).await,
+    };
+    let idempotent = is_idempotent(&method, &path);
+    // BulkBuilder paces `_bulk` rejections itself under its own pause budget; retrying them here
+    // as well would nest the two backoffs
+    let retry_rejections = !path.ends_with("/_bulk");
+
+    let mut attempt = 1;
+    loop {
+        let current = match request.try_clone() {
+            Some(current) if attempt < policy.max_attempts => current,
+            _ => return send_attempt(id, origin, host, request).await,
+        };
+
+        let delay = match send_attempt(id, origin, host, current).await {
+            Ok(response)
+                if should_retry_status(response.status(), idempotent, retry_rejections) =>
+            {
+                println!(
+                    "Retrying {} {} after status {} (attempt {} of {})",
+                    method,
+                    path,
+                    response.status(),
+                    attempt,
+                    policy.max_attempts
+                );
+                // The server knows best when it will have capacity again
+                retry_after(&response).unwrap_or_else(|| backoff_delay(&policy, attempt))
+            }
+            Ok(response) => return Ok(response),
+            Err(e) if idempotent && is_transient(&e) => {
+                println!(
+                    "Retrying {} {} after error: {} (attempt {} of {})",
+                    method, path, e, attempt, policy.max_attempts
+                );
+                backoff_delay(&policy, attempt)
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some(request) = ACTIVE_REQUESTS.lock().get_mut(&id) {
+            request.info.state = "queued".to_string();
+        }
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+// One attempt, holding a slot on the cluster only while it is being sent
+async fn send_attempt(
+    id: u64,
+    origin: &str,
+    host: &str,
+    request: RequestBuilder,
+) -> Result<Response, reqwest::Error> {
+    let (semaphore, queued) = limiter_for(origin);
+
+    queued.fetch_add(1, Ordering::Relaxed);
+    let waiting = QueuedGuard(queued);
+    // The semaphore is never closed, so acquiring only fails if that invariant is broken
+    let _permit = semaphore.acquire_owned().await.ok();
+    drop(waiting);
+
+    if let Some(request) = ACTIVE_REQUESTS.lock().get_mut(&id) {
+        request.info.state = "sending".to_string();
+    }
+
+    let started = std::time::Instant::now();
+    let result = REQUEST_HOST
+        .scope(host.to_ascii_lowercase(), request.send())
+        .await;
+    if !host.is_empty() {
+        record_request(host, started.elapsed().as_millis() as u64, result.is_err());
+    }
+
+    result
+}
+
+// A connection paired with the shared client; builds the URL, auth headers and timeout for each
+// request so callers only supply the method, path and body
+#[derive(Clone)]
+pub(crate) struct Transport {
+    client: ReqwestClient,
+    conn: ElasticsearchConnection,
+    timeout_ms: Option<u64>,
+}
+
+impl Transport {
+    pub(crate) fn new(client: ReqwestClient, conn: ElasticsearchConnection) -> Self {
+        Transport {
+            client,
+            conn,
+            timeout_ms: None,
+        }
+    }
+
+    pub(crate) fn current(window_label: &str) -> Result<Self, String> {
+        let (conn, client) = current_connection(window_label)?;
+        Ok(Transport::new(client, conn))
+    }
+
+    pub(crate) fn connection_id(&self) -> &str {
+        &self.conn.id
+    }
+
+    pub(crate) fn connection(&self) -> &ElasticsearchConnection {
+        &self.conn
+    }
+
+    // Same client and timeout, different credentials; for requests that must not authenticate
+    // as the connection itself
+    pub(crate) fn for_connection(&self, conn: ElasticsearchConnection) -> Self {
+        Transport {
+            client: self.client.clone(),
+            conn,
+            timeout_ms: self.timeout_ms,
+        }
+    }
+
+    pub(crate) fn with_timeout(mut self, timeout_ms: Option<u64>) -> Self {
+        self.timeout_ms = connection_timeout(&self.conn, timeout_ms);
+        self
+    }
+
+    // `path` is everything after the host, starting with a slash and including any query string
+    pub(crate) fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, String> {
+        let url = format!("{}{}", get_base_url(&self.conn), path);
+        let headers = create_auth_headers(&self.conn)?;
+
+        Ok(self
+            .client
+            .request(method, &url)
+            .headers(headers)
+            .with_timeout(self.timeout_ms))
+    }
+
+    // `request` with the newline-delimited JSON content type _bulk expects; `headers` replaces the
+    // JSON one rather than adding a second Content-Type, which ES rejects
+    pub(crate) fn ndjson_request(
+        &self,
+        method: Method,
+        path: &str,
+    ) -> Result<RequestBuilder, String> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-ndjson"),
+        );
+
+        Ok(self.request(method, path)?.headers(headers))
+    }
+
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<Response, String> {
+        let mut request = self.request(method, path)?;
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+
+        request.send_with_retry().await.map_err(|e| e.to_string())
+    }
+
+    // Sends the request and parses a successful response; failures carry the status and ES error
+    pub(crate) async fn json(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        let response = self.send(method, path, body).await?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "request to {} failed: {}",
+                path,
+                describe_error_response(response).await
+            ));
+        }
+
+        response.json().await.map_err(|e| e.to_string())
+    }
+
+    pub(crate) async fn get_json(&self, path: &str) -> Result<serde_json::Value, String> {
+        self.json(Method::GET, path, None).await
+    }
+
+    // Writes a successful response body to `file` chunk by chunk and returns its size, so
+    // huge bodies never sit in memory as one string
+    pub(crate) async fn download(&self, path: &str, file: &std::path::Path) -> Result<u64, String> {
+        let mut response = self.send(Method::GET, path, None).await?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "request to {} failed: {}",
+                path,
+                describe_error_response(response).await
+            ));
+        }
+
+        let mut out = tokio::fs::File::create(file)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", file.display(), e))?;
+        let mut written = 0u64;
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            out.write_all(&chunk).await.map_err(|e| e.to_string())?;
+            written += chunk.len() as u64;
+        }
+        out.flush().await.map_err(|e| e.to_string())?;
+
+        Ok(written)
+    }
+
+    // For slow-changing metadata (mappings, settings, field caps, templates)
+    pub(crate) async fn get_json_cached(&self, path: &str) -> Result<serde_json::Value, String> {
+        let url = format!("{}{}", get_base_url(&self.conn), path);
+        if let Some(value) = cached_json(&self.conn.id, &url) {
+            return Ok(value);
+        }
+
+        let value = self.get_json(path).await?;
+        store_cached_json(&self.conn.id, &url, &value);
+
+        Ok(value)
+    }
+}
+
+#[command]
+pub fn get_retry_policy() -> Result<RetryPolicy, String> {
+    Ok(*RETRY_POLICY.lock())
+}
+
+#[command]
+pub fn set_retry_policy(policy: RetryPolicy) -> Result<RetryPolicy, String> {
+    if policy.max_attempts == 0 {
+        return Err("max_attempts must be at least 1".to_string());
+    }
+
+    let policy = RetryPolicy {
+        max_delay_ms: policy.max_delay_ms.max(policy.base_delay_ms),
+        ..policy
+    };
+    *RETRY_POLICY.lock() = policy;
+
+    Ok(policy)
+}
+
+#[command]
+pub fn list_active_requests() -> Result<Vec<ActiveRequest>, String> {
+    let mut requests: Vec<ActiveRequest> = ACTIVE_REQUESTS
+        .lock()
+        .values()
+        .map(|request| request.info.clone())
+        .collect();
+    requests.sort_by_key(|request| request.id);
+
+    Ok(requests)
+}
+
+// Cancels either a single HTTP request by the id list_active_requests shows, or a whole
+// command by the request_id the UI passed to it
+#[command]
+pub fn cancel_request(id: Option<u64>, request_id: Option<String>) -> Result<(), String> {
+    let cancel = match (id, request_id) {
+        (_, Some(request_id)) => CANCELLABLE_COMMANDS
+            .lock()
+            .remove(&request_id)
+            .ok_or_else(|| format!("No active request with id '{}'", request_id))?,
+        (Some(id), None) => {
+            ACTIVE_REQUESTS
+                .lock()
+                .remove(&id)
+                .ok_or_else(|| format!("No active request with id {}", id))?
+                .cancel
+        }
+        (None, None) => return Err("A request id is required".to_string()),
+    };
+
+    // The receiver is gone if the request finished in the meantime, which is fine
+    let _ = cancel.send(());
+
+    Ok(())
+}
+
+#[command]
+pub fn clear_response_cache() -> Result<usize, String> {
+    let mut cache = RESPONSE_CACHE.lock();
+    let cleared = cache.len();
+    cache.clear();
+
+    Ok(cleared)
+}
+
+#[command]
+pub fn get_request_queue_stats() -> Result<Vec<RequestQueueStats>, String> {
+    let limiters = LIMITERS.lock();
+    let mut stats: Vec<RequestQueueStats> = limiters
+        .iter()
+        .map(|(origin, limiter)| RequestQueueStats {
+            origin: origin.clone(),
+            limit: limiter.limit,
+            in_flight: limiter.limit - limiter.semaphore.available_permits(),
+            queued: limiter.queued.load(Ordering::Relaxed),
+        })
+        .collect();
+    stats.sort_by(|a, b| a.origin.cmp(&b.origin));
+
+    Ok(stats)
+}
+
+// Explains slow requests on a connection: DNS time, how often the pool had to open a new
+// connection instead of reusing one, how long the TCP connect and TLS handshake of each new one
+// took, and overall request latency. All of it is measured by the client's resolver and
+// connector, see TimedResolver and ConnectTimingLayer.
+#[command]
+pub fn get_http_client_stats(connection_id: String) -> Result<HttpClientStats, String> {
+    let conn = connection_by_id(&connection_id)
+        .ok_or_else(|| format!("Connection {} isn't open", connection_id))?;
+    let host = conn.host.to_ascii_lowercase();
+
+    let stats = HOST_STATS.lock();
+    let empty = HostStats::default();
+    let host_stats = stats.get(&host).unwrap_or(&empty);
+
+    let average = |total: u64, count: u64| {
+        if count == 0 {
+            0.0
+        } else {
+            total as f64 / count as f64
+        }
+    };
+
+    let reused_requests = host_stats
+        .requests
+        .saturating_sub(host_stats.connections_opened);
+    let reuse_ratio =
+        (host_stats.requests > 0).then(|| reused_requests as f64 / host_stats.requests as f64);
+
+    Ok(HttpClientStats {
+        connection_id,
+        host,
+        requests: host_stats.requests,
+        failed_requests: host_stats.failed_requests,
+        avg_request_ms: average(host_stats.request_ms_total, host_stats.requests),
+        max_request_ms: host_stats.request_ms_max,
+        dns_lookups: host_stats.dns_lookups,
+        dns_failures: host_stats.dns_failures,
+        avg_dns_ms: average(host_stats.dns_ms_total, host_stats.dns_lookups),
+        max_dns_ms: host_stats.dns_ms_max,
+        resolved_addresses: host_stats.resolved_addresses.clone(),
+        connections_opened: host_stats.connections_opened,
+        connect_failures: host_stats.connect_failures,
+        reused_requests,
+        reuse_ratio,
+        avg_handshake_ms: average(host_stats.handshake_ms_total, host_stats.connections_opened),
+        max_handshake_ms: host_stats.handshake_ms_max,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_segment_escapes_path_separators() {
+        assert_eq!(encode_segment("logs-2024.01"), "logs-2024.01");
+        assert_eq!(encode_segment("logs-*,metrics"), "logs-*,metrics");
+        assert_eq!(encode_segment("a/b"), "a%2Fb");
+        assert_eq!(encode_segment("id?x=1#y"), "id%3Fx=1%23y");
+        assert_eq!(encode_segment("50% off"), "50%25%20off");
+        assert_eq!(encode_segment("a+b&c"), "a%2Bb%26c");
+        assert_eq!(encode_segment("..\\x"), "..%5Cx");
+        assert_eq!(encode_segment("café"), "caf%C3%A9");
+        assert_eq!(
+            encode_segments(&["a/b".to_string(), "c".to_string()]),
+            "a%2Fb,c"
+        );
+    }
+
+    #[test]
+    fn read_only_requests() {
+        assert!(is_read_only(&Method::GET, "/logs/_doc/1"));
+        assert!(is_read_only(&Method::HEAD, "/logs"));
+        assert!(is_read_only(&Method::POST, "/logs/_search"));
+        assert!(is_read_only(&Method::POST, "/_msearch"));
+        assert!(is_read_only(&Method::POST, "/logs/_count"));
+        assert!(is_read_only(&Method::POST, "/_scripts/painless/_execute"));
+
+        assert!(!is_read_only(&Method::POST, "/_watcher/watch/x/_execute"));
+        assert!(!is_read_only(&Method::POST, "/logs/_doc"));
+        assert!(!is_read_only(&Method::POST, "/_bulk"));
+        assert!(!is_read_only(&Method::POST, "/logs/_delete_by_query"));
+        assert!(!is_read_only(&Method::PUT, "/logs"));
+        assert!(!is_read_only(&Method::DELETE, "/logs"));
+    }
+
+    #[test]
+    fn idempotent_requests() {
+        assert!(is_idempotent(&Method::GET, "/_cluster/health"));
+        assert!(is_idempotent(&Method::PUT, "/logs/_doc/1"));
+        assert!(is_idempotent(&Method::DELETE, "/logs"));
+        assert!(is_idempotent(&Method::POST, "/logs/_search"));
+        assert!(is_idempotent(&Method::POST, "/_scripts/painless/_execute"));
+
+        assert!(!is_idempotent(&Method::POST, "/_watcher/watch/x/_execute"));
+        assert!(!is_idempotent(&Method::POST, "/logs/_doc"));
+        assert!(!is_idempotent(&Method::POST, "/logs/_update/1"));
+        assert!(!is_idempotent(&Method::PATCH, "/logs"));
+    }
+
+    #[test]
+    fn retries_gateway_errors_only_when_idempotent() {
+        assert!(should_retry_status(StatusCode::BAD_GATEWAY, true, false));
+        assert!(!should_retry_status(StatusCode::BAD_GATEWAY, false, false));
+        assert!(should_retry_status(
+            StatusCode::TOO_MANY_REQUESTS,
+            false,
+            true
+        ));
+        assert!(!should_retry_status(
+            StatusCode::TOO_MANY_REQUESTS,
+            true,
+            false
+        ));
+        assert!(!should_retry_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            true,
+            true
+        ));
+    }
+
+    #[test]
+    fn backoff_grows_and_stays_under_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay_ms: 100,
+            max_delay_ms: 1000,
+        };
+        let ms = |attempt| backoff_delay(&policy, attempt).as_millis() as u64;
+
+        assert!((50..=100).contains(&ms(1)));
+        assert!((100..=200).contains(&ms(2)));
+        assert!((200..=400).contains(&ms(3)));
+        assert!((500..=1000).contains(&ms(5)));
+        assert!((500..=1000).contains(&ms(40)));
+    }
+
+    #[test]
+    fn retry_after_seconds_and_dates() {
+        assert_eq!(
+            parse_retry_after(" 5 "),
+            Some(std::time::Duration::from_secs(5))
+        );
+        assert_eq!(
+            parse_retry_after("3600"),
+            Some(std::time::Duration::from_secs(MAX_RETRY_AFTER_SECS))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(std::time::Duration::ZERO)
+        );
+
+        let soon = (chrono::Utc::now() + chrono::Duration::seconds(30)).to_rfc2822();
+        let wait = parse_retry_after(&soon).unwrap().as_secs();
+        assert!((28..=30).contains(&wait));
+
+        assert_eq!(parse_retry_after("soon"), None);
+        assert_eq!(parse_retry_after("-1"), None);
+    }
+}
@@ -212,6 +212,17 @@ export class ElasticsearchService {
     }
   }
 
+  /**
+   * Get a single-use token for a destructive operation the user has already confirmed
+   * @param action - The name of the command the token is for
+   * @param target - The index or other resource the command acts on
+   * @returns The token to pass to the command
+   */
+  private async requestConfirmationToken(action: string, target: string): Promise<string> {
+    const confirmation = await invoke<{ token: string }>('request_confirmation_token', { action, target });
+    return confirmation.token;
+  }
+
   /**
    * Delete an Elasticsearch index
    * @param indexName - The name of the index to delete
@@ -224,7 +235,8 @@ export class ElasticsearchService {
 
     try {
       // Call the Rust backend to delete the index
-      const result = await invoke<boolean>('delete_elasticsearch_index', { index: indexName });
+      const confirmationToken = await this.requestConfirmationToken('delete_elasticsearch_index', indexName);
+      const result = await invoke<boolean>('delete_elasticsearch_index', { index: indexName, confirmationToken });
       return result;
     } catch (error) {
       console.error(`Failed to delete index ${indexName}:`, error);
@@ -244,7 +256,8 @@ export class ElasticsearchService {
 
     try {
       // Call the Rust backend to delete all documents in the index
-      const deleted = await invoke<number>('delete_all_documents_in_index', { index: indexName });
+      const confirmationToken = await this.requestConfirmationToken('delete_all_documents_in_index', indexName);
+      const deleted = await invoke<number>('delete_all_documents_in_index', { index: indexName, confirmationToken });
       return deleted;
     } catch (error) {
       console.error(`Failed to delete documents in index ${indexName}:`, error);
@@ -269,9 +282,11 @@ export class ElasticsearchService {
 
     try {
       // Call the Rust backend to delete the specified documents
+      const confirmationToken = await this.requestConfirmationToken('delete_elasticsearch_documents', indexName);
       const deleted = await invoke<number>('delete_elasticsearch_documents', { 
         index: indexName,
-        docIds
+        docIds,
+        confirmationToken
       });
       return deleted;
     } catch (error) {